@@ -1,8 +1,297 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use anchor_spl::token_interface::{self, Burn, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
 
 declare_id!("SoMC111111111111111111111111111111111111111");
 
+/// Share of a device's stake slashed when it lets an assigned task expire (basis points)
+pub const EXPIRY_SLASH_BPS: u64 = 100;
+/// Share of the slashed stake paid to whoever calls expire_task (basis points)
+pub const KEEPER_REWARD_BPS: u64 = 1000;
+/// Share of a device's stake slashed when its result fails verification (basis points)
+pub const VERIFICATION_SLASH_BPS: u64 = 1000;
+/// Stake a verifier must have free, and which is locked for the duration of each vote
+pub const MIN_VERIFIER_STAKE: u64 = 1000;
+/// Share of a verifier's stake slashed when its vote disagrees with the final outcome (basis points)
+pub const VERIFIER_SLASH_BPS: u64 = 500;
+/// Seconds after completion during which verifiers may commit a hidden vote
+pub const COMMIT_PERIOD: i64 = 60 * 60;
+/// Seconds after the commit phase during which verifiers reveal their votes
+pub const REVEAL_PERIOD: i64 = 60 * 60;
+/// Seconds an optimistic result can be challenged before it is accepted
+pub const CHALLENGE_WINDOW: i64 = 24 * 60 * 60;
+/// Tokens a challenger must bond to dispute an optimistic result
+pub const CHALLENGE_BOND: u64 = 1000;
+/// Revealed votes needed before a verification round can be decided
+pub const VERIFICATION_QUORUM: u8 = 3;
+/// Number of verifiers drawn from the pool for each task
+pub const COMMITTEE_SIZE: usize = 5;
+/// Seconds after a rejection during which the device may appeal
+pub const APPEAL_WINDOW: i64 = 24 * 60 * 60;
+/// Tokens a device must bond to appeal a rejected result
+pub const APPEAL_BOND: u64 = 2000;
+/// Share of each task reward set aside for the verifiers who vote with the outcome (basis points).
+/// Pools of tasks accepted without a vote stay in the reward vault.
+pub const VERIFICATION_FEE_BPS: u64 = 500;
+/// Revealed votes needed to decide an appeal round
+pub const APPEAL_QUORUM: u8 = 5;
+/// Number of verifiers drawn for an appeal round
+pub const APPEAL_COMMITTEE_SIZE: usize = 9;
+/// Maximum number of subtasks a task can be split into
+pub const MAX_SUBTASKS: usize = 8;
+/// Maximum number of stages in a task pipeline
+pub const MAX_PIPELINE_STAGES: usize = 8;
+/// Maximum length of a recurring task id
+pub const MAX_RECURRING_ID_LEN: usize = 21;
+/// Task ids starting with this are reserved for recurring task instances, so nobody can take a run's address first
+pub const RECURRING_INSTANCE_PREFIX: char = '~';
+/// Maximum depth of a device allowlist merkle proof
+pub const MAX_ALLOWLIST_PROOF_LEN: usize = 16;
+/// GPU numeric precision flags for DeviceSpecs::gpu_precisions and ComputeRequirements::required_gpu_precisions
+pub const GPU_PRECISION_FP32: u8 = 1 << 0;
+pub const GPU_PRECISION_FP16: u8 = 1 << 1;
+pub const GPU_PRECISION_BF16: u8 = 1 << 2;
+pub const GPU_PRECISION_INT8: u8 = 1 << 3;
+pub const GPU_PRECISION_INT4: u8 = 1 << 4;
+/// Instructions a device owner can delegate to an agent key through AgentAuthority::permissions
+pub const AGENT_PERMISSION_HEARTBEAT: u8 = 1 << 0;
+pub const AGENT_PERMISSION_START: u8 = 1 << 1;
+pub const AGENT_PERMISSION_COMPLETE: u8 = 1 << 2;
+pub const AGENT_PERMISSIONS_ALL: u8 = AGENT_PERMISSION_HEARTBEAT | AGENT_PERMISSION_START | AGENT_PERMISSION_COMPLETE;
+/// Maximum number of votes committed or revealed in a single batch instruction
+pub const MAX_VERIFY_BATCH: usize = 16;
+/// Verifier attestations needed to accept or reject a benchmark result
+pub const BENCHMARK_QUORUM: u8 = 3;
+/// Enough attesters for either outcome to reach quorum
+pub const MAX_BENCHMARK_ATTESTERS: usize = 2 * BENCHMARK_QUORUM as usize - 1;
+/// Maximum number of devices registered as verifiers
+pub const MAX_VERIFIER_POOL_SIZE: usize = 64;
+/// Maximum number of trusted hardware attester keys
+pub const MAX_ATTESTERS: usize = 16;
+/// Maximum number of admin council members
+pub const MAX_COUNCIL_MEMBERS: usize = 10;
+/// Maximum number of accounts and data bytes an admin proposal's instruction may carry
+pub const MAX_ADMIN_ACCOUNTS: usize = 16;
+pub const MAX_ADMIN_DATA_LEN: usize = 512;
+/// Longest delay the timelock on config changes and treasury withdrawals can be set to
+pub const MAX_TIMELOCK_DELAY: i64 = 30 * 24 * 60 * 60;
+/// Stake a device needs before its owner can open a governance proposal
+pub const MIN_PROPOSAL_STAKE: u64 = 5000;
+/// How long stakers can vote on a governance proposal
+pub const GOVERNANCE_VOTING_PERIOD: i64 = 3 * 24 * 60 * 60;
+/// Delay between the end of voting and when a passed proposal can be executed
+pub const GOVERNANCE_TIMELOCK: i64 = 2 * 24 * 60 * 60;
+/// Share of the online stake at proposal time that must vote for the result to count (basis points)
+pub const GOVERNANCE_QUORUM_BPS: u64 = 1000;
+/// Size of a task key sealed to a device: 24-byte nonce, 32-byte key and 16-byte tag
+pub const ENCRYPTED_KEY_LEN: usize = 72;
+/// Largest task input that can be stored on-chain in a TaskData account
+pub const MAX_TASK_DATA_LEN: usize = 8 * 1024;
+/// Largest chunk one append_task_data call may write, to stay within transaction size
+pub const MAX_DATA_CHUNK_LEN: usize = 900;
+/// Maximum length of a result URI
+pub const MAX_RESULT_URI_LEN: usize = 96;
+/// Maximum length of a result MIME type
+pub const MAX_RESULT_MIME_LEN: usize = 32;
+/// URI schemes a result may be stored under
+pub const RESULT_URI_SCHEMES: [&str; 3] = ["ipfs://", "ar://", "https://"];
+/// Share of a failed task's escrow withheld as a penalty when refunding the submitter (basis points)
+pub const FAILED_TASK_PENALTY_BPS: u64 = 500;
+/// Seconds without a progress report after which an in-progress task counts as stalled
+pub const HEARTBEAT_TIMEOUT: i64 = 10 * 60;
+/// Minimum seconds between two availability challenges to the same device
+pub const AVAILABILITY_CHALLENGE_INTERVAL: i64 = 60 * 60;
+/// Seconds a device has to answer an availability challenge
+pub const AVAILABILITY_RESPONSE_WINDOW: i64 = 5 * 60;
+/// Uptime credit taken away for each missed availability challenge (seconds)
+pub const AVAILABILITY_UPTIME_PENALTY: u64 = 60 * 60;
+/// Consecutive missed availability challenges after which a device is deactivated
+pub const MAX_AVAILABILITY_FAILURES: u8 = 3;
+/// Maximum number of tasks or devices one crank call processes
+pub const MAX_CRANK_BATCH: usize = 8;
+/// Idle time after which a device's reputation starts decaying, and the spacing between decay steps
+pub const REPUTATION_DECAY_INTERVAL: i64 = 7 * 24 * 60 * 60;
+/// Share of a device's reputation above the starting score lost per decay step (basis points)
+pub const REPUTATION_DECAY_BPS: u64 = 500;
+/// Reputation every device registers with; decay never goes below it
+pub const BASE_REPUTATION: u16 = 100;
+/// Seconds after completion before an unverified task may be closed by its submitter
+pub const TASK_RETENTION_PERIOD: i64 = 30 * 24 * 60 * 60;
+/// Seconds after settlement verifiers and replicas have to claim their shares; a task can then be
+/// closed without them and whatever they left in its SOL escrow goes to the treasury
+pub const REWARD_CLAIM_PERIOD: i64 = 30 * 24 * 60 * 60;
+/// Minimum seconds between two spec updates of the same device
+pub const SPECS_UPDATE_COOLDOWN: i64 = 24 * 60 * 60;
+/// Seconds an integrity verdict stays valid before the device must be checked again
+pub const INTEGRITY_VALIDITY: i64 = 7 * 24 * 60 * 60;
+/// Seconds a device has to accept a task assigned to it by a third party
+pub const ACCEPT_TIMEOUT: i64 = 5 * 60;
+/// How many times a failed task may be put back up for assignment
+pub const MAX_RETRIES: u8 = 3;
+/// Maximum number of devices that can redundantly execute one task
+pub const MAX_REDUNDANCY: u8 = 5;
+/// Highest self-reported load (percent) at which a device may still claim new work
+pub const MAX_CLAIM_LOAD: u8 = 80;
+/// Upper bound on how much task difficulty can scale a reputation change (basis points)
+pub const MAX_REPUTATION_WEIGHT_BPS: u64 = 40000;
+/// Number of highest-priority pending tasks kept in the priority index
+pub const MAX_PRIORITY_INDEX_SIZE: usize = 32;
+/// Number of pending tasks listed in each per-type pending index
+pub const MAX_PENDING_INDEX_SIZE: usize = 64;
+/// Number of devices ranked on each of an epoch's leaderboards
+pub const LEADERBOARD_SIZE: usize = 10;
+/// Highest commission a pool operator may take from member rewards (basis points)
+pub const MAX_POOL_COMMISSION_BPS: u16 = 5000;
+/// Mints besides the network's reward mint that tasks may be paid in
+pub const MAX_REWARD_MINTS: usize = 4;
+/// Pyth Solana receiver program, owner of the PriceUpdateV2 accounts USD-priced tasks settle against
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+/// Anchor discriminator of the receiver's PriceUpdateV2 account
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+/// Fixed-point scale of the staking yield accumulator
+pub const YIELD_PRECISION: u128 = 1_000_000_000;
+/// SPL account compression program, which keeps the concurrent merkle tree of archived tasks
+pub const SPL_ACCOUNT_COMPRESSION_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+/// SPL noop program the compression program logs tree changes through
+pub const SPL_NOOP_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+/// Anchor discriminators of the compression program's init_empty_merkle_tree and append instructions
+pub const INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR: [u8; 8] = [191, 11, 119, 7, 180, 107, 220, 110];
+pub const APPEND_LEAF_DISCRIMINATOR: [u8; 8] = [149, 120, 18, 222, 236, 225, 88, 203];
+/// Metaplex Bubblegum program, which mints achievement badges as compressed NFTs
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+/// Anchor discriminators of Bubblegum's create_tree and mint_v1 instructions
+pub const CREATE_TREE_DISCRIMINATOR: [u8; 8] = [165, 83, 136, 142, 89, 202, 47, 220];
+pub const MINT_V1_DISCRIMINATOR: [u8; 8] = [145, 98, 192, 118, 184, 147, 118, 104];
+/// Maximum length of the URI prefix badge metadata is served under
+pub const MAX_BADGE_URI_LEN: usize = 128;
+/// Metaplex Token Metadata program, which holds the metadata of device license NFTs
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+/// Token Metadata instruction indices of CreateMetadataAccountV3 and CreateMasterEditionV3
+pub const CREATE_METADATA_V3_INSTRUCTION: u8 = 33;
+pub const CREATE_MASTER_EDITION_V3_INSTRUCTION: u8 = 17;
+
+/// Addresses for programs that submit tasks through CPI. Depend on this crate with the `cpi`
+/// feature, build calls with the generated `cpi` module and derive their accounts here.
+///
+/// A program-derived submitter signs with `CpiContext::new_with_signer` and pays token rewards
+/// from a token account it owns. SOL rewards are moved with a system transfer, so for those the
+/// submitter PDA must hold no data. Rent always comes from the separate `payer`.
+///
+/// The account order of submit_task, cancel_task and close_task is part of this interface: accounts
+/// are only ever appended. The example below fails if an existing account moves.
+///
+/// ```
+/// # use anchor_lang::prelude::Pubkey;
+/// # use anchor_lang::ToAccountMetas;
+/// # use solmobile_compute::accounts;
+/// fn order(metas: impl ToAccountMetas) -> Vec<Pubkey> {
+///     metas.to_account_metas(None).iter().map(|meta| meta.pubkey).collect()
+/// }
+/// let k: Vec<Pubkey> = (0..17).map(|_| Pubkey::new_unique()).collect();
+///
+/// let submit = accounts::SubmitTask {
+///     task_account: k[0],
+///     submitter: k[1],
+///     submitter_profile: k[2],
+///     submitter_token_account: Some(k[3]),
+///     credit_account: Some(k[4]),
+///     sol_escrow: Some(k[5]),
+///     reward_vault: k[6],
+///     queue_tail: Some(k[7]),
+///     pending_index: k[8],
+///     priority_index: k[9],
+///     network_state: k[10],
+///     network_config: k[11],
+///     treasury: k[12],
+///     reward_mint: k[13],
+///     token_program: k[14],
+///     system_program: k[15],
+///     payer: k[16],
+/// };
+/// assert_eq!(order(submit), k);
+///
+/// let cancel = accounts::CancelTask {
+///     task_account: k[0],
+///     submitter: k[1],
+///     submitter_profile: k[2],
+///     submitter_token_account: Some(k[3]),
+///     sol_escrow: Some(k[4]),
+///     reward_vault: k[5],
+///     queue_prev: Some(k[6]),
+///     queue_next: Some(k[7]),
+///     pending_index: k[8],
+///     priority_index: k[9],
+///     network_state: k[10],
+///     reward_mint: k[11],
+///     token_program: k[12],
+///     system_program: k[13],
+/// };
+/// assert_eq!(order(cancel), k[..14]);
+///
+/// let close = accounts::CloseTask {
+///     task_account: k[0],
+///     submitter: k[1],
+///     sol_escrow: Some(k[2]),
+///     network_state: k[3],
+///     system_program: k[4],
+/// };
+/// assert_eq!(order(close), k[..5]);
+/// ```
+pub mod interface {
+    use super::*;
+    
+    pub fn task_address(task_id: &str) -> Pubkey {
+        Pubkey::find_program_address(&[b"task", task_id.as_bytes()], &crate::ID).0
+    }
+    
+    pub fn submitter_profile_address(submitter: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"submitter", submitter.as_ref()], &crate::ID).0
+    }
+    
+    pub fn credit_account_address(submitter: &Pubkey, mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"credits", submitter.as_ref(), mint.as_ref()], &crate::ID).0
+    }
+    
+    pub fn sol_escrow_address(task: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"sol_escrow", task.as_ref()], &crate::ID).0
+    }
+    
+    pub fn reward_vault_address(mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"reward_vault", mint.as_ref()], &crate::ID).0
+    }
+    
+    pub fn treasury_address(mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"treasury", mint.as_ref()], &crate::ID).0
+    }
+    
+    pub fn pending_index_address(task_type: TaskType) -> Pubkey {
+        Pubkey::find_program_address(&[b"pending_index", &[task_type as u8]], &crate::ID).0
+    }
+    
+    pub fn priority_index_address() -> Pubkey {
+        Pubkey::find_program_address(&[b"priority_index"], &crate::ID).0
+    }
+    
+    pub fn network_state_address() -> Pubkey {
+        Pubkey::find_program_address(&[b"network_state"], &crate::ID).0
+    }
+    
+    pub fn network_config_address() -> Pubkey {
+        Pubkey::find_program_address(&[b"network_config"], &crate::ID).0
+    }
+    
+    pub fn recurring_task_address(submitter: &Pubkey, recurring_id: &str) -> Pubkey {
+        Pubkey::find_program_address(&[b"recurring", submitter.as_ref(), recurring_id.as_bytes()], &crate::ID).0
+    }
+}
+
 #[program]
 pub mod solmobile_compute {
     use super::*;
@@ -14,567 +303,14778 @@ pub mod solmobile_compute {
         network_state.total_tasks_completed = 0;
         network_state.total_tokens_distributed = 0;
         network_state.network_utilization = 0;
-        Ok(())
-    }
-
-    pub fn register_device(
-        ctx: Context<RegisterDevice>,
-        device_id: String,
-        device_specs: DeviceSpecs,
-    ) -> Result<()> {
-        let device_account = &mut ctx.accounts.device_account;
-        let network_state = &mut ctx.accounts.network_state;
-        let clock = Clock::get()?;
+        network_state.active_devices = 0;
+        network_state.pending_tasks = 0;
+        network_state.queue_head = Pubkey::default();
+        network_state.queue_tail = Pubkey::default();
+        network_state.queue_len = 0;
+        network_state.timelock_authority = Pubkey::default();
+        network_state.total_completion_seconds = 0;
+        network_state.timed_completions = 0;
+        network_state.in_flight_assignments = 0;
+        network_state.pending_burn = 0;
+        network_state.total_burned = 0;
+        network_state.sol_fees = 0;
+        network_state.receipt_supply = 0;
+        network_state.receipted_stake = 0;
         
-        device_account.owner = ctx.accounts.owner.key();
-        device_account.device_id = device_id;
-        device_account.specs = device_specs;
-        device_account.is_active = true;
-        device_account.reputation_score = 100;
-        device_account.total_tasks_completed = 0;
-        device_account.total_tokens_earned = 0;
-        device_account.last_active = clock.unix_timestamp;
-        device_account.tier = DeviceTier::Bronze;
-        device_account.staked_amount = 0;
-        device_account.stake_timestamp = 0;
-        device_account.total_verifications = 0;
+        let network_config = &mut ctx.accounts.network_config;
+        network_config.tier_thresholds = [1000, 5000, 20000];
+        network_config.unstake_period = 7 * 24 * 60 * 60;
+        network_config.expiry_multiplier = 2;
+        network_config.speed_bonus_bps = 1000;
+        network_config.verification_threshold_bps = 6666;
+        network_config.protocol_fee_bps = 100;
+        network_config.matcher = ctx.accounts.authority.key();
+        network_config.storage_oracle = ctx.accounts.authority.key();
+        network_config.availability_emission = 0;
+        network_config.availability_reward = 0;
+        network_config.submitter_allowlist_enabled = false;
+        network_config.initial_epoch_mint = 0;
+        network_config.emission_decay_bps = 0;
+        network_config.burn_bps = 0;
+        network_config.usd_price_feed_id = [0; 32];
+        network_config.max_price_age = 60;
+        network_config.max_price_conf_bps = 200;
+        network_config.leaderboard_prize = 0;
+        network_config.accepted_mints = [AcceptedMint::default(); MAX_REWARD_MINTS];
+        network_config.collateral_bps = 5000;
+        network_config.epoch_duration = 24 * 60 * 60;
+        network_config.epoch_emission = 0;
+        network_config.vesting_cliff = 0;
+        network_config.vesting_duration = 0;
+        network_config.tier_reward_bps = [10000, 10500, 11000, 11500];
+        network_config.surge_threshold = 80;
+        network_config.max_surge_bps = 15000;
+        network_config.task_type_reputation_bps = [10000, 15000, 12500, 20000, 10000];
+        network_config.gpu_reputation_bps = 5000;
+        network_config.reputation_reward_unit = 1000;
+        network_config.high_value_reward = 10000;
+        network_config.high_value_min_reputation = 150;
+        network_config.max_disputes_lost = 10;
+        network_config.submission_fee = 10;
+        network_config.rate_limit_window_slots = 150;
+        network_config.max_submissions_per_window = 10;
+        network_config.min_reward_by_type = [100, 500, 250, 1000, 100];
+        network_config.max_deadline_extension = 24 * 60 * 60;
+        network_config.grace_period = 60 * 60;
+        network_config.late_reward_floor_bps = 5000;
+        network_config.max_concurrent_by_tier = [1, 2, 4, 8];
+        network_config.tier_benchmark_scores = [100, 500, 2000];
         
-        network_state.total_devices += 1;
+        let epoch_state = &mut ctx.accounts.epoch_state;
+        epoch_state.epoch = 0;
+        epoch_state.epoch_started_at = Clock::get()?.unix_timestamp;
+        epoch_state.reward_per_share = 0;
+        epoch_state.total_weight = 0;
+        epoch_state.unclaimed_yield = 0;
+        epoch_state.availability_budget = 0;
+        epoch_state.tasks_completed_at_start = 0;
+        epoch_state.tokens_distributed_at_start = 0;
+        epoch_state.completion_seconds_at_start = 0;
+        epoch_state.timed_completions_at_start = 0;
+        epoch_state.next_mint_epoch = 0;
         
-        msg!("Device registered successfully: {}", device_account.device_id);
+        emit!(NetworkInitialized {
+            authority: network_state.authority,
+        });
         Ok(())
     }
 
-    pub fn submit_task(
-        ctx: Context<SubmitTask>,
-        task_id: String,
-        task_type: TaskType,
-        compute_requirements: ComputeRequirements,
-        reward_amount: u64,
+    /// Changes the fields set in `update`; the merged config must still pass NetworkConfig::validate
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        update: ConfigUpdate,
     ) -> Result<()> {
-        let task_account = &mut ctx.accounts.task_account;
-        let clock = Clock::get()?;
+        let network_config = &mut ctx.accounts.network_config;
+        update.apply(network_config);
+        network_config.validate()?;
         
-        task_account.submitter = ctx.accounts.submitter.key();
-        task_account.task_id = task_id;
-        task_account.task_type = task_type;
-        task_account.compute_requirements = compute_requirements;
-        task_account.reward_amount = reward_amount;
-        task_account.status = TaskStatus::Pending;
-        task_account.created_at = clock.unix_timestamp;
-        task_account.assigned_at = 0;
-        task_account.completed_at = 0;
-        task_account.expires_at = 0;
-        task_account.result_hash = String::new();
-        task_account.verifications = 0;
-        task_account.valid_verifications = 0;
-        task_account.is_verified = false;
-        task_account.assigned_device = None;
+        emit!(NetworkConfigUpdated {
+            tier_thresholds: network_config.tier_thresholds,
+            unstake_period: network_config.unstake_period,
+            expiry_multiplier: network_config.expiry_multiplier,
+            speed_bonus_bps: network_config.speed_bonus_bps,
+            verification_threshold_bps: network_config.verification_threshold_bps,
+            protocol_fee_bps: network_config.protocol_fee_bps,
+            matcher: network_config.matcher,
+            collateral_bps: network_config.collateral_bps,
+            epoch_duration: network_config.epoch_duration,
+            epoch_emission: network_config.epoch_emission,
+            vesting_cliff: network_config.vesting_cliff,
+            vesting_duration: network_config.vesting_duration,
+            tier_reward_bps: network_config.tier_reward_bps,
+            surge_threshold: network_config.surge_threshold,
+            max_surge_bps: network_config.max_surge_bps,
+            task_type_reputation_bps: network_config.task_type_reputation_bps,
+            gpu_reputation_bps: network_config.gpu_reputation_bps,
+            reputation_reward_unit: network_config.reputation_reward_unit,
+            high_value_reward: network_config.high_value_reward,
+            high_value_min_reputation: network_config.high_value_min_reputation,
+            max_disputes_lost: network_config.max_disputes_lost,
+            submission_fee: network_config.submission_fee,
+            rate_limit_window_slots: network_config.rate_limit_window_slots,
+            max_submissions_per_window: network_config.max_submissions_per_window,
+            min_reward_by_type: network_config.min_reward_by_type,
+            max_deadline_extension: network_config.max_deadline_extension,
+            grace_period: network_config.grace_period,
+            late_reward_floor_bps: network_config.late_reward_floor_bps,
+            max_concurrent_by_tier: network_config.max_concurrent_by_tier,
+            tier_benchmark_scores: network_config.tier_benchmark_scores,
+            storage_oracle: network_config.storage_oracle,
+            availability_emission: network_config.availability_emission,
+            availability_reward: network_config.availability_reward,
+            submitter_allowlist_enabled: network_config.submitter_allowlist_enabled,
+            initial_epoch_mint: network_config.initial_epoch_mint,
+            emission_decay_bps: network_config.emission_decay_bps,
+            burn_bps: network_config.burn_bps,
+            usd_price_feed_id: network_config.usd_price_feed_id,
+            max_price_age: network_config.max_price_age,
+            max_price_conf_bps: network_config.max_price_conf_bps,
+            leaderboard_prize: network_config.leaderboard_prize,
+        });
         
-        msg!("Task submitted: {} with reward: {}", task_account.task_id, reward_amount);
+        msg!("Network config updated");
         Ok(())
     }
 
-    pub fn assign_task(
-        ctx: Context<AssignTask>,
-        task_id: String,
-    ) -> Result<()> {
-        let task_account = &mut ctx.accounts.task_account;
-        let device_account = &mut ctx.accounts.device_account;
-        let clock = Clock::get()?;
-        
-        require!(task_account.status == TaskStatus::Pending, ComputeError::TaskNotPending);
-        require!(device_account.is_active, ComputeError::DeviceNotActive);
-        
-        // Check device capabilities match task requirements
-        let cpu_cores_required = task_account.compute_requirements.cpu_cores_required;
-        let ram_gb_required = task_account.compute_requirements.ram_gb_required;
-        let storage_gb_required = task_account.compute_requirements.storage_gb_required;
-        let gpu_required = task_account.compute_requirements.gpu_required;
-        let estimated_duration = task_account.compute_requirements.estimated_duration;
-        
-        let specs = &device_account.specs;
-        require!(
-            specs.cpu_cores >= cpu_cores_required &&
-            specs.ram_gb >= ram_gb_required &&
-            specs.storage_gb >= storage_gb_required &&
-            (!gpu_required || specs.gpu_available),
-            ComputeError::InsufficientCapabilities
-        );
-        
-        // Check device tier for task eligibility
-        let min_tier = match task_account.task_type {
-            TaskType::DataProcessing => DeviceTier::Bronze,
-            TaskType::MLInference => DeviceTier::Silver,
-            TaskType::ImageProcessing => DeviceTier::Silver,
-            TaskType::VideoTranscoding => DeviceTier::Gold,
-            TaskType::GeneralCompute => DeviceTier::Bronze,
-        };
-        require!(device_account.tier >= min_tier, ComputeError::InsufficientTier);
+    pub fn initialize_vaults(ctx: Context<InitializeVaults>) -> Result<()> {
+        // The vaults and the treasury are PDAs owned by network_state, so every instruction can pin them by seeds
+        let network_state = &mut ctx.accounts.network_state;
+        network_state.reward_mint = ctx.accounts.mint.key();
+        network_state.reward_vault = ctx.accounts.reward_vault.key();
+        network_state.stake_vault = ctx.accounts.stake_vault.key();
         
-        task_account.assigned_device = Some(device_account.key());
-        task_account.status = TaskStatus::Assigned;
-        task_account.assigned_at = clock.unix_timestamp;
-        task_account.expires_at = clock.unix_timestamp + estimated_duration as i64 * 2; // 2x estimated time
+        emit!(VaultsInitialized {
+            mint: network_state.reward_mint,
+            reward_vault: network_state.reward_vault,
+            stake_vault: network_state.stake_vault,
+        });
         
-        msg!("Task {} assigned to device {}", task_id, device_account.device_id);
+        msg!("Vaults initialized for mint {}", network_state.reward_mint);
         Ok(())
     }
 
-    pub fn complete_task(
-        ctx: Context<CompleteTask>,
-        task_id: String,
-        result_hash: String,
+    /// Creates the network mint's treasury PDA on networks whose vaults were initialized before
+    /// treasuries were pinned by seeds. Move any balance over from the old treasury first.
+    pub fn init_treasury(ctx: Context<InitTreasury>) -> Result<()> {
+        msg!("Treasury {} created for mint {}", ctx.accounts.treasury.key(), ctx.accounts.reward_mint.key());
+        Ok(())
+    }
+
+    /// Creates the reward mint as a PDA whose mint authority is network_state, so new supply only
+    /// enters through mint_epoch_emissions. Pass it to initialize_vaults as the network's mint.
+    pub fn initialize_reward_mint(
+        ctx: Context<InitializeRewardMint>,
+        decimals: u8,
     ) -> Result<()> {
-        let task_account = &mut ctx.accounts.task_account;
-        let device_account = &mut ctx.accounts.device_account;
-        let clock = Clock::get()?;
+        msg!("Reward mint {} created with {} decimals", ctx.accounts.reward_mint.key(), decimals);
+        Ok(())
+    }
+    
+    /// Mints the current epoch's scheduled emission into the treasury, which funds the staking
+    /// yield and availability budgets advance_epoch hands out. Permissionless, once per epoch;
+    /// epochs nobody minted for are skipped.
+    pub fn mint_epoch_emissions(ctx: Context<MintEpochEmissions>) -> Result<()> {
+        let epoch_state = &mut ctx.accounts.epoch_state;
+        require!(epoch_state.epoch >= epoch_state.next_mint_epoch, ComputeError::EmissionsAlreadyMinted);
         
-        require!(task_account.status == TaskStatus::Assigned, ComputeError::TaskNotAssigned);
-        require!(task_account.assigned_device == Some(device_account.key()), ComputeError::DeviceNotAssigned);
+        let epoch = epoch_state.epoch;
+        let amount = ctx.accounts.network_config.scheduled_mint(epoch);
+        epoch_state.next_mint_epoch = epoch + 1;
         
-        // Check task expiration
-        if task_account.expires_at < clock.unix_timestamp {
-            task_account.status = TaskStatus::Failed;
-            device_account.reputation_score = device_account.reputation_score.saturating_sub(10);
-            return Err(ComputeError::TaskExpired.into());
+        if amount > 0 {
+            let seeds = &[
+                b"network_state".as_ref(),
+                &[ctx.bumps.network_state]
+            ];
+            let signer_seeds = &[&seeds[..]];
+            let cpi_accounts = MintTo {
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.network_state.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_interface::mint_to(cpi_ctx, amount)?;
         }
         
-        task_account.status = TaskStatus::Completed;
-        task_account.result_hash = result_hash;
-        task_account.completed_at = clock.unix_timestamp;
-        
-        // Calculate performance bonus
-        let time_taken = clock.unix_timestamp - task_account.assigned_at;
-        let estimated_time = task_account.compute_requirements.estimated_duration as i64;
-        let performance_multiplier = if time_taken < estimated_time {
-            110 // 10% bonus for faster completion
-        } else {
-            100
-        };
+        emit!(EmissionsMinted {
+            epoch,
+            amount,
+        });
         
-        let adjusted_reward = task_account.reward_amount
-            .checked_mul(performance_multiplier)
-            .ok_or(ComputeError::MathOverflow)?
-            .checked_div(100)
-            .ok_or(ComputeError::MathOverflow)?;
+        msg!("Minted {} reward tokens for epoch {}", amount, epoch);
+        Ok(())
+    }
+
+    /// Burns the treasury tokens earmarked by accrue_burn. Permissionless; burns what the
+    /// treasury still holds if it is short.
+    pub fn burn_pending(ctx: Context<BurnPending>) -> Result<()> {
+        let amount = ctx.accounts.network_state.pending_burn.min(ctx.accounts.treasury.amount);
+        require!(amount > 0, ComputeError::NothingToBurn);
         
-        // Transfer tokens to device owner
         let seeds = &[
             b"network_state".as_ref(),
             &[ctx.bumps.network_state]
         ];
         let signer_seeds = &[&seeds[..]];
-        
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.reward_vault.to_account_info(),
-            to: ctx.accounts.device_token_account.to_account_info(),
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            from: ctx.accounts.treasury.to_account_info(),
             authority: ctx.accounts.network_state.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        token::transfer(cpi_ctx, adjusted_reward)?;
+        token_interface::burn(cpi_ctx, amount)?;
         
-        device_account.total_tasks_completed += 1;
-        device_account.total_tokens_earned += adjusted_reward;
-        device_account.last_active = clock.unix_timestamp;
-        device_account.reputation_score = device_account.reputation_score.saturating_add(5);
+        let network_state = &mut ctx.accounts.network_state;
+        network_state.pending_burn -= amount;
+        network_state.total_burned += amount;
         
-        ctx.accounts.network_state.total_tasks_completed += 1;
-        ctx.accounts.network_state.total_tokens_distributed += adjusted_reward;
+        emit!(TokensBurned {
+            amount,
+            total_burned: network_state.total_burned,
+        });
         
-        msg!("Task {} completed by device {} with reward {}", task_id, device_account.device_id, adjusted_reward);
+        msg!("Burned {} reward tokens, {} in total", amount, network_state.total_burned);
         Ok(())
     }
 
-    pub fn update_device_status(
-        ctx: Context<UpdateDeviceStatus>,
-        is_active: bool,
-        current_load: u8,
+    pub fn withdraw_treasury(
+        ctx: Context<WithdrawTreasury>,
+        amount: u64,
     ) -> Result<()> {
-        let device_account = &mut ctx.accounts.device_account;
+        let seeds = &[
+            b"network_state".as_ref(),
+            &[ctx.bumps.network_state]
+        ];
+        let signer_seeds = &[&seeds[..]];
         
-        device_account.is_active = is_active;
-        device_account.current_load = current_load;
-        device_account.last_active = Clock::get()?.unix_timestamp;
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.treasury.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.network_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.reward_mint.decimals)?;
         
-        msg!("Device {} status updated: active={}, load={}", 
-            device_account.device_id, is_active, current_load);
+        emit!(TreasuryWithdrawn {
+            destination: ctx.accounts.destination.key(),
+            amount,
+        });
+        
+        msg!("Withdrew {} from treasury", amount);
         Ok(())
     }
     
-    pub fn stake_tokens(
-        ctx: Context<StakeTokens>,
+    /// Withdraws protocol fees collected from SOL-paid tasks, which accrue as lamports on network_state
+    pub fn withdraw_sol_fees(
+        ctx: Context<WithdrawSolFees>,
         amount: u64,
     ) -> Result<()> {
-        let device_account = &mut ctx.accounts.device_account;
-        let clock = Clock::get()?;
+        let network_state = &mut ctx.accounts.network_state;
+        require!(amount <= network_state.sol_fees, ComputeError::InsufficientSolFees);
+        network_state.sol_fees -= amount;
+        network_state.sub_lamports(amount)?;
+        ctx.accounts.destination.add_lamports(amount)?;
         
-        // Transfer tokens from device owner to stake vault
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.owner_token_account.to_account_info(),
-            to: ctx.accounts.stake_vault.to_account_info(),
-            authority: ctx.accounts.owner.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, amount)?;
+        emit!(SolFeesWithdrawn {
+            destination: ctx.accounts.destination.key(),
+            amount,
+        });
         
-        device_account.staked_amount += amount;
-        device_account.stake_timestamp = clock.unix_timestamp;
+        msg!("Withdrew {} lamports of SOL fees", amount);
+        Ok(())
+    }
+    
+    /// Accepts `mint` for task rewards, creating its reward vault, or updates its floors if it is
+    /// already accepted. `min_reward` and `submission_fee` are in the mint's own base units.
+    pub fn add_reward_mint(
+        ctx: Context<AddRewardMint>,
+        min_reward: u64,
+        submission_fee: u64,
+    ) -> Result<()> {
+        let mint = ctx.accounts.mint.key();
+        require!(mint != ctx.accounts.network_state.reward_mint, ComputeError::InvalidConfig);
         
-        // Update device tier based on staked amount
-        device_account.tier = match device_account.staked_amount {
-            0..=1000 => DeviceTier::Bronze,
-            1001..=5000 => DeviceTier::Silver,
-            5001..=20000 => DeviceTier::Gold,
-            _ => DeviceTier::Platinum,
+        let accepted_mints = &mut ctx.accounts.network_config.accepted_mints;
+        let index = accepted_mints.iter()
+            .position(|entry| entry.mint == mint)
+            .or_else(|| accepted_mints.iter().position(|entry| entry.mint == Pubkey::default()))
+            .ok_or(ComputeError::RewardMintListFull)?;
+        accepted_mints[index] = AcceptedMint {
+            mint,
+            min_reward,
+            submission_fee,
         };
         
-        msg!("Device {} staked {} tokens, new tier: {:?}", 
-            device_account.device_id, amount, device_account.tier);
+        emit!(RewardMintUpdated {
+            mint,
+            accepted: true,
+            min_reward,
+            submission_fee,
+        });
+        
+        msg!("Reward mint {} accepted", mint);
         Ok(())
     }
     
-    pub fn unstake_tokens(
-        ctx: Context<UnstakeTokens>,
-        amount: u64,
+    /// Stops accepting `mint` for new tasks; tasks already escrowed in it still settle from its vault
+    pub fn remove_reward_mint(
+        ctx: Context<RemoveRewardMint>,
+        mint: Pubkey,
     ) -> Result<()> {
-        let device_account = &mut ctx.accounts.device_account;
-        let clock = Clock::get()?;
+        let entry = ctx.accounts.network_config.accepted_mints.iter_mut()
+            .find(|entry| entry.mint == mint && mint != Pubkey::default())
+            .ok_or(ComputeError::RewardMintNotAccepted)?;
+        *entry = AcceptedMint::default();
         
-        require!(device_account.staked_amount >= amount, ComputeError::InsufficientStake);
+        emit!(RewardMintUpdated {
+            mint,
+            accepted: false,
+            min_reward: 0,
+            submission_fee: 0,
+        });
         
-        // Check minimum staking period (7 days)
-        let staking_duration = clock.unix_timestamp - device_account.stake_timestamp;
-        require!(staking_duration >= 7 * 24 * 60 * 60, ComputeError::StakingPeriodNotMet);
+        msg!("Reward mint {} removed", mint);
+        Ok(())
+    }
+    
+    /// Hands the network authority to an M-of-N admin council. From then on authority-gated
+    /// instructions only run through execute_admin_action, signed by the admin_authority PDA.
+    pub fn initialize_admin_council(
+        ctx: Context<InitializeAdminCouncil>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        AdminCouncil::validate(&members, threshold)?;
         
-        // Transfer tokens from stake vault to device owner
-        let seeds = &[
-            b"network_state".as_ref(),
-            &[ctx.bumps.network_state]
-        ];
-        let signer_seeds = &[&seeds[..]];
+        let admin_council = &mut ctx.accounts.admin_council;
+        admin_council.members = members.clone();
+        admin_council.threshold = threshold;
+        admin_council.proposal_count = 0;
+        ctx.accounts.network_state.authority = ctx.accounts.admin_authority.key();
         
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.stake_vault.to_account_info(),
-            to: ctx.accounts.owner_token_account.to_account_info(),
-            authority: ctx.accounts.network_state.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-        token::transfer(cpi_ctx, amount)?;
+        emit!(AdminCouncilUpdated {
+            members,
+            threshold,
+        });
         
-        device_account.staked_amount -= amount;
+        msg!("Admin council initialized, {}-of-{}", threshold, admin_council.members.len());
+        Ok(())
+    }
+    
+    /// Replaces the council's members and threshold; only reachable through an executed proposal.
+    /// Approvals from removed members stop counting towards pending proposals.
+    pub fn update_admin_council(
+        ctx: Context<UpdateAdminCouncil>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        AdminCouncil::validate(&members, threshold)?;
         
-        // Update device tier
-        device_account.tier = match device_account.staked_amount {
-            0..=1000 => DeviceTier::Bronze,
-            1001..=5000 => DeviceTier::Silver,
-            5001..=20000 => DeviceTier::Gold,
-            _ => DeviceTier::Platinum,
-        };
+        let admin_council = &mut ctx.accounts.admin_council;
+        admin_council.members = members.clone();
+        admin_council.threshold = threshold;
+        
+        emit!(AdminCouncilUpdated {
+            members,
+            threshold,
+        });
         
-        msg!("Device {} unstaked {} tokens, new tier: {:?}", 
-            device_account.device_id, amount, device_account.tier);
+        msg!("Admin council updated, {}-of-{}", threshold, admin_council.members.len());
         Ok(())
     }
     
-    pub fn verify_task_result(
-        ctx: Context<VerifyTaskResult>,
-        task_id: String,
-        is_valid: bool,
+    /// Proposes an instruction of this program for the council to run as the network authority.
+    /// The proposer's approval is recorded right away.
+    pub fn propose_admin_action(
+        ctx: Context<ProposeAdminAction>,
+        accounts: Vec<AdminAccountMeta>,
+        data: Vec<u8>,
     ) -> Result<()> {
-        let task_account = &mut ctx.accounts.task_account;
-        let device_account = &mut ctx.accounts.device_account;
-        let verifier_account = &mut ctx.accounts.verifier_account;
+        require!(
+            accounts.len() <= MAX_ADMIN_ACCOUNTS && data.len() <= MAX_ADMIN_DATA_LEN,
+            ComputeError::AdminActionTooLarge
+        );
+        let admin_council = &mut ctx.accounts.admin_council;
+        let proposer = ctx.accounts.proposer.key();
+        require!(admin_council.members.contains(&proposer), ComputeError::NotCouncilMember);
         
-        require!(task_account.status == TaskStatus::Completed, ComputeError::TaskNotCompleted);
-        require!(verifier_account.reputation_score >= 100, ComputeError::InsufficientReputation);
+        let id = admin_council.proposal_count;
+        admin_council.proposal_count += 1;
         
-        task_account.verifications += 1;
-        if is_valid {
-            task_account.valid_verifications += 1;
-        }
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.id = id;
+        proposal.proposer = proposer;
+        proposal.accounts = accounts;
+        proposal.data = data;
+        proposal.approvals = vec![proposer];
+        proposal.created_at = Clock::get()?.unix_timestamp;
+        proposal.executed = false;
         
-        // Byzantine fault tolerance: Need 2/3 valid verifications
-        if task_account.verifications >= 3 {
-            if task_account.valid_verifications * 3 >= task_account.verifications * 2 {
-                task_account.is_verified = true;
-                device_account.reputation_score = device_account.reputation_score.saturating_add(2);
-            } else {
-                task_account.status = TaskStatus::Failed;
-                device_account.reputation_score = device_account.reputation_score.saturating_sub(20);
-            }
+        emit!(AdminActionProposed {
+            proposal: proposal.key(),
+            id,
+            proposer,
+        });
+        
+        msg!("Admin action {} proposed by {}", id, proposer);
+        Ok(())
+    }
+    
+    pub fn approve_admin_action(ctx: Context<ApproveAdminAction>) -> Result<()> {
+        let member = ctx.accounts.member.key();
+        let proposal = &mut ctx.accounts.proposal;
+        
+        require!(ctx.accounts.admin_council.members.contains(&member), ComputeError::NotCouncilMember);
+        require!(!proposal.executed, ComputeError::AdminActionExecuted);
+        require!(!proposal.approvals.contains(&member), ComputeError::AlreadyApproved);
+        // Dropping approvals of removed members keeps the list within the account's space
+        let members = &ctx.accounts.admin_council.members;
+        proposal.approvals.retain(|approver| members.contains(approver));
+        proposal.approvals.push(member);
+        
+        emit!(AdminActionApproved {
+            proposal: proposal.key(),
+            member,
+            approvals: ctx.accounts.admin_council.approvals_of(proposal),
+        });
+        
+        msg!("Admin action {} approved by {}", proposal.id, member);
+        Ok(())
+    }
+    
+    /// Runs a proposal approved by the threshold of current members. Permissionless; remaining_accounts
+    /// must hold every account the proposed instruction lists, except admin_authority.
+    pub fn execute_admin_action<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteAdminAction<'info>>,
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        require!(!proposal.executed, ComputeError::AdminActionExecuted);
+        require!(
+            ctx.accounts.admin_council.approvals_of(proposal) >= ctx.accounts.admin_council.threshold,
+            ComputeError::ThresholdNotMet
+        );
+        
+        // Persist the flag before the call so the proposal cannot be executed again from within it
+        proposal.executed = true;
+        proposal.exit(&crate::ID)?;
+        
+        let ix = program_instruction(&proposal.accounts, &proposal.data);
+        let mut account_infos = ctx.remaining_accounts.to_vec();
+        account_infos.push(ctx.accounts.admin_authority.to_account_info());
+        invoke_signed(&ix, &account_infos, &[&[b"admin_authority".as_ref(), &[ctx.bumps.admin_authority]]])?;
+        
+        emit!(AdminActionExecuted {
+            proposal: proposal.key(),
+            id: proposal.id,
+        });
+        
+        msg!("Admin action {} executed", proposal.id);
+        Ok(())
+    }
+    
+    /// Withdraws a proposal that has not been executed and returns its rent to the proposer
+    pub fn cancel_admin_action(ctx: Context<CancelAdminAction>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(!proposal.executed, ComputeError::AdminActionExecuted);
+        
+        emit!(AdminActionCancelled {
+            proposal: proposal.key(),
+            id: proposal.id,
+        });
+        
+        msg!("Admin action {} cancelled", proposal.id);
+        Ok(())
+    }
+    
+    /// Puts config changes and treasury withdrawals behind a timelock of `delay` seconds. From then
+    /// on they only run through schedule_action and execute_action, signed by the timelock_authority PDA.
+    pub fn initialize_timelock(
+        ctx: Context<InitializeTimelock>,
+        delay: i64,
+    ) -> Result<()> {
+        require!(delay > 0 && delay <= MAX_TIMELOCK_DELAY, ComputeError::InvalidConfig);
+        
+        let timelock = &mut ctx.accounts.timelock;
+        timelock.delay = delay;
+        timelock.action_count = 0;
+        ctx.accounts.network_state.timelock_authority = ctx.accounts.timelock_authority.key();
+        
+        emit!(TimelockDelayChanged { delay });
+        
+        msg!("Timelock initialized with a {}s delay", delay);
+        Ok(())
+    }
+    
+    /// Changes the delay; only reachable through an executed action, so it is timelocked itself
+    pub fn set_timelock_delay(
+        ctx: Context<SetTimelockDelay>,
+        delay: i64,
+    ) -> Result<()> {
+        require!(delay > 0 && delay <= MAX_TIMELOCK_DELAY, ComputeError::InvalidConfig);
+        ctx.accounts.timelock.delay = delay;
+        
+        emit!(TimelockDelayChanged { delay });
+        
+        msg!("Timelock delay set to {}s", delay);
+        Ok(())
+    }
+    
+    /// Queues an instruction of this program to run as the timelock authority once the delay has passed
+    pub fn schedule_action(
+        ctx: Context<ScheduleAction>,
+        accounts: Vec<AdminAccountMeta>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        require!(
+            accounts.len() <= MAX_ADMIN_ACCOUNTS && data.len() <= MAX_ADMIN_DATA_LEN,
+            ComputeError::AdminActionTooLarge
+        );
+        let timelock = &mut ctx.accounts.timelock;
+        let id = timelock.action_count;
+        timelock.action_count += 1;
+        let now = Clock::get()?.unix_timestamp;
+        
+        let action = &mut ctx.accounts.action;
+        action.id = id;
+        action.accounts = accounts;
+        action.data = data;
+        action.scheduled_at = now;
+        action.eta = now + timelock.delay;
+        action.executed = false;
+        
+        emit!(ActionScheduled {
+            action: action.key(),
+            id,
+            eta: action.eta,
+        });
+        
+        msg!("Action {} scheduled, executable at {}", id, action.eta);
+        Ok(())
+    }
+    
+    /// Runs a scheduled action whose delay has passed. Permissionless; remaining_accounts must hold
+    /// every account the scheduled instruction lists, except timelock_authority.
+    pub fn execute_action<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteAction<'info>>,
+    ) -> Result<()> {
+        let action = &mut ctx.accounts.action;
+        require!(!action.executed, ComputeError::AdminActionExecuted);
+        require!(Clock::get()?.unix_timestamp >= action.eta, ComputeError::ActionTimelocked);
+        
+        // Persist the flag before the call so the action cannot be executed again from within it
+        action.executed = true;
+        action.exit(&crate::ID)?;
+        
+        let ix = program_instruction(&action.accounts, &action.data);
+        let mut account_infos = ctx.remaining_accounts.to_vec();
+        account_infos.push(ctx.accounts.timelock_authority.to_account_info());
+        invoke_signed(&ix, &account_infos, &[&[b"timelock_authority".as_ref(), &[ctx.bumps.timelock_authority]]])?;
+        
+        emit!(ActionExecuted {
+            action: action.key(),
+            id: action.id,
+        });
+        
+        msg!("Action {} executed", action.id);
+        Ok(())
+    }
+    
+    /// Drops a scheduled action before it runs and returns its rent to the authority
+    pub fn cancel_action(ctx: Context<CancelAction>) -> Result<()> {
+        let action = &ctx.accounts.action;
+        require!(!action.executed, ComputeError::AdminActionExecuted);
+        
+        emit!(ActionCancelled {
+            action: action.key(),
+            id: action.id,
+        });
+        
+        msg!("Action {} cancelled", action.id);
+        Ok(())
+    }
+    
+    /// Opens a stake-weighted vote on a config change. The proposer must own a device with at
+    /// least MIN_PROPOSAL_STAKE; quorum is fixed from the online stake at this point.
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        change: ConfigChange,
+    ) -> Result<()> {
+        let device_account = ctx.accounts.device_account.load()?;
+        require!(!device_account.is_banned(), ComputeError::DeviceBanned);
+        require!(device_account.staked_amount >= MIN_PROPOSAL_STAKE, ComputeError::InsufficientStake);
+        // Reject changes update_config would refuse before anyone votes on them
+        change.apply(&mut ctx.accounts.network_config.clone().into_inner())?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        let governance = &mut ctx.accounts.governance;
+        let id = governance.proposal_count;
+        governance.proposal_count += 1;
+        
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.id = id;
+        proposal.proposer = ctx.accounts.owner.key();
+        proposal.change = change;
+        proposal.votes_for = 0;
+        proposal.votes_against = 0;
+        proposal.quorum = ctx.accounts.epoch_state.total_weight
+            .checked_mul(GOVERNANCE_QUORUM_BPS)
+            .ok_or(ComputeError::MathOverflow)?
+            / 10000;
+        proposal.created_at = now;
+        proposal.voting_ends_at = now + GOVERNANCE_VOTING_PERIOD;
+        proposal.executed = false;
+        
+        emit!(ProposalCreated {
+            proposal: proposal.key(),
+            id,
+            proposer: proposal.proposer,
+            change,
+            voting_ends_at: proposal.voting_ends_at,
+        });
+        
+        msg!("Governance proposal {} created", id);
+        Ok(())
+    }
+    
+    /// Votes with the device's full stake, which stays bonded until the voting window closes
+    pub fn cast_vote(
+        ctx: Context<CastVote>,
+        support: bool,
+    ) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let proposal = &mut ctx.accounts.proposal;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require!(now < proposal.voting_ends_at, ComputeError::VotingClosed);
+        require!(!device_account.is_banned(), ComputeError::DeviceBanned);
+        let weight = device_account.staked_amount;
+        require!(weight > 0, ComputeError::InsufficientStake);
+        
+        if support {
+            proposal.votes_for = proposal.votes_for.checked_add(weight).ok_or(ComputeError::MathOverflow)?;
+        } else {
+            proposal.votes_against = proposal.votes_against.checked_add(weight).ok_or(ComputeError::MathOverflow)?;
         }
+        device_account.vote_locked_until = device_account.vote_locked_until.max(proposal.voting_ends_at);
         
-        // Reward verifier
-        verifier_account.total_verifications += 1;
-        verifier_account.reputation_score = verifier_account.reputation_score.saturating_add(1);
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = proposal.key();
+        vote_record.device = device_key;
+        vote_record.support = support;
+        vote_record.weight = weight;
         
-        msg!("Task {} verification by device {}: valid={}", 
-            task_id, verifier_account.device_id, is_valid);
+        emit!(VoteCast {
+            proposal: proposal.key(),
+            device: device_key,
+            support,
+            weight,
+        });
+        
+        msg!("Device {} voted {} on proposal {} with {}", 
+            device_account.device_id_str(), support, proposal.id, weight);
         Ok(())
     }
-}
-
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        init,
-        payer = authority,
+    
+    /// Applies a proposal that reached quorum with more stake for than against, once
+    /// GOVERNANCE_TIMELOCK has passed since voting closed. Permissionless.
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require!(!proposal.executed, ComputeError::ProposalExecuted);
+        require!(now >= proposal.voting_ends_at + GOVERNANCE_TIMELOCK, ComputeError::ProposalTimelocked);
+        require!(proposal.passed(), ComputeError::ProposalNotPassed);
+        
+        proposal.change.apply(&mut ctx.accounts.network_config)?;
+        proposal.executed = true;
+        
+        emit!(ProposalExecuted {
+            proposal: proposal.key(),
+            id: proposal.id,
+            change: proposal.change,
+        });
+        
+        msg!("Governance proposal {} executed", proposal.id);
+        Ok(())
+    }
+
+    pub fn register_device(
+        ctx: Context<RegisterDevice>,
+        device_id: String,
+        device_specs: DeviceSpecs,
+    ) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let specs_attested = AttesterRegistry::check_specs_attestation(
+            ctx.accounts.attester_registry.as_ref(),
+            ctx.accounts.instructions.as_ref(),
+            &device_key,
+            &device_specs,
+        )?;
+        let license_mint = ctx.accounts.mint_license(&device_id, ctx.bumps.network_state)?;
+        let mut device_account = ctx.accounts.device_account.load_init()?;
+        let network_state = &mut ctx.accounts.network_state;
+        let clock = Clock::get()?;
+        
+        device_account.owner = ctx.accounts.owner.key();
+        device_account.license_mint = license_mint;
+        device_account.device_id = to_fixed_bytes(&device_id)?;
+        device_specs.validate()?;
+        device_account.specs = device_specs;
+        device_account.specs.region_attested = 0;
+        device_account.specs.specs_attested = specs_attested as u8;
+        device_account.is_active = 1;
+        device_account.reputation_score = BASE_REPUTATION;
+        device_account.total_tasks_completed = 0;
+        device_account.total_tokens_earned = 0;
+        device_account.last_active = clock.unix_timestamp;
+        device_account.tier = DeviceTier::Bronze as u8;
+        device_account.staked_amount = 0;
+        device_account.stake_timestamp = 0;
+        device_account.total_verifications = 0;
+        device_account.active_assignments = 0;
+        device_account.specs_updated_at = clock.unix_timestamp;
+        device_account.verifier_locked_stake = 0;
+        device_account.benchmark_score = 0;
+        device_account.benchmarked_at = 0;
+        device_account.receipted_stake = 0;
+        device_account.leaderboard_epoch = 0;
+        device_account.epoch_tasks_completed = 0;
+        device_account.epoch_tokens_earned = 0;
+        device_account.pool = Pubkey::default();
+        device_account.pool_synced_stake = 0;
+        device_account.version = DeviceAccount::VERSION;
+        
+        network_state.total_devices += 1;
+        network_state.device_status_changed(false, true);
+        
+        emit!(DeviceRegistered {
+            device: device_key,
+            owner: device_account.owner,
+            device_id: device_account.device_id_str(),
+            specs: device_account.specs,
+        });
+        
+        msg!("Device registered successfully: {}", device_account.device_id_str());
+        Ok(())
+    }
+    
+    /// Makes the current holder of a device's license NFT its owner. Stake and pending rewards go
+    /// with the device, reputation stays on it, and the previous owner's agent keys are revoked.
+    /// The previous owner already lost control when the license left their wallet.
+    pub fn claim_device_license(ctx: Context<ClaimDeviceLicense>) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let new_owner = ctx.accounts.new_owner.key();
+        
+        require!(device_account.license_mint != Pubkey::default(), ComputeError::NoDeviceLicense);
+        require!(device_account.owner != new_owner, ComputeError::LicenseAlreadyClaimed);
+        
+        let previous_owner = device_account.owner;
+        device_account.owner = new_owner;
+        device_account.agent_key_epoch += 1;
+        
+        emit!(DeviceLicenseClaimed {
+            device: device_key,
+            license_mint: device_account.license_mint,
+            previous_owner,
+            new_owner,
+        });
+        
+        msg!("Device {} claimed by license holder {}", device_account.device_id_str(), new_owner);
+        Ok(())
+    }
+
+    /// Opens a pool under the signing operator, who routes its tasks and takes `commission_bps`
+    /// of the task rewards its members earn in network tokens or SOL
+    pub fn create_pool(
+        ctx: Context<CreatePool>,
+        pool_id: String,
+        commission_bps: u16,
+    ) -> Result<()> {
+        require!(pool_id.len() <= 32, ComputeError::FieldTooLong);
+        require!(commission_bps <= MAX_POOL_COMMISSION_BPS, ComputeError::InvalidCommission);
+        
+        let pool = &mut ctx.accounts.pool;
+        pool.operator = ctx.accounts.operator.key();
+        pool.commission_bps = commission_bps;
+        pool.member_count = 0;
+        pool.total_stake = 0;
+        pool.tasks_completed = 0;
+        pool.pending_commission = 0;
+        pool.sol_commission = 0;
+        pool.created_at = Clock::get()?.unix_timestamp;
+        
+        emit!(PoolCreated {
+            pool: pool.key(),
+            operator: pool.operator,
+            commission_bps,
+        });
+        
+        msg!("Pool {} created by {}", pool_id, pool.operator);
+        Ok(())
+    }
+    
+    pub fn set_pool_commission(
+        ctx: Context<SetPoolCommission>,
+        commission_bps: u16,
+    ) -> Result<()> {
+        require!(commission_bps <= MAX_POOL_COMMISSION_BPS, ComputeError::InvalidCommission);
+        let pool = &mut ctx.accounts.pool;
+        pool.commission_bps = commission_bps;
+        
+        emit!(PoolCommissionUpdated {
+            pool: pool.key(),
+            commission_bps,
+        });
+        
+        msg!("Pool {} commission set to {} bps", pool.key(), commission_bps);
+        Ok(())
+    }
+    
+    /// Adds an idle device to a pool; both the device owner and the pool operator sign
+    pub fn join_pool(ctx: Context<JoinPool>) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let pool = &mut ctx.accounts.pool;
+        
+        require!(device_account.pool == Pubkey::default(), ComputeError::DeviceInPool);
+        require!(!device_account.is_banned(), ComputeError::DeviceBanned);
+        require!(device_account.active_assignments == 0, ComputeError::DeviceHasActiveTasks);
+        
+        device_account.pool = pool.key();
+        device_account.pool_synced_stake = 0;
+        device_account.sync_pool_stake(pool);
+        pool.member_count += 1;
+        
+        emit!(PoolMembershipChanged {
+            pool: pool.key(),
+            device: device_key,
+            joined: true,
+            member_count: pool.member_count,
+            total_stake: pool.total_stake,
+        });
+        
+        msg!("Device {} joined pool {}", device_account.device_id_str(), pool.key());
+        Ok(())
+    }
+    
+    /// Takes an idle device out of its pool, on the word of either the device owner or the operator
+    pub fn leave_pool(ctx: Context<LeavePool>) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let pool = &mut ctx.accounts.pool;
+        
+        let authority = ctx.accounts.authority.key();
+        require!(authority == device_account.owner || authority == pool.operator, ComputeError::Unauthorized);
+        require!(device_account.active_assignments == 0, ComputeError::DeviceHasActiveTasks);
+        
+        device_account.sync_pool_stake(pool);
+        pool.total_stake = pool.total_stake.saturating_sub(device_account.pool_synced_stake);
+        pool.member_count = pool.member_count.saturating_sub(1);
+        device_account.pool = Pubkey::default();
+        device_account.pool_synced_stake = 0;
+        
+        emit!(PoolMembershipChanged {
+            pool: pool.key(),
+            device: device_key,
+            joined: false,
+            member_count: pool.member_count,
+            total_stake: pool.total_stake,
+        });
+        
+        msg!("Device {} left pool {}", device_account.device_id_str(), pool.key());
+        Ok(())
+    }
+    
+    /// Permissionless refresh of a member's stake in its pool's total after staking, unstaking or slashing
+    pub fn sync_pool_stake(ctx: Context<SyncPoolStake>) -> Result<()> {
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        device_account.sync_pool_stake(&mut ctx.accounts.pool);
+        
+        msg!("Pool {} stake synced to {}", ctx.accounts.pool.key(), ctx.accounts.pool.total_stake);
+        Ok(())
+    }
+    
+    /// Hands a pool task to one of the pool's members, who accepts or rejects it like any assignment.
+    /// A member that rejects it or lets it lapse returns it to the operator for another route.
+    pub fn route_pool_task(
+        ctx: Context<RoutePoolTask>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let device_account = ctx.accounts.device_account.load()?;
+        let clock = Clock::get()?;
+        
+        require!(device_account.pool == ctx.accounts.pool.key(), ComputeError::DeviceNotInPool);
+        require!(task_account.is_open(clock.unix_timestamp), ComputeError::TaskNotPending);
+        task_account.check_device(&device_account, &ctx.accounts.network_config)?;
+        if task_account.status == TaskStatus::Pending as u8 {
+            ctx.accounts.network_state.task_taken();
+        }
+        task_account.assign(device_key, clock.unix_timestamp, false, ctx.accounts.network_config.expiry_multiplier);
+        
+        emit!(TaskAssigned {
+            task: task_key,
+            device: device_key,
+            accepted: false,
+        });
+        
+        msg!("Pool task {} routed to device {}", task_id, device_account.device_id_str());
+        Ok(())
+    }
+    
+    /// Pays the operator the commission its pool has accrued, in network tokens and in SOL
+    pub fn claim_pool_commission(ctx: Context<ClaimPoolCommission>) -> Result<()> {
+        let tokens = ctx.accounts.pool.pending_commission;
+        let lamports = ctx.accounts.pool.sol_commission;
+        require!(tokens > 0 || lamports > 0, ComputeError::NoPoolCommission);
+        
+        if tokens > 0 {
+            let seeds = &[
+                b"network_state".as_ref(),
+                &[ctx.bumps.network_state]
+            ];
+            let signer_seeds = &[&seeds[..]];
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: ctx.accounts.operator_token_account.to_account_info(),
+                authority: ctx.accounts.network_state.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_interface::transfer_checked(cpi_ctx, tokens, ctx.accounts.reward_mint.decimals)?;
+        }
+        
+        let pool = &mut ctx.accounts.pool;
+        if lamports > 0 {
+            pool.sub_lamports(lamports)?;
+            ctx.accounts.operator.add_lamports(lamports)?;
+        }
+        pool.pending_commission = 0;
+        pool.sol_commission = 0;
+        
+        emit!(PoolCommissionClaimed {
+            pool: pool.key(),
+            operator: pool.operator,
+            tokens,
+            lamports,
+        });
+        
+        msg!("Pool {} paid out {} tokens and {} lamports of commission", pool.key(), tokens, lamports);
+        Ok(())
+    }
+
+    pub fn migrate_device_pda(
+        ctx: Context<MigrateDevicePda>,
+        device_id: String,
+    ) -> Result<()> {
+        // Move a device registered under the legacy [b"device", device_id] seeds to the owner-namespaced PDA.
+        // Tasks reference devices by address, so the device must be idle while it moves.
+        let legacy = *ctx.accounts.legacy_device_account.load()?;
+        require!(legacy.active_assignments == 0, ComputeError::DeviceHasActiveTasks);
+        *ctx.accounts.device_account.load_init()? = legacy;
+        
+        emit!(DeviceMigrated {
+            legacy_device: ctx.accounts.legacy_device_account.key(),
+            device: ctx.accounts.device_account.key(),
+            owner: ctx.accounts.owner.key(),
+        });
+        
+        msg!("Device {} migrated to owner-scoped address", device_id);
+        Ok(())
+    }
+    
+    /// Permissionless upgrade of a device account to the current layout; `payer` covers any extra rent
+    pub fn migrate_device(ctx: Context<MigrateDevice>) -> Result<()> {
+        let from_version = migrate_layout::<DeviceAccount>(
+            &ctx.accounts.device_account,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+        )?;
+        
+        emit!(AccountMigrated {
+            account: ctx.accounts.device_account.key(),
+            from_version,
+            to_version: DeviceAccount::VERSION,
+        });
+        
+        msg!("Device {} migrated from layout v{}", ctx.accounts.device_account.key(), from_version);
+        Ok(())
+    }
+    
+    /// Permissionless upgrade of a task account to the current layout; `payer` covers any extra rent
+    pub fn migrate_task(ctx: Context<MigrateTask>) -> Result<()> {
+        let from_version = migrate_layout::<TaskAccount>(
+            &ctx.accounts.task_account,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+        )?;
+        
+        emit!(AccountMigrated {
+            account: ctx.accounts.task_account.key(),
+            from_version,
+            to_version: TaskAccount::VERSION,
+        });
+        
+        msg!("Task {} migrated from layout v{}", ctx.accounts.task_account.key(), from_version);
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_task(
+        ctx: Context<SubmitTask>,
+        task_id: String,
+        task_type: TaskType,
+        compute_requirements: ComputeRequirements,
+        reward_amount: u64,
+        verification_mode: VerificationMode,
+        redundancy: u8,
+        min_reputation: u16,
+        priority_fee: u64,
+        reward_kind: RewardKind,
+    ) -> Result<()> {
+        ctx.accounts.submit(
+            task_id,
+            task_type,
+            compute_requirements,
+            reward_amount,
+            verification_mode,
+            redundancy,
+            min_reputation,
+            priority_fee,
+            reward_kind,
+            None,
+            None,
+        )
+    }
+    
+    /// Submits a task straight to a chosen device, skipping matching. If the device rejects it or
+    /// doesn't accept within ACCEPT_TIMEOUT, anyone can refund the escrow through refund_direct_task.
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_direct_task(
+        ctx: Context<SubmitDirectTask>,
+        task_id: String,
+        task_type: TaskType,
+        compute_requirements: ComputeRequirements,
+        reward_amount: u64,
+        verification_mode: VerificationMode,
+        min_reputation: u16,
+        priority_fee: u64,
+    ) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let device_account = ctx.accounts.device_account.load()?;
+        ctx.accounts.submit.submit(
+            task_id,
+            task_type,
+            compute_requirements,
+            reward_amount,
+            verification_mode,
+            1,
+            min_reputation,
+            priority_fee,
+            RewardKind::Token,
+            Some((device_key, &device_account)),
+            None,
+        )
+    }
+    
+    /// Submits a task reserved for the members of a pool. It is not listed for matching or claims;
+    /// the pool's operator routes it to a member with route_pool_task.
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_pool_task(
+        ctx: Context<SubmitPoolTask>,
+        task_id: String,
+        task_type: TaskType,
+        compute_requirements: ComputeRequirements,
+        reward_amount: u64,
+        verification_mode: VerificationMode,
+        min_reputation: u16,
+        priority_fee: u64,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        ctx.accounts.submit.submit(
+            task_id,
+            task_type,
+            compute_requirements,
+            reward_amount,
+            verification_mode,
+            1,
+            min_reputation,
+            priority_fee,
+            RewardKind::Token,
+            None,
+            Some(pool_key),
+        )
+    }
+    
+    /// Permissionless refund of a direct task its device rejected or never accepted
+    pub fn refund_direct_task(
+        ctx: Context<RefundDirectTask>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        require!(task_account.direct != 0, ComputeError::NotDirectTask);
+        let lapsed = task_account.status == TaskStatus::Assigned as u8
+            && task_account.assignment_accepted == 0
+            && clock.unix_timestamp > task_account.assigned_at + ACCEPT_TIMEOUT;
+        let rejected = task_account.status == TaskStatus::Pending as u8;
+        require!(lapsed || rejected, ComputeError::DirectTaskActive);
+        
+        let refund = task_account.reward_amount
+            .checked_add(task_account.priority_fee)
+            .ok_or(ComputeError::MathOverflow)?;
+        if task_account.reward_kind == RewardKind::Sol as u8 {
+            // The task account is closed here, so the escrow's rent deposit goes back with the refund
+            let escrow = SolEscrow::new(task_key, ctx.accounts.sol_escrow.as_ref(), ctx.bumps.sol_escrow, &ctx.accounts.system_program)?;
+            escrow.pay(&ctx.accounts.submitter.to_account_info(), escrow.escrow.lamports())?;
+        } else {
+            let seeds = &[
+                b"network_state".as_ref(),
+                &[ctx.bumps.network_state]
+            ];
+            let signer_seeds = &[&seeds[..]];
+            
+            let submitter_token_account = ctx.accounts.submitter_token_account.as_ref()
+                .ok_or(ComputeError::TokenAccountRequired)?;
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: submitter_token_account.to_account_info(),
+                authority: ctx.accounts.network_state.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_interface::transfer_checked(cpi_ctx, refund, ctx.accounts.reward_mint.decimals)?;
+        }
+        
+        task_account.status = TaskStatus::Cancelled as u8;
+        ctx.accounts.submitter_profile.record_cancellation(refund)?;
+        
+        emit!(TaskCancelled {
+            task: task_key,
+            submitter: task_account.submitter,
+            refund,
+        });
+        
+        msg!("Direct task {} refunded {}", task_id, refund);
+        Ok(())
+    }
+    
+    pub fn create_task_template(
+        ctx: Context<CreateTaskTemplate>,
+        template_id: String,
+        preset: TaskPreset,
+    ) -> Result<()> {
+        require!((1..=MAX_REDUNDANCY).contains(&preset.redundancy), ComputeError::InvalidRedundancy);
+        require!(template_id.len() <= 32, ComputeError::FieldTooLong);
+        
+        let task_template = &mut ctx.accounts.task_template;
+        task_template.owner = ctx.accounts.owner.key();
+        task_template.template_id = template_id.clone();
+        task_template.preset = preset;
+        task_template.created_at = Clock::get()?.unix_timestamp;
+        
+        emit!(TaskTemplateCreated {
+            template: task_template.key(),
+            owner: task_template.owner,
+            task_type: preset.task_type,
+            default_reward: preset.default_reward,
+        });
+        
+        msg!("Task template {} created", template_id);
+        Ok(())
+    }
+    
+    /// Submits a task whose type, requirements and settings come from a stored template.
+    /// `reward_amount` overrides the template's default reward when set.
+    pub fn submit_task_from_template(
+        ctx: Context<SubmitTaskFromTemplate>,
+        task_id: String,
+        reward_amount: Option<u64>,
+        priority_fee: u64,
+    ) -> Result<()> {
+        let preset = ctx.accounts.task_template.preset;
+        ctx.accounts.submit.submit(
+            task_id,
+            preset.task_type,
+            preset.compute_requirements,
+            reward_amount.unwrap_or(preset.default_reward),
+            preset.verification_mode,
+            preset.redundancy,
+            preset.min_reputation,
+            priority_fee,
+            RewardKind::Token,
+            None,
+            None,
+        )
+    }
+    
+    /// Submits a task on behalf of `intent.submitter`, whose Ed25519 signature over the intent is
+    /// checked in the preceding instruction. The relayer pays the transaction and rent; the reward,
+    /// priority fee and submission fee are debited from the submitter's credit account.
+    pub fn submit_task_with_intent(
+        ctx: Context<SubmitTaskWithIntent>,
+        task_id: String,
+        intent: SubmissionIntent,
+    ) -> Result<()> {
+        let signer = ed25519_signer(&ctx.accounts.instructions, &intent.message(&task_id)?)?;
+        require!(signer == intent.submitter, ComputeError::InvalidIntent);
+        
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_init()?;
+        let clock = Clock::get()?;
+        
+        require!(clock.unix_timestamp <= intent.expires_at, ComputeError::IntentExpired);
+        let credit_account = &mut ctx.accounts.credit_account;
+        require!(intent.nonce == credit_account.intent_nonce, ComputeError::IntentNonceMismatch);
+        credit_account.intent_nonce = credit_account.intent_nonce.checked_add(1).ok_or(ComputeError::MathOverflow)?;
+        
+        let submission = intent.submission();
+        let terms = task_account.init_submission(
+            &task_id,
+            &submission,
+            &mut ctx.accounts.submitter_profile,
+            &ctx.accounts.network_state,
+            &ctx.accounts.network_config,
+            &ctx.accounts.pending_index,
+            &clock,
+        )?;
+        let SubmissionTerms { total, submission_fee, .. } = terms;
+        
+        // Credits already sit in the reward vault, so only the submission fee has to move
+        let debit = total
+            .checked_add(submission_fee)
+            .ok_or(ComputeError::MathOverflow)?;
+        require!(debit <= intent.max_spend, ComputeError::IntentSpendExceeded);
+        credit_account.spend(debit)?;
+        
+        if submission_fee > 0 {
+            let seeds = &[
+                b"network_state".as_ref(),
+                &[ctx.bumps.network_state]
+            ];
+            let signer_seeds = &[&seeds[..]];
+            
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.network_state.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_interface::transfer_checked(cpi_ctx, submission_fee, ctx.accounts.reward_mint.decimals)?;
+        }
+        
+        ctx.accounts.network_state.task_opened();
+        ctx.accounts.priority_index.insert(task_key, intent.priority_fee);
+        ctx.accounts.pending_index.push(task_key);
+        ctx.accounts.network_state.enqueue(task_key, &mut task_account, ctx.accounts.queue_tail.as_ref())?;
+        
+        emit!(CreditsSpent {
+            credit_account: ctx.accounts.credit_account.key(),
+            task: task_key,
+            amount: debit,
+            balance: ctx.accounts.credit_account.balance,
+        });
+        emit!(submission.submitted(task_key, &terms));
+        
+        msg!("Task submitted by intent: {} with reward: {}", task_id, terms.reward_amount);
+        Ok(())
+    }
+    
+    /// Deposits tokens as prepaid credits for `beneficiary`, who can then submit tasks against the
+    /// balance without a token transfer of their own. Anyone may deposit, so a sponsor can fund users.
+    pub fn deposit_credits(
+        ctx: Context<DepositCredits>,
+        beneficiary: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ComputeError::EmptyDeposit);
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.depositor_token_account.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: ctx.accounts.depositor.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(
+            cpi_ctx,
+            with_transfer_fee(&ctx.accounts.reward_mint, amount)?,
+            ctx.accounts.reward_mint.decimals,
+        )?;
+        
+        let credit_account = &mut ctx.accounts.credit_account;
+        if credit_account.owner == Pubkey::default() {
+            credit_account.owner = beneficiary;
+            credit_account.mint = ctx.accounts.reward_mint.key();
+        }
+        credit_account.balance = credit_account.balance.checked_add(amount).ok_or(ComputeError::MathOverflow)?;
+        credit_account.total_deposited = credit_account.total_deposited.checked_add(amount).ok_or(ComputeError::MathOverflow)?;
+        
+        emit!(CreditsDeposited {
+            credit_account: credit_account.key(),
+            owner: beneficiary,
+            depositor: ctx.accounts.depositor.key(),
+            amount,
+            balance: credit_account.balance,
+        });
+        
+        msg!("Deposited {} credits for {}", amount, beneficiary);
+        Ok(())
+    }
+    
+    /// Returns unused credits to the owner's token account
+    pub fn withdraw_credits(
+        ctx: Context<WithdrawCredits>,
+        amount: u64,
+    ) -> Result<()> {
+        let credit_account = &mut ctx.accounts.credit_account;
+        require!(amount > 0 && amount <= credit_account.balance, ComputeError::InsufficientCredits);
+        credit_account.balance -= amount;
+        
+        let seeds = &[
+            b"network_state".as_ref(),
+            &[ctx.bumps.network_state]
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: ctx.accounts.network_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.reward_mint.decimals)?;
+        
+        emit!(CreditsWithdrawn {
+            credit_account: credit_account.key(),
+            owner: credit_account.owner,
+            amount,
+            balance: credit_account.balance,
+        });
+        
+        msg!("Withdrew {} credits", amount);
+        Ok(())
+    }
+
+    pub fn cancel_task(
+        ctx: Context<CancelTask>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        
+        // Pipeline stages that have not been released yet can be cancelled as well
+        let was_pending = task_account.status == TaskStatus::Pending as u8;
+        require!(
+            was_pending || task_account.status == TaskStatus::Waiting as u8,
+            ComputeError::TaskNotPending
+        );
+        require!(task_account.assigned_count == 0, ComputeError::TaskNotPending);
+        
+        // Refund the escrowed reward to the submitter
+        let refund = task_account.reward_amount
+            .checked_add(task_account.priority_fee)
+            .ok_or(ComputeError::MathOverflow)?;
+        if task_account.reward_kind == RewardKind::Sol as u8 {
+            // The task account is closed here, so the escrow's rent deposit goes back with the refund
+            let escrow = SolEscrow::new(task_key, ctx.accounts.sol_escrow.as_ref(), ctx.bumps.sol_escrow, &ctx.accounts.system_program)?;
+            escrow.pay(&ctx.accounts.submitter.to_account_info(), escrow.escrow.lamports())?;
+        } else {
+            let seeds = &[
+                b"network_state".as_ref(),
+                &[ctx.bumps.network_state]
+            ];
+            let signer_seeds = &[&seeds[..]];
+            
+            let submitter_token_account = ctx.accounts.submitter_token_account.as_ref()
+                .ok_or(ComputeError::TokenAccountRequired)?;
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: submitter_token_account.to_account_info(),
+                authority: ctx.accounts.network_state.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_interface::transfer_checked(cpi_ctx, refund, ctx.accounts.reward_mint.decimals)?;
+        }
+        
+        task_account.status = TaskStatus::Cancelled as u8;
+        if was_pending && task_account.direct == 0 {
+            ctx.accounts.network_state.task_taken();
+        }
+        ctx.accounts.priority_index.remove(&task_key);
+        ctx.accounts.pending_index.remove(&task_key);
+        ctx.accounts.network_state.dequeue(task_key, &mut task_account, ctx.accounts.queue_prev.as_ref(), ctx.accounts.queue_next.as_ref())?;
+        
+        ctx.accounts.submitter_profile.record_cancellation(refund)?;
+        
+        emit!(TaskCancelled {
+            task: task_key,
+            submitter: task_account.submitter,
+            refund,
+        });
+        
+        msg!("Task {} cancelled, refunded {}", task_id, task_account.reward_amount);
+        Ok(())
+    }
+
+    pub fn assign_task(
+        ctx: Context<AssignTask>,
+        task_id: String,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let device_account = ctx.accounts.device_account.load()?;
+        let clock = Clock::get()?;
+        
+        // Only the network authority, the configured matcher or the task's own submitter may pin it to a device
+        let authority = ctx.accounts.authority.key();
+        require!(
+            authority == ctx.accounts.network_state.authority ||
+            authority == ctx.accounts.network_config.matcher ||
+            authority == task_account.submitter,
+            ComputeError::Unauthorized
+        );
+        require!(task_account.redundancy == 1, ComputeError::RedundantTask);
+        task_account.check_eligibility(&device_account, &ctx.accounts.network_config, clock.unix_timestamp)?;
+        task_account.check_allowlist(&device_key, &allowlist_proof)?;
+        if task_account.status == TaskStatus::Pending as u8 {
+            ctx.accounts.network_state.task_taken();
+            ctx.accounts.priority_index.remove(&task_key);
+            ctx.accounts.pending_index.remove(&task_key);
+            ctx.accounts.network_state.dequeue(task_key, &mut task_account, ctx.accounts.queue_prev.as_ref(), ctx.accounts.queue_next.as_ref())?;
+        }
+        task_account.assign(device_key, clock.unix_timestamp, false, ctx.accounts.network_config.expiry_multiplier);
+        
+        emit!(TaskAssigned {
+            task: task_key,
+            device: device_key,
+            accepted: false,
+        });
+        
+        msg!("Task {} assigned to device {}", task_id, device_account.device_id_str());
+        Ok(())
+    }
+
+    pub fn claim_task(
+        ctx: Context<ClaimTask>,
+        task_id: String,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        // The first eligible device to land its claim wins; later claims fail on TaskNotPending
+        require!(task_account.redundancy == 1, ComputeError::RedundantTask);
+        task_account.check_eligibility(&device_account, &ctx.accounts.network_config, clock.unix_timestamp)?;
+        task_account.check_allowlist(&device_key, &allowlist_proof)?;
+        require!(device_account.current_load <= MAX_CLAIM_LOAD, ComputeError::DeviceOverloaded);
+        // Queued tasks are claimed in FIFO order; a device may only jump the head if it cannot take it.
+        // Private heads can always be skipped since non-membership can't be proven.
+        if task_account.queued != 0 && ctx.accounts.network_state.queue_head != task_key {
+            let queue_head = ctx.accounts.queue_head.as_ref()
+                .filter(|head| head.key() == ctx.accounts.network_state.queue_head)
+                .ok_or(ComputeError::InvalidQueueLink)?;
+            let queue_head = queue_head.load()?;
+            require!(
+                queue_head.allowlist_root != [0; 32] ||
+                queue_head.check_eligibility(&device_account, &ctx.accounts.network_config, clock.unix_timestamp).is_err(),
+                ComputeError::QueueOrderViolation
+            );
+        }
+        if task_account.status == TaskStatus::Pending as u8 {
+            ctx.accounts.network_state.task_taken();
+            ctx.accounts.priority_index.remove(&task_key);
+            ctx.accounts.pending_index.remove(&task_key);
+            ctx.accounts.network_state.dequeue(task_key, &mut task_account, ctx.accounts.queue_prev.as_ref(), ctx.accounts.queue_next.as_ref())?;
+        }
+        task_account.assign(device_key, clock.unix_timestamp, true, ctx.accounts.network_config.expiry_multiplier);
+        device_account.start_assignment(&task_account.compute_requirements, &ctx.accounts.network_config)?;
+        ctx.accounts.network_state.assignment_started();
+        
+        let collateral = ctx.accounts.network_config.collateral_for(task_account.reward_amount)?;
+        device_account.lock_collateral(collateral)?;
+        task_account.collateral = collateral;
+        
+        emit!(TaskAssigned {
+            task: task_key,
+            device: device_key,
+            accepted: true,
+        });
+        
+        emit!(CollateralLocked {
+            task: task_key,
+            device: device_key,
+            amount: collateral,
+        });
+        
+        msg!("Task {} claimed by device {}", task_id, device_account.device_id_str());
+        Ok(())
+    }
+
+    pub fn claim_redundant_task(
+        ctx: Context<ClaimRedundantTask>,
+        task_id: String,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        require!(task_account.redundancy > 1, ComputeError::NotRedundantTask);
+        task_account.check_eligibility(&device_account, &ctx.accounts.network_config, clock.unix_timestamp)?;
+        task_account.check_allowlist(&device_key, &allowlist_proof)?;
+        require!(device_account.current_load <= MAX_CLAIM_LOAD, ComputeError::DeviceOverloaded);
+        
+        // All replicas share one deadline, started by the first claim
+        if task_account.assigned_count == 0 {
+            task_account.assigned_at = clock.unix_timestamp;
+            task_account.expires_at = clock.unix_timestamp + task_account.compute_requirements.estimated_duration as i64
+                * ctx.accounts.network_config.expiry_multiplier as i64;
+        }
+        task_account.assigned_count += 1;
+        if task_account.assigned_count == task_account.redundancy {
+            task_account.status = TaskStatus::InProgress as u8;
+            ctx.accounts.network_state.task_taken();
+            ctx.accounts.priority_index.remove(&task_key);
+            ctx.accounts.pending_index.remove(&task_key);
+            ctx.accounts.network_state.dequeue(task_key, &mut task_account, ctx.accounts.queue_prev.as_ref(), ctx.accounts.queue_next.as_ref())?;
+        }
+        device_account.start_assignment(&task_account.compute_requirements, &ctx.accounts.network_config)?;
+        ctx.accounts.network_state.assignment_started();
+        
+        // Each replica bonds collateral against its own slice of the reward
+        let collateral = ctx.accounts.network_config.collateral_for(task_account.replica_reward()?)?;
+        device_account.lock_collateral(collateral)?;
+        task_account.collateral += collateral;
+        
+        let execution = &mut ctx.accounts.execution;
+        execution.task = task_key;
+        execution.device = device_key;
+        execution.result_hash = String::new();
+        execution.submitted = false;
+        execution.rewarded = false;
+        execution.submitted_at = 0;
+        execution.collateral = collateral;
+        
+        emit!(ReplicaClaimed {
+            task: task_key,
+            device: device_key,
+            assigned_count: task_account.assigned_count,
+        });
+        
+        emit!(CollateralLocked {
+            task: task_key,
+            device: device_key,
+            amount: collateral,
+        });
+        
+        msg!("Task {} replica {}/{} claimed by device {}", 
+            task_id, task_account.assigned_count, task_account.redundancy, device_account.device_id_str());
+        Ok(())
+    }
+
+    pub fn submit_redundant_result(
+        ctx: Context<SubmitRedundantResult>,
+        task_id: String,
+        result_hash: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let execution = &mut ctx.accounts.execution;
+        let clock = Clock::get()?;
+        
+        // Replicas may run before every slot is filled
+        require!(
+            [TaskStatus::Pending as u8, TaskStatus::InProgress as u8].contains(&task_account.status),
+            ComputeError::TaskNotInProgress
+        );
+        require!(!execution.submitted, ComputeError::ResultAlreadySubmitted);
+        require!(clock.unix_timestamp <= task_account.expires_at, ComputeError::TaskExpired);
+        
+        execution.result_hash = result_hash;
+        execution.submitted = true;
+        execution.submitted_at = clock.unix_timestamp;
+        task_account.results_submitted += 1;
+        
+        device_account.active_assignments = device_account.active_assignments.saturating_sub(1);
+        device_account.release(&task_account.compute_requirements);
+        device_account.cumulative_completion_seconds += (clock.unix_timestamp - task_account.assigned_at).max(0) as u64;
+        device_account.touch(clock.unix_timestamp);
+        ctx.accounts.network_state.assignment_ended();
+        
+        emit!(ReplicaResultSubmitted {
+            task: task_key,
+            device: device_key,
+            result_hash: execution.result_hash.clone(),
+        });
+        
+        msg!("Task {} replica result submitted by device {}", task_id, device_account.device_id_str());
+        Ok(())
+    }
+
+    /// Settles a redundant task by majority vote. remaining_accounts holds an [execution, device_account]
+    /// pair for every replica that claimed it. Replicas that never submitted are released and, like those
+    /// outvoted by the majority, forfeit their collateral; the rest get theirs back. Each agreeing replica
+    /// earns a 1/redundancy slice of the escrow and the slices nobody earned go back to the submitter.
+    pub fn settle_redundant_task<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleRedundantTask<'info>>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        require!(
+            [TaskStatus::Pending as u8, TaskStatus::InProgress as u8].contains(&task_account.status) && task_account.assigned_count > 0,
+            ComputeError::TaskNotInProgress
+        );
+        require!(
+            task_account.results_submitted == task_account.redundancy || clock.unix_timestamp > task_account.expires_at,
+            ComputeError::ResultsOutstanding
+        );
+        require!(
+            ctx.remaining_accounts.len() == 2 * task_account.assigned_count as usize,
+            ComputeError::ExecutionMismatch
+        );
+        
+        let mut replicas: Vec<(Account<'info, Execution>, AccountLoader<'info, DeviceAccount>)> =
+            Vec::with_capacity(task_account.assigned_count as usize);
+        for accounts in ctx.remaining_accounts.chunks(2) {
+            let execution = Account::<Execution>::try_from(&accounts[0])?;
+            let device_loader = AccountLoader::<DeviceAccount>::try_from(&accounts[1])?;
+            require!(is_current(&device_loader), ComputeError::AccountNeedsMigration);
+            require!(
+                execution.task == task_key && execution.device == device_loader.key(),
+                ComputeError::ExecutionMismatch
+            );
+            require!(
+                replicas.iter().all(|(other, _)| other.key() != execution.key()),
+                ComputeError::ExecutionMismatch
+            );
+            replicas.push((execution, device_loader));
+        }
+        
+        // Majority hash agreement across all requested replicas
+        let hashes: Vec<&String> = replicas.iter()
+            .filter(|(execution, _)| execution.submitted)
+            .map(|(execution, _)| &execution.result_hash)
+            .collect();
+        let (winning_hash, agreeing) = hashes.iter()
+            .map(|hash| (*hash, hashes.iter().filter(|other| *other == hash).count()))
+            .max_by_key(|(_, count)| *count)
+            .map(|(hash, count)| (hash.clone(), count as u8))
+            .unwrap_or_default();
+        let succeeded = agreeing > task_account.redundancy / 2;
+        
+        let network_mint = task_account.pays_in_network_mint(&ctx.accounts.network_state.reward_mint);
+        let seeds = &[
+            b"network_state".as_ref(),
+            &[ctx.bumps.network_state]
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        
+        let mut forfeited_to_treasury = 0;
+        for (execution, device_loader) in replicas.iter_mut() {
+            let device_key = device_loader.key();
+            let mut device_account = device_loader.load_mut()?;
+            
+            // A replica that never submitted still holds its slot and reserved resources
+            if !execution.submitted {
+                device_account.active_assignments = device_account.active_assignments.saturating_sub(1);
+                device_account.release(&task_account.compute_requirements);
+                device_account.tasks_expired += 1;
+                ctx.accounts.network_state.assignment_ended();
+            }
+            
+            // Without a majority nobody is proven wrong, so only replicas that never submitted forfeit
+            let outvoted = if succeeded { execution.result_hash != winning_hash } else { !execution.submitted };
+            let collateral = execution.collateral;
+            let forfeited = if outvoted { collateral.min(device_account.staked_amount) } else { 0 };
+            device_account.release_collateral(collateral);
+            task_account.collateral = task_account.collateral.saturating_sub(collateral);
+            execution.collateral = 0;
+            execution.exit(ctx.program_id)?;
+            
+            // Collateral is staked in the network mint, so it only reaches submitters paying in that mint
+            if forfeited > 0 {
+                let to = if network_mint {
+                    ctx.accounts.submitter_token_account.as_ref()
+                        .ok_or(ComputeError::SubmitterAccountMissing)?
+                        .to_account_info()
+                } else {
+                    forfeited_to_treasury += forfeited;
+                    ctx.accounts.treasury.to_account_info()
+                };
+                let cpi_accounts = TransferChecked {
+                    from: ctx.accounts.stake_vault.to_account_info(),
+                    mint: ctx.accounts.network_mint.to_account_info(),
+                    to,
+                    authority: ctx.accounts.network_state.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds);
+                token_interface::transfer_checked(cpi_ctx, forfeited, ctx.accounts.network_mint.decimals)?;
+                device_account.slash_stake(forfeited, &mut ctx.accounts.network_state);
+                device_account.tier = device_account.eligible_tier(&ctx.accounts.network_config) as u8;
+            }
+            
+            if collateral > 0 {
+                emit!(CollateralReleased {
+                    task: task_key,
+                    device: device_key,
+                    amount: if outvoted { forfeited } else { collateral },
+                    forfeited: outvoted,
+                });
+            }
+        }
+        ctx.accounts.network_state.accrue_burn(forfeited_to_treasury, &ctx.accounts.network_config);
+        
+        task_account.completed_at = clock.unix_timestamp;
+        let mut refund = 0;
+        if succeeded {
+            task_account.status = TaskStatus::Completed as u8;
+            task_account.result_hash = to_fixed_bytes(&winning_hash)?;
+            task_account.agreeing_count = agreeing;
+            task_account.is_verified = 1;
+            task_account.reward_released = 1;
+            ctx.accounts.network_state.total_tasks_completed += 1;
+            
+            // Slices of replicas that never claimed, never submitted or were outvoted are refunded
+            let earned = task_account.replica_reward()?
+                .checked_add(task_account.replica_priority_fee())
+                .and_then(|slice| slice.checked_mul(agreeing as u64))
+                .ok_or(ComputeError::MathOverflow)?;
+            refund = task_account.reward_amount
+                .checked_add(task_account.priority_fee)
+                .and_then(|escrow| escrow.checked_sub(earned))
+                .ok_or(ComputeError::MathOverflow)?;
+            if refund > 0 {
+                let submitter_token_account = ctx.accounts.submitter_token_account.as_ref()
+                    .ok_or(ComputeError::TokenAccountRequired)?;
+                let cpi_accounts = TransferChecked {
+                    from: ctx.accounts.reward_vault.to_account_info(),
+                    mint: ctx.accounts.reward_mint.to_account_info(),
+                    to: submitter_token_account.to_account_info(),
+                    authority: ctx.accounts.network_state.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                token_interface::transfer_checked(cpi_ctx, refund, ctx.accounts.reward_mint.decimals)?;
+                let submitter_profile = &mut ctx.accounts.submitter_profile;
+                submitter_profile.total_rewards_paid = submitter_profile.total_rewards_paid.saturating_sub(refund);
+            }
+        } else {
+            // No majority: the escrow stays refundable through refund_failed_task
+            task_account.status = TaskStatus::Failed as u8;
+        }
+        
+        emit!(RedundantTaskSettled {
+            task: task_key,
+            agreeing_count: agreeing,
+            succeeded,
+            refund,
+        });
+        
+        msg!("Redundant task {} settled: {} of {} replicas agreed", task_id, agreeing, task_account.redundancy);
+        Ok(())
+    }
+
+    pub fn claim_redundant_reward(
+        ctx: Context<ClaimRedundantReward>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let execution = &mut ctx.accounts.execution;
+        let clock = Clock::get()?;
+        
+        require!(task_account.is_verified != 0 && task_account.redundancy > 1, ComputeError::TaskNotCompleted);
+        require!(!execution.rewarded, ComputeError::RewardAlreadyClaimed);
+        require!(
+            execution.submitted && execution.result_hash == task_account.result_hash_str(),
+            ComputeError::NotEligibleForReward
+        );
+        
+        let gross_share = task_account.replica_reward()?;
+        let protocol_fee = gross_share
+            .checked_mul(ctx.accounts.network_config.protocol_fee_bps)
+            .ok_or(ComputeError::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ComputeError::MathOverflow)?;
+        let share = gross_share
+            .checked_sub(protocol_fee)
+            .and_then(|net| net.checked_add(task_account.replica_priority_fee()))
+            .ok_or(ComputeError::MathOverflow)?;
+        execution.rewarded = true;
+        task_account.replica_claims += 1;
+        
+        let network_mint = task_account.reward_mint == ctx.accounts.network_state.reward_mint;
+        let seeds = &[
+            b"network_state".as_ref(),
+            &[ctx.bumps.network_state]
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        if protocol_fee > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.network_state.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds);
+            token_interface::transfer_checked(cpi_ctx, protocol_fee, ctx.accounts.reward_mint.decimals)?;
+            if network_mint {
+                ctx.accounts.network_state.accrue_burn(protocol_fee, &ctx.accounts.network_config);
+            }
+        }
+        
+        if network_mint {
+            credit_reward(
+                device_key,
+                &mut device_account,
+                ctx.accounts.vesting_account.as_deref_mut(),
+                &ctx.accounts.network_config,
+                share,
+                clock.unix_timestamp,
+            )?;
+            device_account.total_tokens_earned += share;
+            ctx.accounts.network_state.total_tokens_distributed += share;
+        } else {
+            let device_token_account = ctx.accounts.device_token_account.as_ref()
+                .ok_or(ComputeError::DeviceTokenAccountRequired)?;
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: device_token_account.to_account_info(),
+                authority: ctx.accounts.network_state.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_interface::transfer_checked(cpi_ctx, share, ctx.accounts.reward_mint.decimals)?;
+        }
+        device_account.total_tasks_completed += 1;
+        device_account.touch(clock.unix_timestamp);
+        device_account.reputation_score = device_account.reputation_score.saturating_add(task_account.scaled_reputation(5, &ctx.accounts.network_config));
+        
+        emit!(ReplicaRewardPaid {
+            task: task_key,
+            device: device_key,
+            amount: share,
+            protocol_fee,
+        });
+        
+        msg!("Device {} claimed {} for redundant task {}", device_account.device_id_str(), share, task_id);
+        Ok(())
+    }
+
+    pub fn accept_assignment(
+        ctx: Context<RespondToAssignment>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        require!(task_account.status == TaskStatus::Assigned as u8, ComputeError::TaskNotAssigned);
+        require!(task_account.assigned_device == device_key, ComputeError::DeviceNotAssigned);
+        require!(task_account.assignment_accepted == 0, ComputeError::AssignmentAlreadyAccepted);
+        require!(
+            clock.unix_timestamp <= task_account.assigned_at + ACCEPT_TIMEOUT,
+            ComputeError::AcceptanceWindowClosed
+        );
+        
+        task_account.assignment_accepted = 1;
+        device_account.start_assignment(&task_account.compute_requirements, &ctx.accounts.network_config)?;
+        ctx.accounts.network_state.assignment_started();
+        
+        let collateral = ctx.accounts.network_config.collateral_for(task_account.reward_amount)?;
+        device_account.lock_collateral(collateral)?;
+        task_account.collateral = collateral;
+        
+        emit!(AssignmentAccepted {
+            task: task_key,
+            device: device_key,
+        });
+        
+        emit!(CollateralLocked {
+            task: task_key,
+            device: device_key,
+            amount: collateral,
+        });
+        
+        msg!("Task {} accepted by device {}", task_id, device_account.device_id_str());
+        Ok(())
+    }
+
+    pub fn reject_assignment(
+        ctx: Context<RespondToAssignment>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let device_account = ctx.accounts.device_account.load()?;
+        
+        require!(task_account.status == TaskStatus::Assigned as u8, ComputeError::TaskNotAssigned);
+        require!(task_account.assigned_device == device_key, ComputeError::DeviceNotAssigned);
+        require!(task_account.assignment_accepted == 0, ComputeError::AssignmentAlreadyAccepted);
+        
+        // Declining work the device never agreed to carries no reputation penalty.
+        // Rejected direct tasks stay unlisted until refund_direct_task returns the escrow,
+        // and pool tasks go back to their operator for routing.
+        task_account.reset_assignment();
+        if task_account.is_pool_task() {
+            ctx.accounts.network_state.task_opened();
+        } else if task_account.direct == 0 {
+            ctx.accounts.network_state.task_opened();
+            ctx.accounts.priority_index.insert(task_key, task_account.priority_fee);
+            ctx.accounts.pending_index.push(task_key);
+            ctx.accounts.network_state.enqueue(task_key, &mut task_account, ctx.accounts.queue_tail.as_ref())?;
+        }
+        
+        emit!(AssignmentRejected {
+            task: task_key,
+            device: device_key,
+        });
+        
+        msg!("Task {} rejected by device {}", task_id, device_account.device_id_str());
+        Ok(())
+    }
+
+    pub fn start_task(
+        ctx: Context<StartTask>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let device_account = ctx.accounts.device_account.load()?;
+        let clock = Clock::get()?;
+        
+        device_account.check_authority(
+            &ctx.accounts.authority.key(),
+            ctx.accounts.agent_authority.as_deref(),
+            ctx.accounts.license_token_account.as_deref(),
+            AGENT_PERMISSION_START,
+            clock.unix_timestamp,
+        )?;
+        require!(task_account.status == TaskStatus::Assigned as u8, ComputeError::TaskNotAssigned);
+        require!(task_account.assigned_device == device_key, ComputeError::DeviceNotAssigned);
+        require!(task_account.assignment_accepted != 0, ComputeError::AssignmentNotAccepted);
+        require!(task_account.expires_at >= clock.unix_timestamp, ComputeError::TaskExpired);
+        
+        task_account.status = TaskStatus::InProgress as u8;
+        task_account.started_at = clock.unix_timestamp;
+        task_account.last_heartbeat = clock.unix_timestamp;
+        
+        emit!(TaskStarted {
+            task: task_key,
+            device: device_key,
+        });
+        
+        msg!("Task {} started by device {}", task_id, device_account.device_id_str());
+        Ok(())
+    }
+
+    pub fn report_progress(
+        ctx: Context<ReportProgress>,
+        task_id: String,
+        progress: u8,
+        checkpoint_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let device_account = ctx.accounts.device_account.load()?;
+        let clock = Clock::get()?;
+        
+        device_account.check_authority(
+            &ctx.accounts.authority.key(),
+            ctx.accounts.agent_authority.as_deref(),
+            ctx.accounts.license_token_account.as_deref(),
+            AGENT_PERMISSION_HEARTBEAT,
+            clock.unix_timestamp,
+        )?;
+        require!(task_account.status == TaskStatus::InProgress as u8, ComputeError::TaskNotInProgress);
+        require!(task_account.assigned_device == device_key, ComputeError::DeviceNotAssigned);
+        require!(progress <= 100, ComputeError::InvalidProgress);
+        
+        task_account.progress = progress;
+        task_account.last_heartbeat = clock.unix_timestamp;
+        if let Some(hash) = checkpoint_hash {
+            task_account.checkpoint_hash = hash;
+        }
+        
+        emit!(TaskProgress {
+            task: task_key,
+            device: device_key,
+            progress,
+        });
+        
+        msg!("Task {} progress {}% on device {}", task_id, progress, device_account.device_id_str());
+        Ok(())
+    }
+
+    pub fn complete_task(
+        ctx: Context<CompleteTask>,
+        task_id: String,
+        result_hash: String,
+        result_uri: String,
+        result_size_bytes: u64,
+        result_mime: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        device_account.check_authority(
+            &ctx.accounts.authority.key(),
+            ctx.accounts.agent_authority.as_deref(),
+            ctx.accounts.license_token_account.as_deref(),
+            AGENT_PERMISSION_COMPLETE,
+            clock.unix_timestamp,
+        )?;
+        require!(task_account.status == TaskStatus::InProgress as u8, ComputeError::TaskNotInProgress);
+        require!(task_account.assigned_device == device_key, ComputeError::DeviceNotAssigned);
+        validate_result_uri(&result_uri)?;
+        validate_mime_type(&result_mime)?;
+        require!(
+            task_account.compute_requirements.storage_proof_required == 0 || is_content_addressed(&result_uri),
+            ComputeError::InvalidResultUri
+        );
+        
+        // Check task expiration; late results are still accepted during the grace period
+        if task_account.expires_at + ctx.accounts.network_config.grace_period < clock.unix_timestamp {
+            task_account.status = TaskStatus::Failed as u8;
+            device_account.reputation_score = device_account.reputation_score.saturating_sub(task_account.scaled_reputation(10, &ctx.accounts.network_config));
+            return Err(ComputeError::TaskExpired.into());
+        }
+        
+        task_account.status = TaskStatus::Completed as u8;
+        task_account.result_hash = to_fixed_bytes(&result_hash)?;
+        task_account.result_uri = to_fixed_bytes(&result_uri)?;
+        task_account.result_size_bytes = result_size_bytes;
+        task_account.result_mime = to_fixed_bytes(&result_mime)?;
+        task_account.storage_proved_at = 0;
+        task_account.completed_at = clock.unix_timestamp;
+        if task_account.verification_mode == VerificationMode::Committee as u8 || task_account.re_executed() {
+            task_account.open_verification_round(clock.unix_timestamp);
+        } else {
+            task_account.challenge_deadline = clock.unix_timestamp + CHALLENGE_WINDOW;
+        }
+        
+        // Calculate performance bonus
+        let time_taken = clock.unix_timestamp - task_account.assigned_at;
+        let estimated_time = task_account.compute_requirements.estimated_duration as i64;
+        let performance_multiplier = if time_taken < estimated_time {
+            10000 + ctx.accounts.network_config.speed_bonus_bps // bonus for faster completion
+        } else {
+            10000
+        };
+        
+        // A USD-priced reward is converted at the current price, capped by the escrow; the rest is refunded
+        let (reward_amount, usd_refund) = if task_account.usd_reward_cents > 0 {
+            let price_update = ctx.accounts.price_update.as_ref().ok_or(ComputeError::InvalidPriceAccount)?;
+            let converted = usd_to_tokens(
+                price_update,
+                &ctx.accounts.network_config,
+                task_account.usd_reward_cents,
+                ctx.accounts.reward_mint.decimals,
+                clock.unix_timestamp,
+            )?;
+            let reward_amount = converted.min(task_account.reward_amount);
+            (reward_amount, task_account.reward_amount - reward_amount)
+        } else {
+            (task_account.reward_amount, 0)
+        };
+        
+        // Carve out the verifiers' share before paying the device
+        let verification_fee = reward_amount
+            .checked_mul(VERIFICATION_FEE_BPS)
+            .ok_or(ComputeError::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ComputeError::MathOverflow)?;
+        task_account.verification_pool = verification_fee;
+        
+        // The protocol's cut goes to the treasury
+        let protocol_fee = reward_amount
+            .checked_mul(ctx.accounts.network_config.protocol_fee_bps)
+            .ok_or(ComputeError::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ComputeError::MathOverflow)?;
+        
+        // A late result earns a decayed share; the rest goes back to the submitter
+        let net_reward = reward_amount - verification_fee - protocol_fee;
+        let late_reward = net_reward
+            .checked_mul(task_account.late_reward_bps(clock.unix_timestamp, &ctx.accounts.network_config))
+            .ok_or(ComputeError::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ComputeError::MathOverflow)?;
+        let late_refund = net_reward - late_reward + usd_refund;
+        
+        // The priority fee goes to the device on top of its reward. The speed and tier bonuses are
+        // paid out of the network mint's vault; a SOL escrow or another mint's vault only holds
+        // submitters' escrow, so those tasks pay exactly what was escrowed.
+        let network_mint = task_account.reward_kind != RewardKind::Sol as u8 &&
+            task_account.reward_mint == ctx.accounts.network_state.reward_mint;
+        let adjusted_reward = if !network_mint {
+            late_reward + task_account.priority_fee
+        } else {
+            late_reward
+                .checked_mul(performance_multiplier)
+                .ok_or(ComputeError::MathOverflow)?
+                .checked_div(10000)
+                .ok_or(ComputeError::MathOverflow)?
+                .checked_mul(ctx.accounts.network_config.tier_multiplier_bps(device_account.tier))
+                .ok_or(ComputeError::MathOverflow)?
+                .checked_div(10000)
+                .ok_or(ComputeError::MathOverflow)?
+                + task_account.priority_fee
+        };
+        
+        // A pool's operator takes its commission out of member rewards paid in network tokens or SOL
+        require!(
+            device_account.pool == Pubkey::default() || ctx.accounts.pool.is_some(),
+            ComputeError::PoolAccountMissing
+        );
+        let commission = match ctx.accounts.pool.as_ref() {
+            Some(pool) if network_mint || task_account.reward_kind == RewardKind::Sol as u8 => pool.commission(adjusted_reward),
+            _ => 0,
+        };
+        
+        if task_account.reward_kind == RewardKind::Sol as u8 {
+            let escrow = SolEscrow::new(task_key, ctx.accounts.sol_escrow.as_ref(), ctx.bumps.sol_escrow, &ctx.accounts.system_program)?;
+            let submitter = ctx.accounts.submitter.as_ref().ok_or(ComputeError::SolAccountsMissing)?;
+            escrow.pay(&ctx.accounts.network_state.to_account_info(), protocol_fee)?;
+            escrow.pay(&submitter.to_account_info(), late_refund)?;
+            if let Some(pool) = ctx.accounts.pool.as_mut().filter(|_| commission > 0) {
+                escrow.pay(&pool.to_account_info(), commission)?;
+                pool.sol_commission += commission;
+            }
+            escrow.pay(&ctx.accounts.owner.to_account_info(), adjusted_reward - commission)?;
+            ctx.accounts.network_state.sol_fees += protocol_fee;
+        } else {
+            let seeds = &[
+                b"network_state".as_ref(),
+                &[ctx.bumps.network_state]
+            ];
+            let signer_seeds = &[&seeds[..]];
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            if protocol_fee > 0 {
+                let cpi_accounts = TransferChecked {
+                    from: ctx.accounts.reward_vault.to_account_info(),
+                    mint: ctx.accounts.reward_mint.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                    authority: ctx.accounts.network_state.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds);
+                token_interface::transfer_checked(cpi_ctx, protocol_fee, ctx.accounts.reward_mint.decimals)?;
+                if network_mint {
+                    ctx.accounts.network_state.accrue_burn(protocol_fee, &ctx.accounts.network_config);
+                }
+            }
+            if late_refund > 0 {
+                let submitter_token_account = ctx.accounts.submitter_token_account.as_ref()
+                    .ok_or(ComputeError::TokenAccountRequired)?;
+                let cpi_accounts = TransferChecked {
+                    from: ctx.accounts.reward_vault.to_account_info(),
+                    mint: ctx.accounts.reward_mint.to_account_info(),
+                    to: submitter_token_account.to_account_info(),
+                    authority: ctx.accounts.network_state.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds);
+                token_interface::transfer_checked(cpi_ctx, late_refund, ctx.accounts.reward_mint.decimals)?;
+            }
+            
+            if network_mint {
+                // The reward stays in the vault until the owner pulls it with claim_rewards or claim_vested
+                credit_reward(
+                    device_key,
+                    &mut device_account,
+                    ctx.accounts.vesting_account.as_deref_mut(),
+                    &ctx.accounts.network_config,
+                    adjusted_reward - commission,
+                    clock.unix_timestamp,
+                )?;
+                device_account.total_tokens_earned += adjusted_reward - commission;
+                ctx.accounts.network_state.total_tokens_distributed += adjusted_reward;
+                if let Some(pool) = ctx.accounts.pool.as_mut() {
+                    pool.pending_commission += commission;
+                }
+            } else {
+                // pending_rewards and vesting only hold the network token, so other mints are paid out at once
+                let device_token_account = ctx.accounts.device_token_account.as_ref()
+                    .ok_or(ComputeError::DeviceTokenAccountRequired)?;
+                let cpi_accounts = TransferChecked {
+                    from: ctx.accounts.reward_vault.to_account_info(),
+                    mint: ctx.accounts.reward_mint.to_account_info(),
+                    to: device_token_account.to_account_info(),
+                    authority: ctx.accounts.network_state.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                token_interface::transfer_checked(cpi_ctx, adjusted_reward, ctx.accounts.reward_mint.decimals)?;
+            }
+        }
+        task_account.reward_released = 1;
+        
+        device_account.total_tasks_completed += 1;
+        device_account.active_assignments = device_account.active_assignments.saturating_sub(1);
+        device_account.release(&task_account.compute_requirements);
+        let completion_seconds = (clock.unix_timestamp - task_account.assigned_at).max(0) as u64;
+        device_account.cumulative_completion_seconds += completion_seconds;
+        device_account.touch(clock.unix_timestamp);
+        device_account.reputation_score = device_account.reputation_score.saturating_add(task_account.scaled_reputation(5, &ctx.accounts.network_config));
+        
+        // Only network tokens count towards the tokens-earned ranking
+        let leaderboard_tokens = if network_mint { adjusted_reward } else { 0 };
+        device_account.record_epoch_completion(ctx.accounts.epoch_state.epoch, leaderboard_tokens);
+        if let Some(pool) = ctx.accounts.pool.as_mut() {
+            pool.tasks_completed += 1;
+            device_account.sync_pool_stake(pool);
+        }
+        if let Some(leaderboard) = ctx.accounts.leaderboard.as_mut() {
+            leaderboard.record(
+                device_key,
+                device_account.owner,
+                device_account.epoch_tasks_completed,
+                device_account.epoch_tokens_earned,
+            );
+        }
+        
+        ctx.accounts.network_state.total_tasks_completed += 1;
+        ctx.accounts.network_state.total_completion_seconds += completion_seconds;
+        ctx.accounts.network_state.timed_completions += 1;
+        ctx.accounts.network_state.assignment_ended();
+        
+        emit!(TaskCompleted {
+            task: task_key,
+            device: device_key,
+            result_hash: task_account.result_hash_str(),
+            result_uri,
+            result_size_bytes,
+            reward: adjusted_reward,
+            protocol_fee,
+            late_refund,
+        });
+        
+        msg!("Task {} completed by device {} with reward {}", task_id, device_account.device_id_str(), adjusted_reward);
+        Ok(())
+    }
+
+    pub fn expire_task(
+        ctx: Context<ExpireTask>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        require!(task_account.assigned_device == device_key, ComputeError::DeviceNotAssigned);
+        let transfers = ExpiryTransfers {
+            keeper: ctx.accounts.keeper.key(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            reward_mint: ctx.accounts.reward_mint.to_account_info(),
+            decimals: ctx.accounts.reward_mint.decimals,
+            network_state: ctx.accounts.network_state.to_account_info(),
+            network_state_bump: ctx.bumps.network_state,
+            stake_vault: ctx.accounts.stake_vault.to_account_info(),
+            treasury: ctx.accounts.treasury.to_account_info(),
+            submitter_token_account: ctx.accounts.submitter_token_account.as_ref().map(|account| account.to_account_info()),
+            keeper_token_account: ctx.accounts.keeper_token_account.to_account_info(),
+        };
+        let (slash_amount, slashed_to_treasury) = expire_assignment(
+            task_key,
+            &mut task_account,
+            device_key,
+            &mut device_account,
+            &mut ctx.accounts.network_state,
+            &ctx.accounts.network_config,
+            clock.unix_timestamp,
+            &transfers,
+        )?;
+        ctx.accounts.network_state.assignment_ended();
+        ctx.accounts.network_state.accrue_burn(slashed_to_treasury, &ctx.accounts.network_config);
+        
+        let slash_record = &mut ctx.accounts.slash_record;
+        slash_record.device = device_key;
+        slash_record.task = task_key;
+        slash_record.amount = slash_amount;
+        slash_record.reason = SlashReason::TaskExpired;
+        slash_record.timestamp = clock.unix_timestamp;
+        
+        msg!("Task {} expired on device {}, slashed {}", 
+            task_id, device_account.device_id_str(), slash_amount);
+        Ok(())
+    }
+
+    pub fn slash_device(
+        ctx: Context<SlashDevice>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        // Only results finally rejected by verification; expired tasks are slashed by expire_task
+        require!(
+            task_account.verification_outcome(clock.unix_timestamp) == Some(false),
+            ComputeError::VerificationNotFinalized
+        );
+        require!(task_account.assigned_device == device_key, ComputeError::DeviceNotAssigned);
+        
+        let seeds = &[
+            b"network_state".as_ref(),
+            &[ctx.bumps.network_state]
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        
+        // The task's collateral compensates the submitter for the rejected result
+        let forfeited = task_account.collateral.min(device_account.staked_amount);
+        device_account.release_collateral(task_account.collateral);
+        task_account.collateral = 0;
+        if forfeited > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.stake_vault.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: ctx.accounts.submitter_token_account.to_account_info(),
+                authority: ctx.accounts.network_state.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds);
+            token_interface::transfer_checked(cpi_ctx, forfeited, ctx.accounts.reward_mint.decimals)?;
+            device_account.slash_stake(forfeited, &mut ctx.accounts.network_state);
+        }
+        
+        let slash_amount = device_account.staked_amount
+            .checked_mul(VERIFICATION_SLASH_BPS)
+            .ok_or(ComputeError::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ComputeError::MathOverflow)?;
+        
+        if slash_amount > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.stake_vault.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.network_state.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_interface::transfer_checked(cpi_ctx, slash_amount, ctx.accounts.reward_mint.decimals)?;
+            ctx.accounts.network_state.accrue_burn(slash_amount, &ctx.accounts.network_config);
+            
+            device_account.slash_stake(slash_amount, &mut ctx.accounts.network_state);
+        }
+        device_account.tier = device_account.eligible_tier(&ctx.accounts.network_config) as u8;
+        
+        let slash_record = &mut ctx.accounts.slash_record;
+        slash_record.device = device_key;
+        slash_record.task = task_key;
+        slash_record.amount = slash_amount;
+        slash_record.reason = SlashReason::FailedVerification;
+        slash_record.timestamp = clock.unix_timestamp;
+        
+        emit!(Slashed {
+            device: device_key,
+            task: task_key,
+            amount: slash_amount,
+            reason: SlashReason::FailedVerification,
+        });
+        
+        emit!(CollateralReleased {
+            task: task_key,
+            device: device_key,
+            amount: forfeited,
+            forfeited: true,
+        });
+        
+        msg!("Device {} slashed {} for rejected task {}", device_account.device_id_str(), slash_amount, task_id);
+        Ok(())
+    }
+
+    pub fn refund_failed_task(
+        ctx: Context<RefundFailedTask>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let task_account = ctx.accounts.task_account.load()?;
+        
+        require!(task_account.status == TaskStatus::Failed as u8, ComputeError::TaskNotFailed);
+        require!(task_account.reward_released == 0, ComputeError::RewardAlreadyReleased);
+        // A rejected result's collateral has to go through slash_device first
+        require!(task_account.collateral == 0, ComputeError::CollateralNotSettled);
+        
+        // A share of the escrow is kept by the treasury as a failure penalty
+        let penalty = task_account.reward_amount
+            .checked_mul(FAILED_TASK_PENALTY_BPS)
+            .ok_or(ComputeError::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ComputeError::MathOverflow)?;
+        let refund = (task_account.reward_amount - penalty)
+            .checked_add(task_account.priority_fee)
+            .ok_or(ComputeError::MathOverflow)?;
+        
+        if task_account.reward_kind == RewardKind::Sol as u8 {
+            let escrow = SolEscrow::new(task_key, ctx.accounts.sol_escrow.as_ref(), ctx.bumps.sol_escrow, &ctx.accounts.system_program)?;
+            escrow.pay(&ctx.accounts.network_state.to_account_info(), penalty)?;
+            ctx.accounts.network_state.sol_fees += penalty;
+            // The task account is closed here, so the escrow's rent deposit goes back with the refund
+            escrow.pay(&ctx.accounts.submitter.to_account_info(), escrow.escrow.lamports())?;
+        } else {
+            let seeds = &[
+                b"network_state".as_ref(),
+                &[ctx.bumps.network_state]
+            ];
+            let signer_seeds = &[&seeds[..]];
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            
+            let submitter_token_account = ctx.accounts.submitter_token_account.as_ref()
+                .ok_or(ComputeError::TokenAccountRequired)?;
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: submitter_token_account.to_account_info(),
+                authority: ctx.accounts.network_state.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds);
+            token_interface::transfer_checked(cpi_ctx, refund, ctx.accounts.reward_mint.decimals)?;
+            
+            if penalty > 0 {
+                let cpi_accounts = TransferChecked {
+                    from: ctx.accounts.reward_vault.to_account_info(),
+                    mint: ctx.accounts.reward_mint.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                    authority: ctx.accounts.network_state.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                token_interface::transfer_checked(cpi_ctx, penalty, ctx.accounts.reward_mint.decimals)?;
+            }
+        }
+        
+        let submitter_profile = &mut ctx.accounts.submitter_profile;
+        submitter_profile.total_rewards_paid = submitter_profile.total_rewards_paid.saturating_sub(refund);
+        
+        emit!(TaskRefunded {
+            task: task_key,
+            submitter: task_account.submitter,
+            refund,
+            penalty,
+        });
+        
+        msg!("Failed task {} refunded {} (penalty {})", task_id, refund, penalty);
+        Ok(())
+    }
+
+    pub fn reassign_task(
+        ctx: Context<ReassignTask>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        
+        require!(task_account.status == TaskStatus::Failed as u8, ComputeError::TaskNotFailed);
+        require!(task_account.reward_released == 0, ComputeError::RewardAlreadyReleased);
+        require!(task_account.retry_count < MAX_RETRIES, ComputeError::MaxRetriesExceeded);
+        require!(task_account.collateral == 0, ComputeError::CollateralNotSettled);
+        
+        // The reward is still escrowed, so the task can go straight back on the market, or back
+        // to its pool's operator
+        task_account.retry_count += 1;
+        task_account.reset_assignment();
+        ctx.accounts.network_state.task_opened();
+        if !task_account.is_pool_task() {
+            ctx.accounts.priority_index.insert(task_key, task_account.priority_fee);
+            ctx.accounts.pending_index.push(task_key);
+            ctx.accounts.network_state.enqueue(task_key, &mut task_account, ctx.accounts.queue_tail.as_ref())?;
+        }
+        
+        emit!(TaskReassigned {
+            task: task_key,
+            retry_count: task_account.retry_count,
+        });
+        
+        msg!("Task {} returned to pending (retry {}/{})", task_id, task_account.retry_count, MAX_RETRIES);
+        Ok(())
+    }
+
+    pub fn extend_task_deadline(
+        ctx: Context<ExtendTaskDeadline>,
+        task_id: String,
+        extension: i64,
+        top_up: u64,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        
+        require!(
+            [TaskStatus::Assigned as u8, TaskStatus::InProgress as u8].contains(&task_account.status),
+            ComputeError::TaskNotInProgress
+        );
+        require!(task_account.reward_kind == RewardKind::Token as u8, ComputeError::SolRewardUnsupported);
+        require!(
+            extension > 0 && task_account.deadline_extended + extension <= ctx.accounts.network_config.max_deadline_extension,
+            ComputeError::InvalidDeadlineExtension
+        );
+        
+        // An optional top-up compensates the device for the longer job
+        if top_up > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.submitter_token_account.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: ctx.accounts.reward_vault.to_account_info(),
+                authority: ctx.accounts.submitter.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token_interface::transfer_checked(
+                cpi_ctx,
+                with_transfer_fee(&ctx.accounts.reward_mint, top_up)?,
+                ctx.accounts.reward_mint.decimals,
+            )?;
+            task_account.reward_amount += top_up;
+        }
+        
+        task_account.expires_at += extension;
+        task_account.deadline_extended += extension;
+        
+        emit!(TaskDeadlineExtended {
+            task: task_key,
+            expires_at: task_account.expires_at,
+            top_up,
+            reward_amount: task_account.reward_amount,
+        });
+        
+        msg!("Task {} deadline extended to {}", task_id, task_account.expires_at);
+        Ok(())
+    }
+
+    /// Restricts a pending task to an allowlist of devices, or opens it up again with a zero root
+    pub fn set_task_allowlist(
+        ctx: Context<SetTaskAllowlist>,
+        task_id: String,
+        allowlist_root: [u8; 32],
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        
+        require!(task_account.status == TaskStatus::Pending as u8, ComputeError::TaskNotPending);
+        require!(task_account.assigned_count == 0, ComputeError::TaskNotPending);
+        task_account.allowlist_root = allowlist_root;
+        
+        emit!(TaskAllowlistSet {
+            task: task_key,
+            allowlist_root,
+        });
+        
+        msg!("Task {} allowlist updated", task_id);
+        Ok(())
+    }
+    
+    /// Allocates an on-chain input buffer of `total_len` bytes for a pending task
+    pub fn initialize_task_data(
+        ctx: Context<InitializeTaskData>,
+        task_id: String,
+        total_len: u32,
+    ) -> Result<()> {
+        let task_account = ctx.accounts.task_account.load()?;
+        require!(task_account.status == TaskStatus::Pending as u8, ComputeError::TaskNotPending);
+        require!(total_len > 0 && total_len as usize <= MAX_TASK_DATA_LEN, ComputeError::InvalidDataLength);
+        
+        let task_data = &mut ctx.accounts.task_data;
+        task_data.task = ctx.accounts.task_account.key();
+        task_data.total_len = total_len;
+        task_data.finalized = false;
+        task_data.data = Vec::new();
+        
+        msg!("Task {} data buffer of {} bytes created", task_id, total_len);
+        Ok(())
+    }
+    
+    /// Writes the next chunk of input; chunks are appended in order
+    pub fn append_task_data(
+        ctx: Context<AppendTaskData>,
+        _task_id: String,
+        offset: u32,
+        chunk: Vec<u8>,
+    ) -> Result<()> {
+        let task_data = &mut ctx.accounts.task_data;
+        require!(!task_data.finalized, ComputeError::TaskDataFinalized);
+        require!(!chunk.is_empty() && chunk.len() <= MAX_DATA_CHUNK_LEN, ComputeError::InvalidDataLength);
+        // Offsets make a retried or reordered write fail instead of corrupting the buffer
+        require!(offset as usize == task_data.data.len(), ComputeError::InvalidDataOffset);
+        require!(
+            task_data.data.len() + chunk.len() <= task_data.total_len as usize,
+            ComputeError::InvalidDataLength
+        );
+        task_data.data.extend_from_slice(&chunk);
+        Ok(())
+    }
+    
+    /// Seals the buffer once it matches `data_hash` and links it to the task as its input
+    pub fn finalize_task_data(
+        ctx: Context<FinalizeTaskData>,
+        task_id: String,
+        data_hash: [u8; 32],
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let task_data = &mut ctx.accounts.task_data;
+        
+        require!(task_account.status == TaskStatus::Pending as u8, ComputeError::TaskNotPending);
+        require!(!task_data.finalized, ComputeError::TaskDataFinalized);
+        require!(task_data.data.len() == task_data.total_len as usize, ComputeError::InvalidDataLength);
+        require!(hashv(&[&task_data.data]).to_bytes() == data_hash, ComputeError::DataHashMismatch);
+        
+        task_data.data_hash = data_hash;
+        task_data.finalized = true;
+        task_account.task_data = task_data.key();
+        
+        emit!(TaskDataFinalized {
+            task: task_key,
+            task_data: task_data.key(),
+            len: task_data.total_len,
+            data_hash,
+        });
+        
+        msg!("Task {} data finalized", task_id);
+        Ok(())
+    }
+    
+    /// Points a ZkProof task at the verifier program and verifying key its results are proven against
+    pub fn set_zk_verifier(
+        ctx: Context<SetZkVerifier>,
+        task_id: String,
+        zk_verifier: Pubkey,
+        zk_verifying_key: Pubkey,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        
+        require!(task_account.status == TaskStatus::Pending as u8, ComputeError::TaskNotPending);
+        require!(task_account.verification_mode == VerificationMode::ZkProof as u8, ComputeError::NotZkTask);
+        task_account.zk_verifier = zk_verifier;
+        task_account.zk_verifying_key = zk_verifying_key;
+        
+        emit!(ZkVerifierSet {
+            task: task_key,
+            zk_verifier,
+            zk_verifying_key,
+        });
+        
+        msg!("Task {} zk verifier set", task_id);
+        Ok(())
+    }
+    
+    /// Prices a pending task's reward in USD cents. The escrowed reward_amount becomes a ceiling:
+    /// complete_task pays the token equivalent at the current Pyth price and refunds the rest.
+    pub fn set_usd_reward(
+        ctx: Context<SetUsdReward>,
+        task_id: String,
+        usd_reward_cents: u64,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        
+        require!(ctx.accounts.network_config.usd_price_feed_id != [0; 32], ComputeError::UsdPricingDisabled);
+        require!(task_account.status == TaskStatus::Pending as u8, ComputeError::TaskNotPending);
+        require!(task_account.assigned_count == 0, ComputeError::TaskNotPending);
+        // The feed prices the network token, and replicas would each need their own conversion
+        require!(
+            task_account.reward_kind == RewardKind::Token as u8 &&
+            task_account.reward_mint == ctx.accounts.network_state.reward_mint,
+            ComputeError::RewardMintNotAccepted
+        );
+        require!(task_account.redundancy == 1, ComputeError::RedundantTask);
+        require!(usd_reward_cents > 0, ComputeError::RewardTooLow);
+        task_account.usd_reward_cents = usd_reward_cents;
+        
+        emit!(UsdRewardSet {
+            task: task_key,
+            usd_reward_cents,
+            max_reward: task_account.reward_amount,
+        });
+        
+        msg!("Task {} priced at {} USD cents", task_id, usd_reward_cents);
+        Ok(())
+    }
+    
+    /// Verifies a ZkProof task's result by CPI into its verifier program, without a committee.
+    /// The proof's public inputs are bound to the task's input and result hashes.
+    pub fn verify_zk_proof(
+        ctx: Context<VerifyZkProof>,
+        task_id: String,
+        proof: Vec<u8>,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        
+        require!(task_account.status == TaskStatus::Completed as u8, ComputeError::TaskNotCompleted);
+        require!(task_account.verification_mode == VerificationMode::ZkProof as u8, ComputeError::NotZkTask);
+        require!(task_account.is_verified == 0, ComputeError::TaskAlreadyVerified);
+        require!(task_account.assigned_device == device_key, ComputeError::DeviceNotAssigned);
+        require!(
+            task_account.zk_verifier != Pubkey::default() &&
+            ctx.accounts.zk_verifier.key() == task_account.zk_verifier &&
+            ctx.accounts.zk_verifying_key.key() == task_account.zk_verifying_key,
+            ComputeError::ZkVerifierMismatch
+        );
+        
+        // The verifier program fails the CPI, and so this instruction, if the proof does not check out
+        let mut data = proof;
+        data.extend_from_slice(&to_field_element(hashv(&[&task_account.input_hash]).to_bytes()));
+        data.extend_from_slice(&to_field_element(hashv(&[&task_account.result_hash]).to_bytes()));
+        let ix = Instruction {
+            program_id: task_account.zk_verifier,
+            accounts: vec![AccountMeta::new_readonly(task_account.zk_verifying_key, false)],
+            data,
+        };
+        invoke(&ix, &[
+            ctx.accounts.zk_verifying_key.to_account_info(),
+            ctx.accounts.zk_verifier.to_account_info(),
+        ])?;
+        
+        task_account.is_verified = 1;
+        device_account.reputation_score = device_account.reputation_score.saturating_add(task_account.scaled_reputation(2, &ctx.accounts.network_config));
+        
+        let collateral = task_account.collateral;
+        device_account.release_collateral(collateral);
+        task_account.collateral = 0;
+        
+        emit!(CollateralReleased {
+            task: task_key,
+            device: device_key,
+            amount: collateral,
+            forfeited: false,
+        });
+        
+        emit!(TaskVerified {
+            task: task_key,
+            device: device_key,
+            is_verified: true,
+            valid_verifications: 0,
+            verifications: 0,
+        });
+        
+        msg!("Task {} result verified by zk proof", task_id);
+        Ok(())
+    }
+    
+    /// Called by the storage oracle once it has fetched the task's content-addressed result
+    pub fn prove_storage(
+        ctx: Context<ProveStorage>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        
+        require!(task_account.status == TaskStatus::Completed as u8, ComputeError::TaskNotCompleted);
+        let result_uri = task_account.result_uri_str();
+        require!(is_content_addressed(&result_uri), ComputeError::InvalidResultUri);
+        require!(task_account.storage_proved_at == 0, ComputeError::StorageAlreadyProven);
+        
+        task_account.storage_proved_at = Clock::get()?.unix_timestamp;
+        
+        emit!(StorageProved {
+            task: task_key,
+            oracle: ctx.accounts.oracle.key(),
+            result_uri,
+        });
+        
+        msg!("Task {} result storage proven", task_id);
+        Ok(())
+    }
+    
+    /// Hands the assigned device the payload key, sealed to the encryption key it registered
+    pub fn post_task_key(
+        ctx: Context<PostTaskKey>,
+        task_id: String,
+        submitter_pubkey: [u8; 32],
+        encrypted_key: [u8; ENCRYPTED_KEY_LEN],
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let device_key = ctx.accounts.device_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_account = ctx.accounts.device_account.load()?;
+        
+        require!(
+            task_account.status == TaskStatus::Assigned as u8 || task_account.status == TaskStatus::InProgress as u8,
+            ComputeError::TaskNotAssigned
+        );
+        require!(task_account.assigned_device == device_key, ComputeError::DeviceNotAssigned);
+        require!(device_account.encryption_pubkey != [0; 32], ComputeError::MissingEncryptionKey);
+        
+        task_account.submitter_pubkey = submitter_pubkey;
+        task_account.encrypted_key = encrypted_key;
+        task_account.key_device = device_key;
+        
+        emit!(TaskKeyPosted {
+            task: task_key,
+            device: device_key,
+            device_pubkey: device_account.encryption_pubkey,
+        });
+        
+        msg!("Task {} key posted", task_id);
+        Ok(())
+    }
+    
+    pub fn open_bidding(
+        ctx: Context<OpenBidding>,
+        task_id: String,
+        bid_window: i64,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        require!(task_account.status == TaskStatus::Pending as u8, ComputeError::TaskNotPending);
+        require!(task_account.redundancy == 1, ComputeError::RedundantTask);
+        require!(task_account.bid_deadline == 0, ComputeError::BiddingTask);
+        require!(task_account.reward_kind == RewardKind::Token as u8, ComputeError::SolRewardUnsupported);
+        require!(bid_window > 0, ComputeError::InvalidBidWindow);
+        
+        // The escrowed reward becomes the ceiling for bids
+        task_account.bid_deadline = clock.unix_timestamp + bid_window;
+        task_account.best_bid = task_account.reward_amount;
+        task_account.best_bid_device = Pubkey::default();
+        
+        emit!(BiddingOpened {
+            task: task_key,
+            max_reward: task_account.reward_amount,
+            bid_deadline: task_account.bid_deadline,
+        });
+        
+        msg!("Task {} open for bids until {}", task_id, task_account.bid_deadline);
+        Ok(())
+    }
+
+    pub fn place_bid(
+        ctx: Context<PlaceBid>,
+        task_id: String,
+        amount: u64,
+        eta: u32,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let device_account = ctx.accounts.device_account.load()?;
+        let clock = Clock::get()?;
+        
+        require!(task_account.status == TaskStatus::Pending as u8, ComputeError::TaskNotPending);
+        require!(
+            task_account.bid_deadline != 0 && clock.unix_timestamp <= task_account.bid_deadline,
+            ComputeError::BidWindowClosed
+        );
+        require!(amount > 0 && amount <= task_account.reward_amount, ComputeError::InvalidBid);
+        require!(eta > 0, ComputeError::InvalidBid);
+        task_account.check_device(&device_account, &ctx.accounts.network_config)?;
+        task_account.check_allowlist(&device_key, &allowlist_proof)?;
+        
+        let bid = &mut ctx.accounts.bid;
+        bid.task = task_key;
+        bid.device = device_key;
+        bid.owner = ctx.accounts.owner.key();
+        bid.amount = amount;
+        bid.eta = eta;
+        bid.created_at = clock.unix_timestamp;
+        
+        // Strictly lower bids take the lead, so ties go to the earlier bidder
+        if task_account.best_bid_device == Pubkey::default() || amount < task_account.best_bid {
+            task_account.best_bid = amount;
+            task_account.best_bid_device = device_key;
+        }
+        
+        emit!(BidPlaced {
+            task: task_key,
+            device: device_key,
+            amount,
+            eta,
+        });
+        
+        msg!("Device {} bid {} on task {}", device_account.device_id_str(), amount, task_id);
+        Ok(())
+    }
+
+    pub fn award_task(
+        ctx: Context<AwardTask>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let bid = &ctx.accounts.bid;
+        let clock = Clock::get()?;
+        
+        require!(task_account.status == TaskStatus::Pending as u8, ComputeError::TaskNotPending);
+        require!(task_account.bid_deadline != 0, ComputeError::NotBiddingTask);
+        // The submitter may pick any bid at any time; anyone else can only settle on the lowest bid once bidding closes
+        if ctx.accounts.awarder.key() != task_account.submitter {
+            require!(clock.unix_timestamp > task_account.bid_deadline, ComputeError::BidWindowOpen);
+            require!(bid.device == task_account.best_bid_device, ComputeError::NotBestBid);
+        }
+        task_account.check_device(&device_account, &ctx.accounts.network_config)?;
+        
+        // Only the winning amount stays escrowed
+        let refund = task_account.reward_amount - bid.amount;
+        if refund > 0 {
+            let seeds = &[
+                b"network_state".as_ref(),
+                &[ctx.bumps.network_state]
+            ];
+            let signer_seeds = &[&seeds[..]];
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: ctx.accounts.submitter_token_account.to_account_info(),
+                authority: ctx.accounts.network_state.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_interface::transfer_checked(cpi_ctx, refund, ctx.accounts.reward_mint.decimals)?;
+        }
+        
+        // The device committed to its bid, so the assignment starts out accepted with the promised ETA
+        task_account.reward_amount = bid.amount;
+        task_account.compute_requirements.estimated_duration = bid.eta;
+        task_account.bid_deadline = 0;
+        task_account.assign(device_key, clock.unix_timestamp, true, ctx.accounts.network_config.expiry_multiplier);
+        device_account.start_assignment(&task_account.compute_requirements, &ctx.accounts.network_config)?;
+        ctx.accounts.network_state.assignment_started();
+        ctx.accounts.network_state.task_taken();
+        ctx.accounts.priority_index.remove(&task_key);
+        ctx.accounts.pending_index.remove(&task_key);
+        ctx.accounts.network_state.dequeue(task_key, &mut task_account, ctx.accounts.queue_prev.as_ref(), ctx.accounts.queue_next.as_ref())?;
+        
+        let collateral = ctx.accounts.network_config.collateral_for(task_account.reward_amount)?;
+        device_account.lock_collateral(collateral)?;
+        task_account.collateral = collateral;
+        
+        emit!(TaskAwarded {
+            task: task_key,
+            device: device_key,
+            amount: bid.amount,
+            refund,
+        });
+        
+        emit!(CollateralLocked {
+            task: task_key,
+            device: device_key,
+            amount: collateral,
+        });
+        
+        msg!("Task {} awarded to device {} for {}", task_id, device_account.device_id_str(), bid.amount);
+        Ok(())
+    }
+
+    pub fn withdraw_bid(ctx: Context<WithdrawBid>) -> Result<()> {
+        let bid = &ctx.accounts.bid;
+        
+        // The leading bid stays binding until the auction is settled; bids on closed tasks are always free
+        let task_info = ctx.accounts.task_account.to_account_info();
+        let data = task_info.try_borrow_data()?;
+        if task_info.owner == &crate::ID && data.len() >= 8 && data[..8] == *TaskAccount::DISCRIMINATOR {
+            require!(data.len() >= 8 + TaskAccount::LEN, ComputeError::AccountNeedsMigration);
+            let task_account: TaskAccount = bytemuck::pod_read_unaligned(&data[8..8 + TaskAccount::LEN]);
+            require!(
+                task_account.bid_deadline == 0 || task_account.best_bid_device != bid.device,
+                ComputeError::LeadingBidLocked
+            );
+        }
+        
+        emit!(BidWithdrawn {
+            task: bid.task,
+            device: bid.device,
+        });
+        
+        msg!("Bid by device {} withdrawn", bid.device);
+        Ok(())
+    }
+
+    /// Splits a pending task into subtasks that are matched and verified independently.
+    /// remaining_accounts holds the uninitialized task PDA for each of `subtask_ids`, in order;
+    /// the parent's reward, priority fee, duration, storage and data sizes are divided between them.
+    /// Allowlist, pool routing, USD pricing and input data carry over to every subtask.
+    pub fn split_task<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SplitTask<'info>>,
+        task_id: String,
+        subtask_ids: Vec<String>,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        require!(task_account.status == TaskStatus::Pending as u8, ComputeError::TaskNotPending);
+        require!(task_account.assigned_count == 0, ComputeError::TaskNotPending);
+        require!(task_account.bid_deadline == 0, ComputeError::BiddingTask);
+        require!(task_account.reward_kind == RewardKind::Token as u8, ComputeError::SolRewardUnsupported);
+        require!(
+            (2..=MAX_SUBTASKS).contains(&subtask_ids.len()),
+            ComputeError::InvalidSubtaskCount
+        );
+        require!(ctx.remaining_accounts.len() == subtask_ids.len(), ComputeError::InvalidSubtaskCount);
+        
+        let count = subtask_ids.len() as u64;
+        // Every subtask of a USD-priced task has to keep a non-zero USD price
+        require!(
+            task_account.usd_reward_cents == 0 || task_account.usd_reward_cents >= count,
+            ComputeError::RewardTooLow
+        );
+        
+        // Pool tasks were never listed, so only public tasks leave the queue here
+        let is_pool_task = task_account.is_pool_task();
+        ctx.accounts.network_state.task_taken();
+        if !is_pool_task {
+            ctx.accounts.priority_index.remove(&task_key);
+            ctx.accounts.pending_index.remove(&task_key);
+            ctx.accounts.network_state.dequeue(task_key, &mut task_account, ctx.accounts.queue_prev.as_ref(), ctx.accounts.queue_next.as_ref())?;
+        }
+        
+        let mut compute_requirements = task_account.compute_requirements;
+        compute_requirements.estimated_duration = compute_requirements.estimated_duration.div_ceil(count as u32);
+        compute_requirements.storage_gb_required = compute_requirements.storage_gb_required.div_ceil(count as u16);
+        compute_requirements.input_size_mb = compute_requirements.input_size_mb.div_ceil(count as u32);
+        compute_requirements.output_size_mb = compute_requirements.output_size_mb.div_ceil(count as u32);
+        
+        let mut previous: Option<AccountLoader<'info, TaskAccount>> = None;
+        for (index, (subtask_id, subtask_info)) in subtask_ids.iter().zip(ctx.remaining_accounts.iter()).enumerate() {
+            let subtask_loader = create_task_account(
+                subtask_info,
+                subtask_id,
+                ctx.accounts.submitter.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.program_id,
+            )?;
+            let subtask_key = subtask_loader.key();
+            
+            // The last subtask absorbs the rounding remainder so the escrow is fully allocated
+            let is_last = index as u64 == count - 1;
+            let share = |amount: u64| if is_last {
+                amount - amount / count * (count - 1)
+            } else {
+                amount / count
+            };
+            let reward_amount = share(task_account.reward_amount);
+            let priority_fee = share(task_account.priority_fee);
+            
+            {
+                let mut subtask = subtask_loader.load_mut()?;
+                subtask.submitter = task_account.submitter;
+                subtask.reward_mint = task_account.reward_mint;
+                subtask.task_id = to_fixed_bytes(subtask_id)?;
+                subtask.task_type = task_account.task_type;
+                subtask.compute_requirements = compute_requirements;
+                subtask.reward_amount = reward_amount;
+                subtask.priority_fee = priority_fee;
+                subtask.status = TaskStatus::Pending as u8;
+                subtask.created_at = clock.unix_timestamp;
+                subtask.verification_mode = task_account.verification_mode;
+                subtask.redundancy = task_account.redundancy;
+                subtask.min_reputation = task_account.min_reputation;
+                subtask.usd_reward_cents = share(task_account.usd_reward_cents);
+                subtask.allowlist_root = task_account.allowlist_root;
+                subtask.target_pool = task_account.target_pool;
+                subtask.task_data = task_account.task_data;
+                subtask.input_hash = task_account.input_hash;
+                subtask.parent_task = task_key;
+                subtask.subtask_index = index as u8;
+                
+                ctx.accounts.network_state.task_opened();
+                // Pool subtasks wait for the operator's route_pool_task like their parent did
+                if !is_pool_task {
+                    ctx.accounts.priority_index.insert(subtask_key, priority_fee);
+                    ctx.accounts.pending_index.push(subtask_key);
+                    ctx.accounts.network_state.enqueue(
+                        subtask_key,
+                        &mut subtask,
+                        previous.as_ref().or(ctx.accounts.queue_tail.as_ref()),
+                    )?;
+                }
+            }
+            if !is_pool_task {
+                previous = Some(subtask_loader);
+            }
+        }
+        
+        task_account.status = TaskStatus::Split as u8;
+        task_account.subtask_count = count as u8;
+        
+        emit!(TaskSplit {
+            task: task_key,
+            subtasks: count as u8,
+        });
+        
+        msg!("Task {} split into {} subtasks", task_id, count);
+        Ok(())
+    }
+    
+    /// Completes a split task once every subtask is verified. remaining_accounts holds the subtasks in index
+    /// order; the parent's result hash is the hash of their result hashes.
+    pub fn aggregate_results<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AggregateResults<'info>>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        require!(task_account.status == TaskStatus::Split as u8, ComputeError::TaskNotSplit);
+        require!(
+            ctx.remaining_accounts.len() == task_account.subtask_count as usize,
+            ComputeError::InvalidSubtaskCount
+        );
+        
+        let mut result_hashes: Vec<[u8; 64]> = Vec::with_capacity(ctx.remaining_accounts.len());
+        for (index, subtask_info) in ctx.remaining_accounts.iter().enumerate() {
+            let subtask_loader = AccountLoader::<TaskAccount>::try_from(subtask_info)?;
+            require!(is_current(&subtask_loader), ComputeError::AccountNeedsMigration);
+            let subtask = subtask_loader.load()?;
+            require!(
+                subtask.parent_task == task_key && subtask.subtask_index as usize == index,
+                ComputeError::InvalidSubtaskAccount
+            );
+            require!(
+                subtask.status == TaskStatus::Completed as u8 && subtask.is_verified != 0,
+                ComputeError::SubtaskNotVerified
+            );
+            result_hashes.push(subtask.result_hash);
+        }
+        
+        let slices: Vec<&[u8]> = result_hashes.iter().map(|hash| hash.as_ref()).collect();
+        let combined_hash: String = hashv(&slices)
+            .to_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        
+        task_account.status = TaskStatus::Completed as u8;
+        task_account.result_hash = to_fixed_bytes(&combined_hash)?;
+        task_account.completed_at = clock.unix_timestamp;
+        task_account.is_verified = 1;
+        task_account.reward_released = 1;
+        
+        emit!(ResultsAggregated {
+            task: task_key,
+            result_hash: combined_hash,
+        });
+        
+        msg!("Task {} completed from {} subtasks", task_id, task_account.subtask_count);
+        Ok(())
+    }
+    
+    /// Creates a pipeline of tasks where each stage consumes the previous stage's result.
+    /// remaining_accounts holds the uninitialized task PDA of every stage, in order. The first
+    /// stage is pending right away; later stages wait until advance_pipeline releases them.
+    pub fn create_pipeline<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreatePipeline<'info>>,
+        pipeline_id: String,
+        stages: Vec<PipelineStage>,
+    ) -> Result<()> {
+        let pipeline_key = ctx.accounts.pipeline.key();
+        let clock = Clock::get()?;
+        
+        require!(
+            (1..=MAX_PIPELINE_STAGES).contains(&stages.len()),
+            ComputeError::InvalidPipelineStages
+        );
+        require!(ctx.remaining_accounts.len() == stages.len(), ComputeError::InvalidPipelineStages);
+        for stage in stages.iter() {
+            let (min_reward, _) = ctx.accounts.network_config.reward_terms(
+                &ctx.accounts.network_state.reward_mint,
+                &ctx.accounts.reward_mint.key(),
+                stage.task_type as u8,
+            )?;
+            require!(stage.reward_amount > 0 && stage.reward_amount >= min_reward, ComputeError::RewardTooLow);
+        }
+        
+        let submitter_profile = &mut ctx.accounts.submitter_profile;
+        if submitter_profile.submitter == Pubkey::default() {
+            submitter_profile.submitter = ctx.accounts.submitter.key();
+            submitter_profile.created_at = clock.unix_timestamp;
+        }
+        submitter_profile.check_allowed(&ctx.accounts.network_config)?;
+        let max_disputes_lost = ctx.accounts.network_config.max_disputes_lost;
+        require!(
+            max_disputes_lost == 0 || submitter_profile.disputes_lost < max_disputes_lost,
+            ComputeError::SubmitterRestricted
+        );
+        submitter_profile.record_submission(clock.slot, &ctx.accounts.network_config)?;
+        
+        let mut stage_keys = Vec::with_capacity(stages.len());
+        for (index, (stage, task_info)) in stages.iter().zip(ctx.remaining_accounts.iter()).enumerate() {
+            let task_loader = create_task_account(
+                task_info,
+                &stage.task_id,
+                ctx.accounts.submitter.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.program_id,
+            )?;
+            let task_key = task_loader.key();
+            let mut task_account = task_loader.load_mut()?;
+            task_account.submitter = ctx.accounts.submitter.key();
+            task_account.reward_mint = ctx.accounts.reward_mint.key();
+            task_account.task_id = to_fixed_bytes(&stage.task_id)?;
+            task_account.task_type = stage.task_type as u8;
+            task_account.compute_requirements = stage.compute_requirements;
+            task_account.reward_amount = stage.reward_amount;
+            task_account.verification_mode = stage.verification_mode as u8;
+            task_account.redundancy = 1;
+            task_account.created_at = clock.unix_timestamp;
+            task_account.pipeline = pipeline_key;
+            task_account.pipeline_stage = index as u8;
+            if index == 0 {
+                task_account.status = TaskStatus::Pending as u8;
+                ctx.accounts.network_state.task_opened();
+                ctx.accounts.pending_index.push(task_key);
+                ctx.accounts.network_state.enqueue(task_key, &mut task_account, ctx.accounts.queue_tail.as_ref())?;
+            } else {
+                task_account.status = TaskStatus::Waiting as u8;
+            }
+            stage_keys.push(task_key);
+        }
+        
+        // Escrow every stage's reward up front
+        let total_reward = stages.iter()
+            .try_fold(0u64, |total, stage| total.checked_add(stage.reward_amount))
+            .ok_or(ComputeError::MathOverflow)?;
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.submitter_token_account.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: ctx.accounts.submitter.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(
+            cpi_ctx,
+            with_transfer_fee(&ctx.accounts.reward_mint, total_reward)?,
+            ctx.accounts.reward_mint.decimals,
+        )?;
+        ctx.accounts.submitter_profile.total_rewards_paid += total_reward;
+        
+        let pipeline = &mut ctx.accounts.pipeline;
+        pipeline.submitter = ctx.accounts.submitter.key();
+        pipeline.pipeline_id = pipeline_id.clone();
+        pipeline.stages = stage_keys;
+        pipeline.current_stage = 0;
+        pipeline.created_at = clock.unix_timestamp;
+        
+        emit!(PipelineCreated {
+            pipeline: pipeline_key,
+            submitter: pipeline.submitter,
+            stages: pipeline.stages.len() as u8,
+            total_reward,
+        });
+        
+        msg!("Pipeline {} created with {} stages", pipeline_id, pipeline.stages.len());
+        Ok(())
+    }
+    
+    /// Releases the next pipeline stage once the current one is completed and verified,
+    /// recording the current stage's result hash as the next stage's input
+    pub fn advance_pipeline(
+        ctx: Context<AdvancePipeline>,
+        pipeline_id: String,
+    ) -> Result<()> {
+        let pipeline = &mut ctx.accounts.pipeline;
+        let current_task = ctx.accounts.current_task.load()?;
+        let next_key = ctx.accounts.next_task.key();
+        let mut next_task = ctx.accounts.next_task.load_mut()?;
+        let clock = Clock::get()?;
+        
+        require!(
+            current_task.status == TaskStatus::Completed as u8 && current_task.is_verified != 0,
+            ComputeError::StageNotVerified
+        );
+        require!(next_task.status == TaskStatus::Waiting as u8, ComputeError::StageNotWaiting);
+        
+        next_task.input_hash = current_task.result_hash;
+        next_task.status = TaskStatus::Pending as u8;
+        next_task.created_at = clock.unix_timestamp;
+        ctx.accounts.network_state.task_opened();
+        ctx.accounts.pending_index.push(next_key);
+        ctx.accounts.network_state.enqueue(next_key, &mut next_task, ctx.accounts.queue_tail.as_ref())?;
+        pipeline.current_stage += 1;
+        
+        emit!(PipelineAdvanced {
+            pipeline: pipeline.key(),
+            stage: pipeline.current_stage,
+            task: next_key,
+            input_hash: from_fixed_bytes(&next_task.input_hash),
+        });
+        
+        msg!("Pipeline {} advanced to stage {}", pipeline_id, pipeline.current_stage);
+        Ok(())
+    }
+    
+    /// Schedules a task that is re-submitted every `interval` seconds, funded from an escrowed budget.
+    /// Each run pays `reward_per_run` to the device and `keeper_reward` to whoever spawns it.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_recurring_task(
+        ctx: Context<CreateRecurringTask>,
+        recurring_id: String,
+        task_type: TaskType,
+        compute_requirements: ComputeRequirements,
+        verification_mode: VerificationMode,
+        reward_per_run: u64,
+        keeper_reward: u64,
+        interval: i64,
+        budget: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        
+        require!(recurring_id.len() <= MAX_RECURRING_ID_LEN, ComputeError::FieldTooLong);
+        require!(interval > 0, ComputeError::InvalidInterval);
+        let (min_reward, _) = ctx.accounts.network_config.reward_terms(
+            &ctx.accounts.network_state.reward_mint,
+            &ctx.accounts.reward_mint.key(),
+            task_type as u8,
+        )?;
+        require!(reward_per_run > 0 && reward_per_run >= min_reward, ComputeError::RewardTooLow);
+        let run_cost = reward_per_run.checked_add(keeper_reward).ok_or(ComputeError::MathOverflow)?;
+        require!(budget >= run_cost, ComputeError::InsufficientBudget);
+        
+        let submitter_profile = &mut ctx.accounts.submitter_profile;
+        if submitter_profile.submitter == Pubkey::default() {
+            submitter_profile.submitter = ctx.accounts.submitter.key();
+            submitter_profile.created_at = clock.unix_timestamp;
+        }
+        submitter_profile.check_allowed(&ctx.accounts.network_config)?;
+        let max_disputes_lost = ctx.accounts.network_config.max_disputes_lost;
+        require!(
+            max_disputes_lost == 0 || submitter_profile.disputes_lost < max_disputes_lost,
+            ComputeError::SubmitterRestricted
+        );
+        submitter_profile.total_rewards_paid = submitter_profile.total_rewards_paid
+            .checked_add(budget)
+            .ok_or(ComputeError::MathOverflow)?;
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.submitter_token_account.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: ctx.accounts.submitter.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(
+            cpi_ctx,
+            with_transfer_fee(&ctx.accounts.reward_mint, budget)?,
+            ctx.accounts.reward_mint.decimals,
+        )?;
+        
+        let recurring_task = &mut ctx.accounts.recurring_task;
+        recurring_task.submitter = ctx.accounts.submitter.key();
+        recurring_task.reward_mint = ctx.accounts.reward_mint.key();
+        recurring_task.recurring_id = recurring_id.clone();
+        recurring_task.task_type = task_type as u8;
+        recurring_task.compute_requirements = compute_requirements;
+        recurring_task.verification_mode = verification_mode as u8;
+        recurring_task.reward_per_run = reward_per_run;
+        recurring_task.keeper_reward = keeper_reward;
+        recurring_task.interval = interval;
+        recurring_task.next_run_at = clock.unix_timestamp;
+        recurring_task.budget_remaining = budget;
+        recurring_task.runs = 0;
+        
+        emit!(RecurringTaskCreated {
+            recurring_task: recurring_task.key(),
+            submitter: recurring_task.submitter,
+            reward_per_run,
+            interval,
+            budget,
+        });
+        
+        msg!("Recurring task {} created with budget {}", recurring_id, budget);
+        Ok(())
+    }
+    
+    /// Permissionless crank that materializes the next run of a recurring task as a fresh pending task.
+    /// `task_id` must be the instance id for the current run, see RecurringTask::instance_id.
+    pub fn spawn_recurring_instance(
+        ctx: Context<SpawnRecurringInstance>,
+        recurring_id: String,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_init()?;
+        let recurring_task = &mut ctx.accounts.recurring_task;
+        let clock = Clock::get()?;
+        
+        require!(task_id == recurring_task.instance_id(&recurring_task.key()), ComputeError::TaskAddressMismatch);
+        require!(clock.unix_timestamp >= recurring_task.next_run_at, ComputeError::RunNotDue);
+        let run_cost = recurring_task.reward_per_run
+            .checked_add(recurring_task.keeper_reward)
+            .ok_or(ComputeError::MathOverflow)?;
+        require!(recurring_task.budget_remaining >= run_cost, ComputeError::InsufficientBudget);
+        
+        task_account.submitter = recurring_task.submitter;
+        task_account.reward_mint = recurring_task.reward_mint;
+        task_account.version = TaskAccount::VERSION;
+        task_account.task_id = to_fixed_bytes(&task_id)?;
+        task_account.task_type = recurring_task.task_type;
+        task_account.compute_requirements = recurring_task.compute_requirements;
+        task_account.reward_amount = recurring_task.reward_per_run;
+        task_account.verification_mode = recurring_task.verification_mode;
+        task_account.redundancy = 1;
+        task_account.status = TaskStatus::Pending as u8;
+        task_account.created_at = clock.unix_timestamp;
+        ctx.accounts.network_state.task_opened();
+        ctx.accounts.pending_index.push(task_key);
+        ctx.accounts.network_state.enqueue(task_key, &mut task_account, ctx.accounts.queue_tail.as_ref())?;
+        
+        // Missed runs are skipped rather than spawned back to back
+        let elapsed = clock.unix_timestamp - recurring_task.next_run_at;
+        recurring_task.next_run_at += (elapsed / recurring_task.interval + 1) * recurring_task.interval;
+        recurring_task.budget_remaining -= run_cost;
+        recurring_task.runs += 1;
+        
+        if recurring_task.keeper_reward > 0 {
+            let seeds = &[
+                b"network_state".as_ref(),
+                &[ctx.bumps.network_state]
+            ];
+            let signer_seeds = &[&seeds[..]];
+            
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: ctx.accounts.keeper_token_account.to_account_info(),
+                authority: ctx.accounts.network_state.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_interface::transfer_checked(cpi_ctx, recurring_task.keeper_reward, ctx.accounts.reward_mint.decimals)?;
+        }
+        
+        emit!(RecurringInstanceSpawned {
+            recurring_task: recurring_task.key(),
+            task: task_key,
+            run: recurring_task.runs,
+            keeper: ctx.accounts.keeper.key(),
+            budget_remaining: recurring_task.budget_remaining,
+        });
+        
+        msg!("Recurring task {} spawned run {}", recurring_id, recurring_task.runs);
+        Ok(())
+    }
+    
+    /// Stops a recurring task and refunds the unspent budget; already spawned runs are unaffected
+    pub fn cancel_recurring_task(
+        ctx: Context<CancelRecurringTask>,
+        recurring_id: String,
+    ) -> Result<()> {
+        let refund = ctx.accounts.recurring_task.budget_remaining;
+        
+        let seeds = &[
+            b"network_state".as_ref(),
+            &[ctx.bumps.network_state]
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.submitter_token_account.to_account_info(),
+            authority: ctx.accounts.network_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, refund, ctx.accounts.reward_mint.decimals)?;
+        
+        let submitter_profile = &mut ctx.accounts.submitter_profile;
+        submitter_profile.total_rewards_paid = submitter_profile.total_rewards_paid.saturating_sub(refund);
+        
+        emit!(RecurringTaskCancelled {
+            recurring_task: ctx.accounts.recurring_task.key(),
+            runs: ctx.accounts.recurring_task.runs,
+            refund,
+        });
+        
+        msg!("Recurring task {} cancelled, refunded {}", recurring_id, refund);
+        Ok(())
+    }
+    
+    pub fn close_task(
+        ctx: Context<CloseTask>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let task_account = ctx.accounts.task_account.load()?;
+        let clock = Clock::get()?;
+        
+        task_account.check_closable(clock.unix_timestamp)?;
+        require!(
+            task_account.is_verified != 0 ||
+            clock.unix_timestamp >= task_account.completed_at + TASK_RETENTION_PERIOD,
+            ComputeError::RetentionPeriodNotMet
+        );
+        
+        if task_account.reward_kind == RewardKind::Sol as u8 {
+            let escrow = SolEscrow::new(task_key, ctx.accounts.sol_escrow.as_ref(), ctx.bumps.sol_escrow, &ctx.accounts.system_program)?;
+            escrow.close(&task_account, &ctx.accounts.submitter.to_account_info(), &mut ctx.accounts.network_state, clock.unix_timestamp)?;
+        }
+        
+        emit!(TaskClosed {
+            task: task_key,
+            task_id: task_account.task_id_str(),
+            submitter: task_account.submitter,
+            assigned_device: task_account.assigned_device(),
+            reward_amount: task_account.reward_amount,
+            status: enum_from_u8(task_account.status)?,
+            is_verified: task_account.is_verified != 0,
+            result_hash: task_account.result_hash_str(),
+            completed_at: task_account.completed_at,
+        });
+        
+        msg!("Task {} closed", task_id);
+        Ok(())
+    }
+    
+    /// Sets up the archive over a concurrent merkle tree account the caller has already allocated,
+    /// owned by the compression program and sized for `max_depth` and `max_buffer_size`
+    pub fn init_archive_tree(
+        ctx: Context<InitArchiveTree>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        let archive_tree = &mut ctx.accounts.archive_tree;
+        archive_tree.merkle_tree = ctx.accounts.merkle_tree.key();
+        archive_tree.max_depth = max_depth;
+        archive_tree.max_buffer_size = max_buffer_size;
+        archive_tree.leaf_count = 0;
+        archive_tree.created_at = Clock::get()?.unix_timestamp;
+        
+        let mut data = INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&max_depth.to_le_bytes());
+        data.extend_from_slice(&max_buffer_size.to_le_bytes());
+        ArchiveTree::compression_cpi(
+            archive_tree.to_account_info(),
+            ctx.bumps.archive_tree,
+            data,
+            &ctx.accounts.merkle_tree,
+            &ctx.accounts.log_wrapper,
+            &ctx.accounts.compression_program,
+        )?;
+        
+        emit!(ArchiveTreeInitialized {
+            merkle_tree: archive_tree.merkle_tree,
+            max_depth,
+            max_buffer_size,
+        });
+        
+        msg!("Archive tree {} initialized", archive_tree.merkle_tree);
+        Ok(())
+    }
+    
+    /// Permissionless crank that appends a settled task past its retention window to the archive
+    /// tree and closes its account, returning the rent to the submitter. Tasks close_task would
+    /// refuse, with collateral locked or reward shares unclaimed, are refused here too. The leaf hashes the task's
+    /// full state, which is also emitted so audits can rebuild it and prove it against the tree.
+    pub fn archive_task(
+        ctx: Context<ArchiveTask>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let task_account = ctx.accounts.task_account.load()?;
+        let clock = Clock::get()?;
+        
+        task_account.check_closable(clock.unix_timestamp)?;
+        require!(
+            clock.unix_timestamp >= task_account.completed_at + TASK_RETENTION_PERIOD,
+            ComputeError::RetentionPeriodNotMet
+        );
+        
+        if task_account.reward_kind == RewardKind::Sol as u8 {
+            let escrow = SolEscrow::new(task_key, ctx.accounts.sol_escrow.as_ref(), ctx.bumps.sol_escrow, &ctx.accounts.system_program)?;
+            escrow.close(&task_account, &ctx.accounts.submitter.to_account_info(), &mut ctx.accounts.network_state, clock.unix_timestamp)?;
+        }
+        
+        let task_data = bytemuck::bytes_of(&*task_account).to_vec();
+        let leaf = hashv(&[b"archived_task", task_key.as_ref(), &task_data]).to_bytes();
+        let archive_tree = &mut ctx.accounts.archive_tree;
+        let leaf_index = archive_tree.leaf_count;
+        
+        let mut data = APPEND_LEAF_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&leaf);
+        ArchiveTree::compression_cpi(
+            archive_tree.to_account_info(),
+            ctx.bumps.archive_tree,
+            data,
+            &ctx.accounts.merkle_tree,
+            &ctx.accounts.log_wrapper,
+            &ctx.accounts.compression_program,
+        )?;
+        archive_tree.leaf_count += 1;
+        
+        emit!(TaskArchived {
+            task: task_key,
+            task_id: task_account.task_id_str(),
+            merkle_tree: archive_tree.merkle_tree,
+            leaf_index,
+            leaf,
+            task_data,
+        });
+        
+        msg!("Task {} archived at leaf {}", task_id, leaf_index);
+        Ok(())
+    }
+
+    pub fn deregister_device(ctx: Context<DeregisterDevice>) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        
+        require!(!device_account.is_banned(), ComputeError::DeviceBanned);
+        require!(device_account.staked_amount == 0, ComputeError::DeviceStillStaked);
+        require!(device_account.pool == Pubkey::default(), ComputeError::DeviceInPool);
+        require!(device_account.active_assignments == 0, ComputeError::DeviceHasActiveTasks);
+        require!(device_account.pending_rewards == 0, ComputeError::UnclaimedRewards);
+        
+        // Drop the device's weight from the yield pool; unclaimed yield is forfeited
+        let epoch_state = &mut ctx.accounts.epoch_state;
+        device_account.sync_yield(epoch_state)?;
+        epoch_state.unclaimed_yield = epoch_state.unclaimed_yield.saturating_sub(device_account.pending_yield);
+        device_account.pending_yield = 0;
+        
+        let network_state = &mut ctx.accounts.network_state;
+        network_state.total_devices = network_state.total_devices.saturating_sub(1);
+        network_state.device_status_changed(device_account.is_active != 0, false);
+        
+        emit!(DeviceDeregistered {
+            device: device_key,
+            owner: device_account.owner,
+        });
+        
+        msg!("Device deregistered: {}", device_account.device_id_str());
+        Ok(())
+    }
+
+    pub fn update_device_specs(
+        ctx: Context<UpdateDeviceSpecs>,
+        device_specs: DeviceSpecs,
+    ) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let specs_attested = AttesterRegistry::check_specs_attestation(
+            ctx.accounts.attester_registry.as_ref(),
+            ctx.accounts.instructions.as_ref(),
+            &device_key,
+            &device_specs,
+        )?;
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        require!(
+            clock.unix_timestamp >= device_account.specs_updated_at + SPECS_UPDATE_COOLDOWN,
+            ComputeError::SpecsUpdateCooldown
+        );
+        // Specs are matched against running tasks, so they can only change between jobs
+        require!(device_account.active_assignments == 0, ComputeError::DeviceHasActiveTasks);
+        
+        device_specs.validate()?;
+        
+        // An attestation only carries over while the declared region stays the same
+        let attested = device_account.specs.region_attested != 0 && device_account.specs.region == device_specs.region;
+        // New compute hardware has to be benchmarked again before it counts towards the tier
+        if !device_account.specs.same_hardware(&device_specs) {
+            device_account.benchmark_score = 0;
+            device_account.tier = device_account.eligible_tier(&ctx.accounts.network_config) as u8;
+        }
+        device_account.specs = device_specs;
+        device_account.specs.region_attested = attested as u8;
+        // Attestation covers exactly the specs it signed, so an unsigned update drops it
+        device_account.specs.specs_attested = specs_attested as u8;
+        device_account.specs_updated_at = clock.unix_timestamp;
+        
+        emit!(DeviceSpecsUpdated {
+            device: device_key,
+            specs: device_account.specs,
+        });
+        
+        msg!("Device {} specs updated", device_account.device_id_str());
+        Ok(())
+    }
+    
+    /// Records an off-chain verified region for a device, e.g. from IP or carrier checks
+    pub fn attest_device_region(
+        ctx: Context<AttestDeviceRegion>,
+        region: u16,
+    ) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        
+        device_account.specs.region = region;
+        device_account.specs.region_attested = 1;
+        
+        emit!(DeviceRegionAttested {
+            device: device_key,
+            region,
+        });
+        
+        msg!("Device {} region attested as {}", device_account.device_id_str(), region);
+        Ok(())
+    }
+    
+    /// Publishes a benchmark workload that devices can run to back their tier
+    pub fn publish_benchmark(
+        ctx: Context<PublishBenchmark>,
+        benchmark_id: u16,
+        workload_hash: [u8; 32],
+    ) -> Result<()> {
+        let benchmark = &mut ctx.accounts.benchmark;
+        benchmark.benchmark_id = benchmark_id;
+        benchmark.workload_hash = workload_hash;
+        benchmark.active = true;
+        benchmark.created_at = Clock::get()?.unix_timestamp;
+        
+        emit!(BenchmarkPublished {
+            benchmark: benchmark.key(),
+            benchmark_id,
+            workload_hash,
+        });
+        
+        msg!("Benchmark {} published", benchmark_id);
+        Ok(())
+    }
+    
+    pub fn retire_benchmark(
+        ctx: Context<RetireBenchmark>,
+        benchmark_id: u16,
+    ) -> Result<()> {
+        ctx.accounts.benchmark.active = false;
+        
+        emit!(BenchmarkRetired {
+            benchmark: ctx.accounts.benchmark.key(),
+            benchmark_id,
+        });
+        
+        msg!("Benchmark {} retired", benchmark_id);
+        Ok(())
+    }
+    
+    pub fn initialize_attester_registry(ctx: Context<InitializeAttesterRegistry>) -> Result<()> {
+        ctx.accounts.attester_registry.attesters = Vec::new();
+        msg!("Attester registry initialized");
+        Ok(())
+    }
+    
+    /// Records an attester's integrity verdict for a device; a passing verdict holds for INTEGRITY_VALIDITY
+    pub fn attest_device_integrity(
+        ctx: Context<AttestDeviceIntegrity>,
+        verdict_hash: [u8; 32],
+        verified: bool,
+    ) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let expires_at = Clock::get()?.unix_timestamp + INTEGRITY_VALIDITY;
+        
+        device_account.integrity_verdict_hash = verdict_hash;
+        device_account.integrity_verified = verified as u8;
+        device_account.integrity_expires_at = if verified { expires_at } else { 0 };
+        
+        emit!(DeviceIntegrityAttested {
+            device: device_key,
+            attester: ctx.accounts.attester.key(),
+            verdict_hash,
+            verified,
+            expires_at: device_account.integrity_expires_at,
+        });
+        
+        msg!("Device {} integrity verdict: {}", device_account.device_id_str(), verified);
+        Ok(())
+    }
+    
+    /// Trusts `attester` to sign device spec attestations
+    pub fn add_attester(
+        ctx: Context<ManageAttesters>,
+        attester: Pubkey,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.attester_registry;
+        require!(!registry.attesters.contains(&attester), ComputeError::AttesterAlreadyRegistered);
+        require!(registry.attesters.len() < MAX_ATTESTERS, ComputeError::AttesterRegistryFull);
+        registry.attesters.push(attester);
+        
+        emit!(AttesterChanged {
+            attester,
+            added: true,
+        });
+        
+        msg!("Attester {} added", attester);
+        Ok(())
+    }
+    
+    /// Stops trusting `attester`; devices it already attested keep their flag until their next spec update
+    pub fn remove_attester(
+        ctx: Context<ManageAttesters>,
+        attester: Pubkey,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.attester_registry;
+        let index = registry.attesters.iter()
+            .position(|key| *key == attester)
+            .ok_or(ComputeError::UnknownAttester)?;
+        registry.attesters.swap_remove(index);
+        
+        emit!(AttesterChanged {
+            attester,
+            added: false,
+        });
+        
+        msg!("Attester {} removed", attester);
+        Ok(())
+    }
+    
+    /// Reports a benchmark run for the verifier pool to attest
+    pub fn submit_benchmark(
+        ctx: Context<SubmitBenchmark>,
+        _benchmark_id: u16,
+        score: u64,
+        proof_hash: [u8; 32],
+    ) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let device_account = ctx.accounts.device_account.load()?;
+        let clock = Clock::get()?;
+        let result = &mut ctx.accounts.benchmark_result;
+        
+        require!(ctx.accounts.benchmark.active, ComputeError::BenchmarkInactive);
+        require!(score > 0, ComputeError::InvalidBenchmarkScore);
+        // A pending run blocks a new one unless the hardware it measured has since changed
+        require!(
+            result.status != BenchmarkStatus::Pending || result.submitted_at < device_account.specs_updated_at,
+            ComputeError::BenchmarkPending
+        );
+        
+        result.device = device_key;
+        result.benchmark = ctx.accounts.benchmark.key();
+        result.score = score;
+        result.proof_hash = proof_hash;
+        result.submitted_at = clock.unix_timestamp;
+        result.status = BenchmarkStatus::Pending;
+        result.approvals = 0;
+        result.rejections = 0;
+        result.attesters = Vec::new();
+        
+        emit!(BenchmarkSubmitted {
+            device: device_key,
+            benchmark: result.benchmark,
+            score,
+            proof_hash,
+        });
+        
+        msg!("Device {} submitted benchmark score {}", device_account.device_id_str(), score);
+        Ok(())
+    }
+    
+    /// Records a pool verifier's check of a pending benchmark; a quorum either way settles it
+    pub fn attest_benchmark(
+        ctx: Context<AttestBenchmark>,
+        approve: bool,
+    ) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let verifier_key = ctx.accounts.verifier_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let clock = Clock::get()?;
+        let result = &mut ctx.accounts.benchmark_result;
+        
+        require!(result.status == BenchmarkStatus::Pending, ComputeError::BenchmarkNotPending);
+        // Results measured on hardware the device no longer declares can't be verified
+        require!(result.submitted_at >= device_account.specs_updated_at, ComputeError::BenchmarkNotPending);
+        require!(
+            ctx.accounts.verifier_pool.entries.iter().any(|entry| entry.device == verifier_key),
+            ComputeError::NotInVerifierPool
+        );
+        require!(!result.attesters.contains(&verifier_key), ComputeError::AlreadyAttested);
+        
+        result.attesters.push(verifier_key);
+        if approve {
+            result.approvals += 1;
+        } else {
+            result.rejections += 1;
+        }
+        
+        emit!(BenchmarkAttested {
+            device: device_key,
+            verifier: verifier_key,
+            approve,
+        });
+        
+        if result.approvals >= BENCHMARK_QUORUM {
+            result.status = BenchmarkStatus::Verified;
+            device_account.benchmark_score = result.score;
+            device_account.benchmarked_at = clock.unix_timestamp;
+            let tier = device_account.eligible_tier(&ctx.accounts.network_config);
+            device_account.tier = tier as u8;
+            
+            emit!(BenchmarkVerified {
+                device: device_key,
+                score: result.score,
+                tier,
+            });
+            msg!("Device {} benchmark verified, new tier: {:?}", device_account.device_id_str(), tier);
+        } else if result.rejections >= BENCHMARK_QUORUM {
+            result.status = BenchmarkStatus::Rejected;
+            
+            emit!(BenchmarkRejected {
+                device: device_key,
+                score: result.score,
+            });
+            msg!("Device {} benchmark rejected", device_account.device_id_str());
+        }
+        Ok(())
+    }
+
+    pub fn update_device_status(
+        ctx: Context<UpdateDeviceStatus>,
+        is_active: bool,
+        current_load: u8,
+        battery_percent: u8,
+        is_charging: bool,
+        thermal_state: ThermalState,
+    ) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let now = Clock::get()?.unix_timestamp;
+        
+        device_account.check_authority(
+            &ctx.accounts.authority.key(),
+            ctx.accounts.agent_authority.as_deref(),
+            ctx.accounts.license_token_account.as_deref(),
+            AGENT_PERMISSION_HEARTBEAT,
+            now,
+        )?;
+        require!(battery_percent <= 100, ComputeError::InvalidBatteryLevel);
+        require!(!is_active || !device_account.is_banned(), ComputeError::DeviceBanned);
+        
+        // Settle yield at the old status so offline time earns nothing
+        device_account.sync_yield(&mut ctx.accounts.epoch_state)?;
+        ctx.accounts.network_state.device_status_changed(device_account.is_active != 0, is_active);
+        device_account.touch(now);
+        device_account.is_active = is_active as u8;
+        device_account.current_load = current_load;
+        device_account.battery_percent = battery_percent;
+        device_account.is_charging = is_charging as u8;
+        device_account.thermal_state = thermal_state as u8;
+        device_account.sync_yield(&mut ctx.accounts.epoch_state)?;
+        
+        emit!(DeviceStatusUpdated {
+            device: device_key,
+            is_active,
+            current_load,
+            battery_percent,
+            is_charging,
+            thermal_state,
+        });
+        
+        msg!("Device {} status updated: active={}, load={}", 
+            device_account.device_id_str(), is_active, current_load);
+        Ok(())
+    }
+    
+    /// Delegates status updates, progress reports, starts and completions (per `permissions`) to a
+    /// hot key until `expires_at`. Staking, unstaking and withdrawals always need the owner.
+    pub fn set_agent_authority(
+        ctx: Context<SetAgentAuthority>,
+        agent: Pubkey,
+        permissions: u8,
+        expires_at: i64,
+    ) -> Result<()> {
+        require!(
+            permissions != 0 && permissions & !AGENT_PERMISSIONS_ALL == 0,
+            ComputeError::AgentNotPermitted
+        );
+        require!(expires_at > Clock::get()?.unix_timestamp, ComputeError::AgentExpired);
+        
+        let agent_authority = &mut ctx.accounts.agent_authority;
+        agent_authority.device = ctx.accounts.device_account.key();
+        agent_authority.agent = agent;
+        agent_authority.permissions = permissions;
+        agent_authority.expires_at = expires_at;
+        agent_authority.key_epoch = ctx.accounts.device_account.load()?.agent_key_epoch;
+        
+        emit!(AgentAuthoritySet {
+            device: agent_authority.device,
+            agent,
+            permissions,
+            expires_at,
+        });
+        
+        msg!("Device {} delegated to agent {}", agent_authority.device, agent);
+        Ok(())
+    }
+    
+    /// Swaps the delegated key for `new_agent`, keeping its permissions and expiry. The epoch bump
+    /// rejects the old key at once, even if it is presented again later.
+    pub fn rotate_agent_key(
+        ctx: Context<RotateAgentKey>,
+        new_agent: Pubkey,
+    ) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let agent_authority = &mut ctx.accounts.agent_authority;
+        
+        device_account.agent_key_epoch += 1;
+        let old_agent = agent_authority.agent;
+        agent_authority.agent = new_agent;
+        agent_authority.key_epoch = device_account.agent_key_epoch;
+        
+        emit!(AgentKeyRotated {
+            device: device_key,
+            old_agent,
+            new_agent,
+            key_epoch: device_account.agent_key_epoch,
+        });
+        
+        msg!("Device {} agent key rotated to {}", device_account.device_id_str(), new_agent);
+        Ok(())
+    }
+    
+    /// Invalidates every agent key issued so far and closes the delegation if one is passed.
+    /// Stake, reputation and running assignments are untouched.
+    pub fn revoke_agent_key(ctx: Context<RevokeAgentKey>) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        device_account.agent_key_epoch += 1;
+        
+        emit!(AgentKeyRevoked {
+            device: device_key,
+            agent: ctx.accounts.agent_authority.as_ref().map(|agent_authority| agent_authority.agent),
+            key_epoch: device_account.agent_key_epoch,
+        });
+        
+        msg!("Device {} agent keys revoked", device_account.device_id_str());
+        Ok(())
+    }
+    
+    /// Lets `submitter` post tasks while the network runs in permissioned mode
+    pub fn add_submitter(
+        ctx: Context<AddSubmitter>,
+        submitter: Pubkey,
+    ) -> Result<()> {
+        let submitter_profile = &mut ctx.accounts.submitter_profile;
+        if submitter_profile.submitter == Pubkey::default() {
+            submitter_profile.submitter = submitter;
+            submitter_profile.created_at = Clock::get()?.unix_timestamp;
+        }
+        submitter_profile.allowlisted = true;
+        
+        emit!(SubmitterAllowlistChanged {
+            submitter,
+            allowed: true,
+        });
+        
+        msg!("Submitter {} added to the allowlist", submitter);
+        Ok(())
+    }
+    
+    /// Revokes `submitter`'s allowlisting; tasks it already posted are unaffected
+    pub fn remove_submitter(
+        ctx: Context<RemoveSubmitter>,
+        submitter: Pubkey,
+    ) -> Result<()> {
+        let submitter_profile = &mut ctx.accounts.submitter_profile;
+        require!(submitter_profile.allowlisted, ComputeError::SubmitterNotAllowed);
+        submitter_profile.allowlisted = false;
+        
+        emit!(SubmitterAllowlistChanged {
+            submitter,
+            allowed: false,
+        });
+        
+        msg!("Submitter {} removed from the allowlist", submitter);
+        Ok(())
+    }
+    
+    /// Bans a device pending investigation: it goes offline and can no longer take tasks, claim
+    /// rewards or withdraw stake. Optionally slashes `slash_bps` of its unlocked stake to the treasury.
+    pub fn ban_device(
+        ctx: Context<BanDevice>,
+        slash_bps: u16,
+    ) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require!(!device_account.is_banned(), ComputeError::DeviceBanned);
+        require!(slash_bps <= 10000, ComputeError::InvalidConfig);
+        
+        // Collateral locked for running tasks stays put so those tasks can still be settled
+        let slash_amount = device_account.available_stake()
+            .checked_mul(slash_bps as u64)
+            .ok_or(ComputeError::MathOverflow)?
+            / 10000;
+        
+        device_account.sync_yield(&mut ctx.accounts.epoch_state)?;
+        ctx.accounts.network_state.device_status_changed(device_account.is_active != 0, false);
+        device_account.touch(now);
+        device_account.is_active = 0;
+        device_account.banned_at = now;
+        
+        if slash_amount > 0 {
+            let seeds = &[
+                b"network_state".as_ref(),
+                &[ctx.bumps.network_state]
+            ];
+            let signer_seeds = &[&seeds[..]];
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.stake_vault.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.network_state.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_interface::transfer_checked(cpi_ctx, slash_amount, ctx.accounts.reward_mint.decimals)?;
+            ctx.accounts.network_state.accrue_burn(slash_amount, &ctx.accounts.network_config);
+            
+            device_account.slash_stake(slash_amount, &mut ctx.accounts.network_state);
+            device_account.tier = device_account.eligible_tier(&ctx.accounts.network_config) as u8;
+            
+            emit!(Slashed {
+                device: device_key,
+                task: Pubkey::default(),
+                amount: slash_amount,
+                reason: SlashReason::Banned,
+            });
+        }
+        device_account.sync_yield(&mut ctx.accounts.epoch_state)?;
+        
+        emit!(DeviceBanStatusChanged {
+            device: device_key,
+            banned: true,
+            slashed: slash_amount,
+        });
+        
+        msg!("Device {} banned, slashed {}", device_account.device_id_str(), slash_amount);
+        Ok(())
+    }
+    
+    /// Lifts a ban; the owner brings the device back online through update_device_status
+    pub fn unban_device(ctx: Context<UnbanDevice>) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        
+        require!(device_account.is_banned(), ComputeError::DeviceNotBanned);
+        device_account.banned_at = 0;
+        
+        emit!(DeviceBanStatusChanged {
+            device: device_key,
+            banned: false,
+            slashed: 0,
+        });
+        
+        msg!("Device {} unbanned", device_account.device_id_str());
+        Ok(())
+    }
+    
+    /// Sends an active device a random nonce it must answer within AVAILABILITY_RESPONSE_WINDOW
+    pub fn issue_availability_challenge(ctx: Context<IssueAvailabilityChallenge>) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let challenge = &mut ctx.accounts.availability_challenge;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require!(device_account.is_active != 0, ComputeError::DeviceNotActive);
+        require!(!challenge.open, ComputeError::AvailabilityChallengeOpen);
+        require!(
+            now >= device_account.last_availability_challenge + AVAILABILITY_CHALLENGE_INTERVAL,
+            ComputeError::AvailabilityChallengeTooSoon
+        );
+        
+        let slot_hashes = ctx.accounts.slot_hashes.try_borrow_data()?;
+        require!(slot_hashes.len() >= 48, ComputeError::InvalidSlotHashes);
+        let nonce = hashv(&[&slot_hashes[16..48], device_key.as_ref()]).to_bytes();
+        drop(slot_hashes);
+        
+        challenge.device = device_key;
+        challenge.keeper = ctx.accounts.keeper.key();
+        challenge.nonce = nonce;
+        challenge.issued_at = now;
+        challenge.deadline = now + AVAILABILITY_RESPONSE_WINDOW;
+        challenge.open = true;
+        device_account.last_availability_challenge = now;
+        
+        emit!(AvailabilityChallengeIssued {
+            device: device_key,
+            nonce,
+            deadline: challenge.deadline,
+        });
+        
+        msg!("Availability challenge issued to device {}", device_account.device_id_str());
+        Ok(())
+    }
+    
+    /// Answers an availability challenge with hash(nonce || device), signed by the device owner
+    pub fn respond_availability_challenge(
+        ctx: Context<RespondAvailabilityChallenge>,
+        response: [u8; 32],
+    ) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let challenge = &mut ctx.accounts.availability_challenge;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require!(challenge.open, ComputeError::NoOpenAvailabilityChallenge);
+        require!(now <= challenge.deadline, ComputeError::AvailabilityDeadlinePassed);
+        require!(
+            response == hashv(&[&challenge.nonce, device_key.as_ref()]).to_bytes(),
+            ComputeError::InvalidAvailabilityResponse
+        );
+        
+        challenge.open = false;
+        device_account.availability_failures = 0;
+        device_account.touch(now);
+        
+        emit!(AvailabilityChallengeResolved {
+            device: device_key,
+            passed: true,
+            failures: 0,
+            deactivated: false,
+        });
+        
+        msg!("Device {} answered its availability challenge", device_account.device_id_str());
+        Ok(())
+    }
+    
+    /// Records a missed challenge once its deadline passes; repeated misses take the device offline
+    pub fn resolve_availability_challenge(ctx: Context<ResolveAvailabilityChallenge>) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let challenge = &mut ctx.accounts.availability_challenge;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require!(challenge.open, ComputeError::NoOpenAvailabilityChallenge);
+        require!(now > challenge.deadline, ComputeError::AvailabilityDeadlineNotReached);
+        
+        challenge.open = false;
+        device_account.uptime_seconds = device_account.uptime_seconds.saturating_sub(AVAILABILITY_UPTIME_PENALTY);
+        device_account.availability_failures = device_account.availability_failures.saturating_add(1);
+        
+        let deactivated = device_account.availability_failures >= MAX_AVAILABILITY_FAILURES && device_account.is_active != 0;
+        if deactivated {
+            // Same bookkeeping as going offline through update_device_status
+            device_account.sync_yield(&mut ctx.accounts.epoch_state)?;
+            ctx.accounts.network_state.device_status_changed(true, false);
+            device_account.touch(now);
+            device_account.is_active = 0;
+            device_account.sync_yield(&mut ctx.accounts.epoch_state)?;
+        }
+        
+        emit!(AvailabilityChallengeResolved {
+            device: device_key,
+            passed: false,
+            failures: device_account.availability_failures,
+            deactivated,
+        });
+        
+        msg!("Device {} missed its availability challenge ({} in a row)", 
+            device_account.device_id_str(), device_account.availability_failures);
+        Ok(())
+    }
+    
+    /// Registers the X25519 key confidential task keys are sealed to
+    pub fn set_encryption_key(
+        ctx: Context<SetEncryptionKey>,
+        encryption_pubkey: [u8; 32],
+    ) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        device_account.encryption_pubkey = encryption_pubkey;
+        
+        emit!(EncryptionKeySet {
+            device: device_key,
+            encryption_pubkey,
+        });
+        
+        msg!("Device {} encryption key set", device_account.device_id_str());
+        Ok(())
+    }
+    
+    /// Creates the stake receipt mint, issued by the network PDA against device stake
+    pub fn init_stake_receipt_mint(ctx: Context<InitStakeReceiptMint>) -> Result<()> {
+        msg!("Stake receipt mint {} initialized", ctx.accounts.receipt_mint.key());
+        Ok(())
+    }
+    
+    /// Stakes tokens for the device. Passing the receipt mint and a receipt account also mints
+    /// transferable stake receipts against the new stake at the current swap rate.
+    pub fn stake_tokens(
+        ctx: Context<StakeTokens>,
+        amount: u64,
+    ) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        // Transfer tokens from device owner to stake vault
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.owner_token_account.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(
+            cpi_ctx,
+            with_transfer_fee(&ctx.accounts.reward_mint, amount)?,
+            ctx.accounts.reward_mint.decimals,
+        )?;
+        
+        device_account.sync_yield(&mut ctx.accounts.epoch_state)?;
+        device_account.staked_amount += amount;
+        device_account.stake_timestamp = clock.unix_timestamp;
+        device_account.sync_yield(&mut ctx.accounts.epoch_state)?;
+        
+        match (&ctx.accounts.receipt_mint, &ctx.accounts.receipt_token_account) {
+            (Some(receipt_mint), Some(receipt_token_account)) => {
+                let network_state = &mut ctx.accounts.network_state;
+                let receipts = network_state.receipts_for_stake(amount)?;
+                
+                let seeds = &[
+                    b"network_state".as_ref(),
+                    &[ctx.bumps.network_state]
+                ];
+                let signer_seeds = &[&seeds[..]];
+                let cpi_accounts = MintTo {
+                    mint: receipt_mint.to_account_info(),
+                    to: receipt_token_account.to_account_info(),
+                    authority: network_state.to_account_info(),
+                };
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                token_interface::mint_to(cpi_ctx, receipts)?;
+                
+                device_account.receipted_stake += amount;
+                network_state.receipt_supply += receipts;
+                network_state.receipted_stake += amount;
+                
+                emit!(StakeReceiptsMinted {
+                    device: device_key,
+                    holder: receipt_token_account.key(),
+                    stake: amount,
+                    receipts,
+                    receipt_supply: network_state.receipt_supply,
+                    receipted_stake: network_state.receipted_stake,
+                });
+            }
+            (None, None) => {}
+            _ => return Err(ComputeError::ReceiptAccountsMissing.into()),
+        }
+        
+        // Update device tier based on staked amount
+        let tier = device_account.eligible_tier(&ctx.accounts.network_config);
+        device_account.tier = tier as u8;
+        
+        emit!(StakeChanged {
+            device: device_key,
+            amount,
+            increased: true,
+            staked_amount: device_account.staked_amount,
+            tier,
+        });
+        
+        msg!("Device {} staked {} tokens, new tier: {:?}", 
+            device_account.device_id_str(), amount, tier);
+        Ok(())
+    }
+    
+    pub fn request_unstake(
+        ctx: Context<RequestUnstake>,
+        amount: u64,
+    ) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        require!(!device_account.is_banned(), ComputeError::DeviceBanned);
+        require!(clock.unix_timestamp >= device_account.vote_locked_until, ComputeError::StakeLockedForVote);
+        require!(device_account.available_stake() >= amount, ComputeError::InsufficientStake);
+        // Stake backs the work in flight, so it stays bonded until every accepted task is settled
+        require!(device_account.active_assignments == 0, ComputeError::DeviceHasActiveTasks);
+        
+        // Queued stake stops counting towards the tier right away and unlocks after the cooldown
+        let unlock_at = clock.unix_timestamp + ctx.accounts.network_config.unstake_period;
+        let withdrawal = &mut ctx.accounts.withdrawal;
+        withdrawal.device = device_key;
+        withdrawal.owner = ctx.accounts.owner.key();
+        withdrawal.amount = amount;
+        withdrawal.unlock_at = unlock_at;
+        
+        // Stake without receipts leaves first; the rest has to be bought back by burning receipts
+        let receipted = amount.saturating_sub(device_account.staked_amount - device_account.receipted_stake);
+        if receipted > 0 {
+            let (Some(receipt_mint), Some(receipt_token_account), Some(token_program)) = (
+                &ctx.accounts.receipt_mint,
+                &ctx.accounts.receipt_token_account,
+                &ctx.accounts.token_program,
+            ) else {
+                return Err(ComputeError::ReceiptAccountsMissing.into());
+            };
+            let network_state = &mut ctx.accounts.network_state;
+            let receipts = network_state.receipts_to_burn(receipted)?;
+            
+            let cpi_accounts = Burn {
+                mint: receipt_mint.to_account_info(),
+                from: receipt_token_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+            token_interface::burn(cpi_ctx, receipts)?;
+            
+            device_account.receipted_stake -= receipted;
+            network_state.receipt_supply -= receipts;
+            network_state.receipted_stake -= receipted;
+            
+            emit!(StakeReceiptsBurned {
+                device: device_key,
+                holder: receipt_token_account.key(),
+                stake: receipted,
+                receipts,
+                receipt_supply: network_state.receipt_supply,
+                receipted_stake: network_state.receipted_stake,
+            });
+        }
+        
+        device_account.sync_yield(&mut ctx.accounts.epoch_state)?;
+        device_account.staked_amount -= amount;
+        device_account.withdrawal_count += 1;
+        device_account.sync_yield(&mut ctx.accounts.epoch_state)?;
+        
+        let tier = device_account.eligible_tier(&ctx.accounts.network_config);
+        device_account.tier = tier as u8;
+        
+        emit!(UnstakeRequested {
+            device: device_key,
+            withdrawal: ctx.accounts.withdrawal.key(),
+            amount,
+            unlock_at,
+        });
+        
+        emit!(StakeChanged {
+            device: device_key,
+            amount,
+            increased: false,
+            staked_amount: device_account.staked_amount,
+            tier,
+        });
+        
+        msg!("Device {} queued {} tokens for unstaking, unlocks at {}", 
+            device_account.device_id_str(), amount, unlock_at);
+        Ok(())
+    }
+    
+    pub fn withdraw_unstaked(ctx: Context<WithdrawUnstaked>) -> Result<()> {
+        let withdrawal = &ctx.accounts.withdrawal;
+        let clock = Clock::get()?;
+        
+        require!(clock.unix_timestamp >= withdrawal.unlock_at, ComputeError::UnstakeCooldownActive);
+        // Queued stake stays frozen while its device is banned; a deregistered device's account is gone
+        let device_info = ctx.accounts.device_account.to_account_info();
+        let data = device_info.try_borrow_data()?;
+        if device_info.owner == &crate::ID && data.len() >= 8 && data[..8] == *DeviceAccount::DISCRIMINATOR {
+            require!(data.len() >= 8 + DeviceAccount::LEN, ComputeError::AccountNeedsMigration);
+            let device_account: DeviceAccount = bytemuck::pod_read_unaligned(&data[8..8 + DeviceAccount::LEN]);
+            require!(!device_account.is_banned(), ComputeError::DeviceBanned);
+        }
+        
+        // Transfer tokens from stake vault to device owner
+        let seeds = &[
+            b"network_state".as_ref(),
+            &[ctx.bumps.network_state]
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: ctx.accounts.network_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, withdrawal.amount, ctx.accounts.reward_mint.decimals)?;
+        
+        emit!(UnstakeWithdrawn {
+            device: withdrawal.device,
+            withdrawal: withdrawal.key(),
+            amount: withdrawal.amount,
+        });
+        
+        msg!("Withdrew {} unstaked tokens for device {}", withdrawal.amount, withdrawal.device);
+        Ok(())
+    }
+    
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        
+        require!(!device_account.is_banned(), ComputeError::DeviceBanned);
+        let amount = device_account.pending_rewards;
+        require!(amount > 0, ComputeError::NoPendingRewards);
+        
+        let seeds = &[
+            b"network_state".as_ref(),
+            &[ctx.bumps.network_state]
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.network_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.reward_mint.decimals)?;
+        
+        device_account.pending_rewards = 0;
+        
+        emit!(RewardsClaimed {
+            device: device_key,
+            destination: ctx.accounts.destination.key(),
+            amount,
+        });
+        
+        msg!("Device {} claimed {} in task rewards", device_account.device_id_str(), amount);
+        Ok(())
+    }
+    
+    pub fn initialize_vesting(ctx: Context<InitializeVesting>) -> Result<()> {
+        let vesting_account = &mut ctx.accounts.vesting_account;
+        vesting_account.device = ctx.accounts.device_account.key();
+        vesting_account.owner = ctx.accounts.owner.key();
+        vesting_account.total = 0;
+        vesting_account.released = 0;
+        vesting_account.start = Clock::get()?.unix_timestamp;
+        
+        msg!("Vesting account created for device {}", vesting_account.device);
+        Ok(())
+    }
+    
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        let vesting_account = &mut ctx.accounts.vesting_account;
+        let clock = Clock::get()?;
+        
+        let amount = vesting_account.claimable(&ctx.accounts.network_config, clock.unix_timestamp)?;
+        require!(amount > 0, ComputeError::NothingVested);
+        
+        let seeds = &[
+            b"network_state".as_ref(),
+            &[ctx.bumps.network_state]
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.network_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.reward_mint.decimals)?;
+        
+        vesting_account.released += amount;
+        
+        emit!(VestedClaimed {
+            device: vesting_account.device,
+            destination: ctx.accounts.destination.key(),
+            amount,
+            remaining: vesting_account.total - vesting_account.released,
+        });
+        
+        msg!("Released {} vested tokens for device {}", amount, vesting_account.device);
+        Ok(())
+    }
+    
+    /// Expires a batch of overdue tasks for automation threads. remaining_accounts holds one
+    /// [task_account, assigned device_account, submitter_token_account] group per task; the token account
+    /// is only read for tasks paid in the network mint. Tasks that are not expired are skipped, so a thread
+    /// can resubmit a stale list; no slash records are written.
+    pub fn expire_tasks<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExpireTasks<'info>>,
+    ) -> Result<()> {
+        let groups = ctx.remaining_accounts.len() / 3;
+        require!(
+            groups > 0 && groups <= MAX_CRANK_BATCH && ctx.remaining_accounts.len().is_multiple_of(3),
+            ComputeError::InvalidBatch
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let config = &ctx.accounts.network_config;
+        
+        let mut expired = 0;
+        for accounts in ctx.remaining_accounts.chunks(3) {
+            let task_loader = AccountLoader::<TaskAccount>::try_from(&accounts[0])?;
+            require!(is_current(&task_loader), ComputeError::AccountNeedsMigration);
+            let mut task_account = task_loader.load_mut()?;
+            let device_loader = AccountLoader::<DeviceAccount>::try_from(&accounts[1])?;
+            require!(is_current(&device_loader), ComputeError::AccountNeedsMigration);
+            if !task_account.is_expired(now, config) || task_account.assigned_device != device_loader.key() {
+                continue;
+            }
+            let mut device_account = device_loader.load_mut()?;
+            
+            let submitter_token_account = if task_account.pays_in_network_mint(&ctx.accounts.network_state.reward_mint) {
+                let token_account = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?;
+                require!(
+                    token_account.owner == task_account.submitter &&
+                    token_account.mint == task_account.reward_mint,
+                    ComputeError::InvalidTokenAccount
+                );
+                Some(accounts[2].clone())
+            } else {
+                None
+            };
+            
+            let transfers = ExpiryTransfers {
+                keeper: ctx.accounts.thread.key(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                reward_mint: ctx.accounts.reward_mint.to_account_info(),
+                decimals: ctx.accounts.reward_mint.decimals,
+                network_state: ctx.accounts.network_state.to_account_info(),
+                network_state_bump: ctx.bumps.network_state,
+                stake_vault: ctx.accounts.stake_vault.to_account_info(),
+                treasury: ctx.accounts.treasury.to_account_info(),
+                submitter_token_account,
+                keeper_token_account: ctx.accounts.keeper_token_account.to_account_info(),
+            };
+            let (_, slashed_to_treasury) = expire_assignment(
+                task_loader.key(),
+                &mut task_account,
+                device_loader.key(),
+                &mut device_account,
+                &mut ctx.accounts.network_state,
+                config,
+                now,
+                &transfers,
+            )?;
+            ctx.accounts.network_state.assignment_ended();
+            ctx.accounts.network_state.accrue_burn(slashed_to_treasury, config);
+            expired += 1;
+        }
+        
+        msg!("Expired {} of {} tasks", expired, groups);
+        Ok(())
+    }
+    
+    /// Lets the reputation of devices idle for REPUTATION_DECAY_INTERVAL drift back towards the
+    /// starting score, one step per interval. Permissionless; remaining_accounts lists the devices.
+    pub fn decay_reputation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DecayReputation>,
+    ) -> Result<()> {
+        require!(
+            !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() <= MAX_CRANK_BATCH,
+            ComputeError::InvalidBatch
+        );
+        let now = Clock::get()?.unix_timestamp;
+        
+        for account in ctx.remaining_accounts {
+            let device_loader = AccountLoader::<DeviceAccount>::try_from(account)?;
+            require!(is_current(&device_loader), ComputeError::AccountNeedsMigration);
+            let mut device_account = device_loader.load_mut()?;
+            let idle_since = device_account.last_active.max(device_account.last_reputation_decay);
+            if now < idle_since + REPUTATION_DECAY_INTERVAL || device_account.reputation_score <= BASE_REPUTATION {
+                continue;
+            }
+            
+            let excess = (device_account.reputation_score - BASE_REPUTATION) as u64;
+            let decay = (excess * REPUTATION_DECAY_BPS / 10000).max(1) as u16;
+            device_account.reputation_score -= decay;
+            device_account.last_reputation_decay = now;
+            
+            emit!(ReputationDecayed {
+                device: device_loader.key(),
+                decay,
+                reputation_score: device_account.reputation_score,
+            });
+        }
+        Ok(())
+    }
+    
+    /// Closes the current epoch once it has elapsed and records its EpochStats snapshot. Only needs
+    /// a payer for the snapshot besides PDA accounts, so it can be scheduled by an automation thread.
+    pub fn advance_epoch(ctx: Context<AdvanceEpoch>) -> Result<()> {
+        let epoch_state = &mut ctx.accounts.epoch_state;
+        let network_state = &ctx.accounts.network_state;
+        let network_config = &ctx.accounts.network_config;
+        let clock = Clock::get()?;
+        
+        require!(
+            clock.unix_timestamp >= epoch_state.epoch_started_at + network_config.epoch_duration,
+            ComputeError::EpochNotEnded
+        );
+        
+        // Only emit what the treasury can still cover after yield already owed to devices
+        let available = ctx.accounts.treasury.amount.saturating_sub(epoch_state.unclaimed_yield);
+        let emission = if epoch_state.total_weight > 0 {
+            network_config.epoch_emission.min(available)
+        } else {
+            0
+        };
+        
+        if emission > 0 {
+            let increment = (emission as u128)
+                .checked_mul(YIELD_PRECISION)
+                .ok_or(ComputeError::MathOverflow)?
+                / epoch_state.total_weight as u128;
+            epoch_state.reward_per_share = epoch_state.reward_per_share
+                .checked_add(increment)
+                .ok_or(ComputeError::MathOverflow)?;
+            epoch_state.unclaimed_yield += emission;
+        }
+        
+        // Unspent availability budget lapses; the new one comes out of what the yield left over
+        epoch_state.availability_budget = network_config.availability_emission.min(available - emission);
+        
+        let timed_completions = network_state.timed_completions - epoch_state.timed_completions_at_start;
+        let epoch_stats = &mut ctx.accounts.epoch_stats;
+        epoch_stats.epoch = epoch_state.epoch;
+        epoch_stats.started_at = epoch_state.epoch_started_at;
+        epoch_stats.ended_at = clock.unix_timestamp;
+        epoch_stats.tasks_completed = network_state.total_tasks_completed - epoch_state.tasks_completed_at_start;
+        epoch_stats.tokens_distributed = network_state.total_tokens_distributed - epoch_state.tokens_distributed_at_start;
+        epoch_stats.active_devices = network_state.active_devices;
+        epoch_stats.avg_completion_seconds = (network_state.total_completion_seconds - epoch_state.completion_seconds_at_start)
+            .checked_div(timed_completions)
+            .unwrap_or(0);
+        epoch_stats.emission = emission;
+        epoch_stats.total_weight = epoch_state.total_weight;
+        epoch_stats.network_utilization = network_state.network_utilization;
+        
+        epoch_state.tasks_completed_at_start = network_state.total_tasks_completed;
+        epoch_state.tokens_distributed_at_start = network_state.total_tokens_distributed;
+        epoch_state.completion_seconds_at_start = network_state.total_completion_seconds;
+        epoch_state.timed_completions_at_start = network_state.timed_completions;
+        epoch_state.epoch += 1;
+        epoch_state.epoch_started_at = clock.unix_timestamp;
+        
+        emit!(EpochAdvanced {
+            epoch: epoch_state.epoch,
+            emission,
+            total_weight: epoch_state.total_weight,
+            reward_per_share: epoch_state.reward_per_share,
+        });
+        
+        msg!("Epoch {} started, emitted {} to {} staked", 
+            epoch_state.epoch, emission, epoch_state.total_weight);
+        Ok(())
+    }
+    
+    pub fn claim_staking_rewards(ctx: Context<ClaimStakingRewards>) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let epoch_state = &mut ctx.accounts.epoch_state;
+        require!(!device_account.is_banned(), ComputeError::DeviceBanned);
+        
+        device_account.sync_yield(epoch_state)?;
+        let amount = device_account.pending_yield;
+        require!(amount > 0, ComputeError::NoStakingRewards);
+        
+        let seeds = &[
+            b"network_state".as_ref(),
+            &[ctx.bumps.network_state]
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.treasury.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: ctx.accounts.network_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.reward_mint.decimals)?;
+        
+        device_account.pending_yield = 0;
+        epoch_state.unclaimed_yield = epoch_state.unclaimed_yield.saturating_sub(amount);
+        
+        emit!(StakingRewardsClaimed {
+            device: device_key,
+            amount,
+            epoch: epoch_state.epoch,
+        });
+        
+        msg!("Device {} claimed {} staking rewards", device_account.device_id_str(), amount);
+        Ok(())
+    }
+    
+    /// Pays a device for time spent online since its last claim, at most once per epoch.
+    /// Up to one epoch of uptime counts, weighted by tier, out of the epoch's availability budget.
+    pub fn claim_availability_reward(ctx: Context<ClaimAvailabilityReward>) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let epoch_state = &mut ctx.accounts.epoch_state;
+        let network_config = &ctx.accounts.network_config;
+        
+        require!(!device_account.is_banned(), ComputeError::DeviceBanned);
+        require!(
+            epoch_state.epoch > device_account.availability_claimed_epoch,
+            ComputeError::AvailabilityAlreadyClaimed
+        );
+        device_account.touch(Clock::get()?.unix_timestamp);
+        
+        // Missed availability challenges can push uptime below what was already claimed
+        let uptime = device_account.uptime_seconds
+            .saturating_sub(device_account.uptime_claimed_seconds)
+            .min(network_config.epoch_duration as u64);
+        let amount = (network_config.availability_reward as u128)
+            .checked_mul(network_config.tier_multiplier_bps(device_account.tier) as u128)
+            .ok_or(ComputeError::MathOverflow)?
+            .checked_mul(uptime as u128)
+            .ok_or(ComputeError::MathOverflow)?
+            / (10000 * network_config.epoch_duration as u128);
+        let amount = (amount as u64).min(epoch_state.availability_budget);
+        require!(amount > 0, ComputeError::NoAvailabilityReward);
+        
+        let seeds = &[
+            b"network_state".as_ref(),
+            &[ctx.bumps.network_state]
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.treasury.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: ctx.accounts.network_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.reward_mint.decimals)?;
+        
+        device_account.uptime_claimed_seconds = device_account.uptime_seconds;
+        device_account.availability_claimed_epoch = epoch_state.epoch;
+        epoch_state.availability_budget -= amount;
+        
+        emit!(AvailabilityRewardClaimed {
+            device: device_key,
+            amount,
+            uptime_seconds: uptime,
+            epoch: epoch_state.epoch,
+        });
+        
+        msg!("Device {} claimed {} for {}s of uptime", device_account.device_id_str(), amount, uptime);
+        Ok(())
+    }
+    
+    /// Creates the current epoch's leaderboard, which complete_task then keeps ranked. Permissionless.
+    pub fn open_leaderboard(ctx: Context<OpenLeaderboard>) -> Result<()> {
+        let leaderboard = &mut ctx.accounts.leaderboard;
+        leaderboard.epoch = ctx.accounts.epoch_state.epoch;
+        leaderboard.by_tasks = Vec::new();
+        leaderboard.by_tokens = Vec::new();
+        leaderboard.prizes_paid = false;
+        
+        msg!("Leaderboard for epoch {} opened", leaderboard.epoch);
+        Ok(())
+    }
+    
+    /// Pays the leaderboard_prize of each board of a finished epoch out of the treasury, split
+    /// linearly by rank. Permissionless; remaining_accounts holds a reward token account of each
+    /// ranked device's owner, in rank order, first for the tasks board and then for the tokens board.
+    pub fn distribute_leaderboard_prizes<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeLeaderboardPrizes<'info>>,
+        epoch: u64,
+    ) -> Result<()> {
+        let leaderboard = &ctx.accounts.leaderboard;
+        let epoch_state = &ctx.accounts.epoch_state;
+        require!(epoch < epoch_state.epoch, ComputeError::EpochNotEnded);
+        require!(!leaderboard.prizes_paid, ComputeError::PrizesAlreadyPaid);
+        
+        let winners: Vec<LeaderboardEntry> = leaderboard.by_tasks.iter()
+            .chain(leaderboard.by_tokens.iter())
+            .copied()
+            .collect();
+        require!(ctx.remaining_accounts.len() == winners.len(), ComputeError::InvalidTokenAccount);
+        
+        // Yield and availability rewards already promised to devices come first
+        let mut available = ctx.accounts.treasury.amount
+            .saturating_sub(epoch_state.unclaimed_yield)
+            .saturating_sub(epoch_state.availability_budget);
+        let seeds = &[
+            b"network_state".as_ref(),
+            &[ctx.bumps.network_state]
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let mut total_paid = 0;
+        for (board, offset) in [(&leaderboard.by_tasks, 0), (&leaderboard.by_tokens, leaderboard.by_tasks.len())] {
+            let pool = ctx.accounts.network_config.leaderboard_prize.min(available);
+            for (rank, entry) in board.iter().enumerate() {
+                let account_info = &ctx.remaining_accounts[offset + rank];
+                let token_account = InterfaceAccount::<TokenAccount>::try_from(account_info)?;
+                require!(
+                    token_account.owner == entry.owner && token_account.mint == ctx.accounts.reward_mint.key(),
+                    ComputeError::InvalidTokenAccount
+                );
+                let prize = Leaderboard::prize(pool, rank, board.len());
+                if prize == 0 {
+                    continue;
+                }
+                
+                let cpi_accounts = TransferChecked {
+                    from: ctx.accounts.treasury.to_account_info(),
+                    mint: ctx.accounts.reward_mint.to_account_info(),
+                    to: account_info.clone(),
+                    authority: ctx.accounts.network_state.to_account_info(),
+                };
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                token_interface::transfer_checked(cpi_ctx, prize, ctx.accounts.reward_mint.decimals)?;
+                available -= prize;
+                total_paid += prize;
+            }
+        }
+        ctx.accounts.leaderboard.prizes_paid = true;
+        
+        emit!(LeaderboardPrizesPaid {
+            epoch,
+            winners: winners.len() as u8,
+            amount: total_paid,
+        });
+        
+        msg!("Paid {} in leaderboard prizes for epoch {}", total_paid, epoch);
+        Ok(())
+    }
+    
+    /// Creates the Bubblegum tree achievement badges are minted into, with the badge_tree PDA as
+    /// its creator. `merkle_tree` must already be allocated for the compression program.
+    pub fn init_badge_tree(
+        ctx: Context<InitBadgeTree>,
+        max_depth: u32,
+        max_buffer_size: u32,
+        uri_prefix: String,
+    ) -> Result<()> {
+        require!(uri_prefix.len() <= MAX_BADGE_URI_LEN, ComputeError::FieldTooLong);
+        
+        let badge_tree = &mut ctx.accounts.badge_tree;
+        badge_tree.merkle_tree = ctx.accounts.merkle_tree.key();
+        badge_tree.uri_prefix = uri_prefix;
+        badge_tree.badges_minted = 0;
+        
+        let mut data = CREATE_TREE_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&max_depth.to_le_bytes());
+        data.extend_from_slice(&max_buffer_size.to_le_bytes());
+        // public: None, so only the badge_tree PDA can mint
+        data.push(0);
+        let ix = Instruction {
+            program_id: BUBBLEGUM_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(ctx.accounts.tree_authority.key(), false),
+                AccountMeta::new(ctx.accounts.merkle_tree.key(), false),
+                AccountMeta::new(ctx.accounts.authority.key(), true),
+                AccountMeta::new_readonly(badge_tree.key(), true),
+                AccountMeta::new_readonly(SPL_NOOP_ID, false),
+                AccountMeta::new_readonly(SPL_ACCOUNT_COMPRESSION_ID, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data,
+        };
+        invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.tree_authority.to_account_info(),
+                ctx.accounts.merkle_tree.to_account_info(),
+                ctx.accounts.authority.to_account_info(),
+                badge_tree.to_account_info(),
+                ctx.accounts.log_wrapper.to_account_info(),
+                ctx.accounts.compression_program.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.bubblegum_program.to_account_info(),
+            ],
+            &[&[b"badge_tree".as_ref(), &[ctx.bumps.badge_tree]]],
+        )?;
+        
+        msg!("Badge tree {} initialized", badge_tree.merkle_tree);
+        Ok(())
+    }
+    
+    /// Permissionless: mints the badge for an achievement the device has earned as a compressed NFT
+    /// to its owner. The Achievement record makes each badge claimable once per device.
+    pub fn claim_achievement(
+        ctx: Context<ClaimAchievement>,
+        kind: AchievementKind,
+    ) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let device_account = ctx.accounts.device_account.load()?;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require!(kind.is_earned(&device_account), ComputeError::AchievementNotEarned);
+        
+        let achievement = &mut ctx.accounts.achievement;
+        achievement.device = device_key;
+        achievement.kind = kind;
+        achievement.recipient = device_account.owner;
+        achievement.awarded_at = now;
+        
+        let badge_tree = &mut ctx.accounts.badge_tree;
+        let metadata = BubblegumMetadataArgs {
+            name: kind.badge_name().to_string(),
+            symbol: "SMBADGE".to_string(),
+            uri: format!("{}{}.json", badge_tree.uri_prefix, kind as u8),
+            seller_fee_basis_points: 0,
+            primary_sale_happened: false,
+            is_mutable: false,
+            edition_nonce: None,
+            token_standard: Some(0),
+            collection: None,
+            uses: None,
+            token_program_version: 0,
+            creators: Vec::new(),
+        };
+        let mut data = MINT_V1_DISCRIMINATOR.to_vec();
+        metadata.serialize(&mut data)?;
+        let ix = Instruction {
+            program_id: BUBBLEGUM_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(ctx.accounts.tree_authority.key(), false),
+                AccountMeta::new_readonly(device_account.owner, false),
+                AccountMeta::new_readonly(device_account.owner, false),
+                AccountMeta::new(ctx.accounts.merkle_tree.key(), false),
+                AccountMeta::new(ctx.accounts.payer.key(), true),
+                AccountMeta::new_readonly(badge_tree.key(), true),
+                AccountMeta::new_readonly(SPL_NOOP_ID, false),
+                AccountMeta::new_readonly(SPL_ACCOUNT_COMPRESSION_ID, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data,
+        };
+        invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.tree_authority.to_account_info(),
+                ctx.accounts.owner.to_account_info(),
+                ctx.accounts.merkle_tree.to_account_info(),
+                ctx.accounts.payer.to_account_info(),
+                badge_tree.to_account_info(),
+                ctx.accounts.log_wrapper.to_account_info(),
+                ctx.accounts.compression_program.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.bubblegum_program.to_account_info(),
+            ],
+            &[&[b"badge_tree".as_ref(), &[ctx.bumps.badge_tree]]],
+        )?;
+        badge_tree.badges_minted += 1;
+        
+        emit!(AchievementAwarded {
+            device: device_key,
+            owner: device_account.owner,
+            kind,
+            merkle_tree: badge_tree.merkle_tree,
+        });
+        
+        msg!("Device {} earned {}", device_account.device_id_str(), kind.badge_name());
+        Ok(())
+    }
+    
+    pub fn initialize_pending_index(
+        ctx: Context<InitializePendingIndex>,
+        task_type: TaskType,
+    ) -> Result<()> {
+        let pending_index = &mut ctx.accounts.pending_index;
+        pending_index.task_type = task_type as u8;
+        pending_index.tasks = Vec::new();
+        
+        msg!("Pending index initialized for task type {}", task_type as u8);
+        Ok(())
+    }
+    
+    pub fn initialize_priority_index(ctx: Context<InitializePriorityIndex>) -> Result<()> {
+        ctx.accounts.priority_index.entries = Vec::new();
+        
+        msg!("Priority index initialized");
+        Ok(())
+    }
+    
+    pub fn initialize_verifier_pool(ctx: Context<InitializeVerifierPool>) -> Result<()> {
+        ctx.accounts.verifier_pool.entries = Vec::new();
+        emit!(VerifierPoolInitialized {
+            authority: ctx.accounts.authority.key(),
+        });
+        
+        msg!("Verifier pool initialized");
+        Ok(())
+    }
+    
+    pub fn join_verifier_pool(ctx: Context<JoinVerifierPool>) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let device_account = ctx.accounts.device_account.load()?;
+        let verifier_pool = &mut ctx.accounts.verifier_pool;
+        
+        require!(device_account.is_active != 0, ComputeError::DeviceNotActive);
+        require!(
+            device_account.available_stake() >= MIN_VERIFIER_STAKE,
+            ComputeError::InsufficientVerifierStake
+        );
+        require!(
+            !verifier_pool.entries.iter().any(|entry| entry.device == device_key),
+            ComputeError::AlreadyInVerifierPool
+        );
+        require!(verifier_pool.entries.len() < MAX_VERIFIER_POOL_SIZE, ComputeError::VerifierPoolFull);
+        
+        verifier_pool.entries.push(VerifierEntry {
+            device: device_key,
+            owner: device_account.owner,
+        });
+        
+        emit!(VerifierPoolChanged {
+            device: device_key,
+            joined: true,
+        });
+        
+        msg!("Device {} joined the verifier pool", device_account.device_id_str());
+        Ok(())
+    }
+    
+    pub fn leave_verifier_pool(ctx: Context<LeaveVerifierPool>) -> Result<()> {
+        let device_key = ctx.accounts.device_account.key();
+        let verifier_pool = &mut ctx.accounts.verifier_pool;
+        
+        let index = verifier_pool.entries.iter()
+            .position(|entry| entry.device == device_key)
+            .ok_or(ComputeError::NotInVerifierPool)?;
+        verifier_pool.entries.swap_remove(index);
+        
+        emit!(VerifierPoolChanged {
+            device: device_key,
+            joined: false,
+        });
+        
+        msg!("Device {} left the verifier pool", ctx.accounts.device_account.load()?.device_id_str());
+        Ok(())
+    }
+    
+    pub fn select_committee(
+        ctx: Context<SelectCommittee>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let task_account = ctx.accounts.task_account.load()?;
+        let device_key = ctx.accounts.device_account.key();
+        let device_account = ctx.accounts.device_account.load()?;
+        let verifier_pool = &ctx.accounts.verifier_pool;
+        let clock = Clock::get()?;
+        
+        require!(task_account.status == TaskStatus::Completed as u8, ComputeError::TaskNotCompleted);
+        require!(task_account.is_verified == 0, ComputeError::TaskAlreadyVerified);
+        require!(task_account.needs_committee(), ComputeError::TaskNotChallenged);
+        
+        // The worker and anyone sharing its owner are never eligible to judge the result
+        let candidates: Vec<&VerifierEntry> = verifier_pool.entries.iter()
+            .filter(|entry| entry.device != device_key && entry.owner != device_account.owner)
+            .collect();
+        require!(candidates.len() >= task_account.quorum() as usize, ComputeError::NotEnoughVerifiers);
+        
+        // Seed selection with the most recent slot hash, which the caller cannot choose
+        let slot_hashes = ctx.accounts.slot_hashes.try_borrow_data()?;
+        require!(slot_hashes.len() >= 48, ComputeError::InvalidSlotHashes);
+        let seed = hashv(&[&slot_hashes[16..48], task_key.as_ref()]).to_bytes();
+        drop(slot_hashes);
+        
+        // Partial Fisher-Yates shuffle over the candidate list
+        let mut candidates = candidates;
+        let committee_size = task_account.committee_size().min(candidates.len());
+        for i in 0..committee_size {
+            let draw = hashv(&[&seed, &(i as u32).to_le_bytes()]).to_bytes();
+            let roll = u64::from_le_bytes(draw[..8].try_into().unwrap());
+            let j = i + (roll % (candidates.len() - i) as u64) as usize;
+            candidates.swap(i, j);
+        }
+        
+        let task_committee = &mut ctx.accounts.task_committee;
+        task_committee.task = task_key;
+        task_committee.members = candidates[..committee_size].iter().map(|entry| entry.device).collect();
+        task_committee.seed = seed;
+        task_committee.selected_at = clock.unix_timestamp;
+        
+        emit!(CommitteeSelected {
+            task: task_key,
+            round: task_account.verification_round,
+            members: task_committee.members.clone(),
+        });
+        
+        msg!("Task {} committee selected with {} members", task_id, committee_size);
+        Ok(())
+    }
+    
+    pub fn challenge_result(
+        ctx: Context<ChallengeResult>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        require!(task_account.status == TaskStatus::Completed as u8, ComputeError::TaskNotCompleted);
+        require!(task_account.verification_mode == VerificationMode::Optimistic as u8, ComputeError::NotOptimistic);
+        require!(task_account.challenged == 0, ComputeError::AlreadyChallenged);
+        require!(clock.unix_timestamp <= task_account.challenge_deadline, ComputeError::ChallengeWindowClosed);
+        
+        // Post the challenger's bond; it is returned if the committee rejects the result
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.challenger_token_account.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.challenger.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(
+            cpi_ctx,
+            with_transfer_fee(&ctx.accounts.reward_mint, CHALLENGE_BOND)?,
+            ctx.accounts.reward_mint.decimals,
+        )?;
+        
+        let challenge = &mut ctx.accounts.challenge;
+        challenge.task = task_key;
+        challenge.challenger = ctx.accounts.challenger.key();
+        challenge.challenger_token_account = ctx.accounts.challenger_token_account.key();
+        challenge.bond = CHALLENGE_BOND;
+        challenge.created_at = clock.unix_timestamp;
+        challenge.resolved = false;
+        
+        // A challenged result falls back to full committee verification
+        task_account.challenged = 1;
+        task_account.open_verification_round(clock.unix_timestamp);
+        
+        emit!(ResultChallenged {
+            task: task_key,
+            challenger: challenge.challenger,
+            bond: challenge.bond,
+        });
+        
+        msg!("Task {} result challenged by {}", task_id, challenge.challenger);
+        Ok(())
+    }
+    
+    pub fn accept_optimistic_result(
+        ctx: Context<AcceptOptimisticResult>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        require!(task_account.status == TaskStatus::Completed as u8, ComputeError::TaskNotCompleted);
+        require!(task_account.verification_mode == VerificationMode::Optimistic as u8, ComputeError::NotOptimistic);
+        require!(task_account.challenged == 0, ComputeError::AlreadyChallenged);
+        require!(task_account.is_verified == 0, ComputeError::TaskAlreadyVerified);
+        require!(task_account.assigned_device == device_key, ComputeError::DeviceNotAssigned);
+        require!(clock.unix_timestamp > task_account.challenge_deadline, ComputeError::ChallengeWindowOpen);
+        require!(
+            task_account.compute_requirements.storage_proof_required == 0 || task_account.storage_proved_at != 0,
+            ComputeError::StorageNotProven
+        );
+        
+        task_account.is_verified = 1;
+        device_account.reputation_score = device_account.reputation_score.saturating_add(task_account.scaled_reputation(2, &ctx.accounts.network_config));
+        
+        let collateral = task_account.collateral;
+        device_account.release_collateral(collateral);
+        task_account.collateral = 0;
+        
+        emit!(CollateralReleased {
+            task: task_key,
+            device: device_key,
+            amount: collateral,
+            forfeited: false,
+        });
+        
+        emit!(TaskVerified {
+            task: task_key,
+            device: device_key,
+            is_verified: true,
+            valid_verifications: 0,
+            verifications: 0,
+        });
+        
+        msg!("Task {} result accepted after unchallenged window", task_id);
+        Ok(())
+    }
+    
+    pub fn resolve_challenge(
+        ctx: Context<ResolveChallenge>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let task_account = ctx.accounts.task_account.load()?;
+        let challenge = &mut ctx.accounts.challenge;
+        
+        require!(!challenge.resolved, ComputeError::ChallengeAlreadyResolved);
+        let outcome = task_account.verification_outcome(Clock::get()?.unix_timestamp)
+            .ok_or(ComputeError::VerificationNotFinalized)?;
+        challenge.resolved = true;
+        
+        // A submitter disputing its own task must have the loss recorded against its profile
+        require!(
+            ctx.accounts.challenger_profile.is_some() || challenge.challenger != task_account.submitter,
+            ComputeError::SubmitterProfileRequired
+        );
+        if outcome {
+            if let Some(profile) = ctx.accounts.challenger_profile.as_mut() {
+                profile.disputes_lost += 1;
+            }
+        }
+        
+        // The challenger wins when the committee rejects the result; the device's side is slashed via slash_device
+        let destination = if outcome {
+            ctx.accounts.treasury.to_account_info()
+        } else {
+            ctx.accounts.challenger_token_account.to_account_info()
+        };
+        
+        let seeds = &[
+            b"network_state".as_ref(),
+            &[ctx.bumps.network_state]
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: destination,
+            authority: ctx.accounts.network_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, challenge.bond, ctx.accounts.reward_mint.decimals)?;
+        
+        emit!(ChallengeResolved {
+            task: task_key,
+            challenger: challenge.challenger,
+            upheld: outcome,
+        });
+        
+        msg!("Challenge on task {} resolved: result upheld={}", task_id, outcome);
+        Ok(())
+    }
+    
+    pub fn commit_verification(
+        ctx: Context<CommitVerification>,
+        task_id: String,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let task_account = ctx.accounts.task_account.load()?;
+        let verifier_key = ctx.accounts.verifier_account.key();
+        let mut verifier_account = ctx.accounts.verifier_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        require!(task_account.status == TaskStatus::Completed as u8, ComputeError::TaskNotCompleted);
+        require!(task_account.is_verified == 0, ComputeError::TaskAlreadyVerified);
+        require!(clock.unix_timestamp <= task_account.commit_deadline, ComputeError::CommitPhaseClosed);
+        require!(verifier_account.reputation_score >= 100, ComputeError::InsufficientReputation);
+        require!(
+            verifier_account.available_stake() >= MIN_VERIFIER_STAKE,
+            ComputeError::InsufficientVerifierStake
+        );
+        
+        // Lock the verifier's bond until the vote is settled against the final outcome
+        verifier_account.verifier_locked_stake += MIN_VERIFIER_STAKE;
+        
+        let verification_record = &mut ctx.accounts.verification_record;
+        verification_record.task = task_key;
+        verification_record.verifier = verifier_key;
+        verification_record.verifier_owner = verifier_account.owner;
+        verification_record.commitment = commitment;
+        verification_record.revealed = false;
+        verification_record.is_valid = false;
+        verification_record.locked_stake = MIN_VERIFIER_STAKE;
+        verification_record.settled = false;
+        verification_record.round = task_account.verification_round;
+        verification_record.reward_claimed = false;
+        verification_record.timestamp = clock.unix_timestamp;
+        
+        emit!(VerificationCommitted {
+            task: task_key,
+            verifier: verifier_key,
+        });
+        
+        msg!("Task {} verification committed by device {}", task_id, verifier_account.device_id_str());
+        Ok(())
+    }
+    
+    pub fn reveal_verification(
+        ctx: Context<RevealVerification>,
+        task_id: String,
+        is_valid: bool,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let verifier_key = ctx.accounts.verifier_account.key();
+        let mut verifier_account = ctx.accounts.verifier_account.load_mut()?;
+        let verification_record = &mut ctx.accounts.verification_record;
+        let clock = Clock::get()?;
+        
+        require!(task_account.status == TaskStatus::Completed as u8, ComputeError::TaskNotCompleted);
+        require!(task_account.is_verified == 0, ComputeError::TaskAlreadyVerified);
+        require!(clock.unix_timestamp > task_account.commit_deadline, ComputeError::RevealPhaseNotOpen);
+        require!(clock.unix_timestamp <= task_account.reveal_deadline, ComputeError::RevealPhaseClosed);
+        require!(!verification_record.revealed, ComputeError::VoteAlreadyRevealed);
+        require!(!task_account.re_executed(), ComputeError::ReExecutionRequired);
+        require!(
+            VerificationRecord::commitment_for(is_valid, &salt, &verifier_key) == verification_record.commitment,
+            ComputeError::CommitmentMismatch
+        );
+        
+        verification_record.revealed = true;
+        verification_record.is_valid = is_valid;
+        
+        // Only revealed votes count toward the 2/3 threshold
+        task_account.record_vote(is_valid);
+        
+        // Reward verifier
+        verifier_account.total_verifications += 1;
+        verifier_account.reputation_score = verifier_account.reputation_score.saturating_add(1);
+        
+        emit!(VerificationRevealed {
+            task: task_key,
+            verifier: verifier_key,
+            is_valid,
+        });
+        
+        msg!("Task {} verification by device {}: valid={}", 
+            task_id, verifier_account.device_id_str(), is_valid);
+        Ok(())
+    }
+    
+    /// Hands a committee member of a ReExecution task its shadow task: the original inputs to re-run
+    pub fn claim_shadow_task(
+        ctx: Context<ClaimShadowTask>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let task_account = ctx.accounts.task_account.load()?;
+        let verifier_key = ctx.accounts.verifier_account.key();
+        let clock = Clock::get()?;
+        
+        require!(task_account.status == TaskStatus::Completed as u8, ComputeError::TaskNotCompleted);
+        require!(task_account.re_executed(), ComputeError::NotReExecutionTask);
+        require!(task_account.is_verified == 0, ComputeError::TaskAlreadyVerified);
+        require!(clock.unix_timestamp <= task_account.commit_deadline, ComputeError::CommitPhaseClosed);
+        
+        let shadow_task = &mut ctx.accounts.shadow_task;
+        shadow_task.task = task_key;
+        shadow_task.verifier = verifier_key;
+        shadow_task.round = task_account.verification_round;
+        shadow_task.input_hash = task_account.input_hash;
+        shadow_task.task_data = task_account.task_data;
+        shadow_task.result_hash = [0; 64];
+        shadow_task.revealed = false;
+        shadow_task.created_at = clock.unix_timestamp;
+        
+        emit!(ShadowTaskAssigned {
+            task: task_key,
+            shadow_task: shadow_task.key(),
+            verifier: verifier_key,
+            round: shadow_task.round,
+        });
+        
+        msg!("Task {} shadow task assigned to {}", task_id, verifier_key);
+        Ok(())
+    }
+    
+    /// Reveals a re-execution result committed as hash(result_hash || salt || verifier).
+    /// The vote is valid exactly when the verifier's result matches the device's.
+    pub fn reveal_reexecution(
+        ctx: Context<RevealReExecution>,
+        task_id: String,
+        result_hash: String,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let verifier_key = ctx.accounts.verifier_account.key();
+        let mut verifier_account = ctx.accounts.verifier_account.load_mut()?;
+        let verification_record = &mut ctx.accounts.verification_record;
+        let shadow_task = &mut ctx.accounts.shadow_task;
+        let clock = Clock::get()?;
+        
+        require!(task_account.status == TaskStatus::Completed as u8, ComputeError::TaskNotCompleted);
+        require!(task_account.re_executed(), ComputeError::NotReExecutionTask);
+        require!(task_account.is_verified == 0, ComputeError::TaskAlreadyVerified);
+        require!(clock.unix_timestamp > task_account.commit_deadline, ComputeError::RevealPhaseNotOpen);
+        require!(clock.unix_timestamp <= task_account.reveal_deadline, ComputeError::RevealPhaseClosed);
+        require!(!verification_record.revealed, ComputeError::VoteAlreadyRevealed);
+        require!(
+            VerificationRecord::result_commitment_for(&result_hash, &salt, &verifier_key) == verification_record.commitment,
+            ComputeError::CommitmentMismatch
+        );
+        
+        let result_hash: [u8; 64] = to_fixed_bytes(&result_hash)?;
+        let is_valid = result_hash == task_account.result_hash;
+        shadow_task.result_hash = result_hash;
+        shadow_task.revealed = true;
+        verification_record.revealed = true;
+        verification_record.is_valid = is_valid;
+        task_account.record_vote(is_valid);
+        
+        verifier_account.total_verifications += 1;
+        verifier_account.reputation_score = verifier_account.reputation_score.saturating_add(1);
+        
+        emit!(VerificationRevealed {
+            task: task_key,
+            verifier: verifier_key,
+            is_valid,
+        });
+        
+        msg!("Task {} re-executed by device {}: match={}", 
+            task_id, verifier_account.device_id_str(), is_valid);
+        Ok(())
+    }
+    
+    /// Commits hidden votes on several tasks at once. remaining_accounts holds one
+    /// [task_account, assigned device_account, task_committee, verification_record] group per commitment,
+    /// where the verification record is the uninitialized PDA that commit_verification would create.
+    pub fn commit_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CommitBatch<'info>>,
+        commitments: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let verifier_key = ctx.accounts.verifier_account.key();
+        let mut verifier_account = ctx.accounts.verifier_account.load_mut()?;
+        let verifier_owner = verifier_account.owner;
+        let clock = Clock::get()?;
+        
+        require!(
+            !commitments.is_empty() && commitments.len() <= MAX_VERIFY_BATCH,
+            ComputeError::InvalidBatch
+        );
+        require!(ctx.remaining_accounts.len() == commitments.len() * 4, ComputeError::InvalidBatch);
+        require!(verifier_account.reputation_score >= 100, ComputeError::InsufficientReputation);
+        
+        let space = 8 + VerificationRecord::LEN;
+        let lamports = Rent::get()?.minimum_balance(space);
+        for (accounts, commitment) in ctx.remaining_accounts.chunks(4).zip(commitments) {
+            let task_loader = AccountLoader::<TaskAccount>::try_from(&accounts[0])?;
+            require!(is_current(&task_loader), ComputeError::AccountNeedsMigration);
+            let task_key = task_loader.key();
+            let task_account = task_loader.load()?;
+            require!(task_account.status == TaskStatus::Completed as u8, ComputeError::TaskNotCompleted);
+            require!(task_account.is_verified == 0, ComputeError::TaskAlreadyVerified);
+            require!(clock.unix_timestamp <= task_account.commit_deadline, ComputeError::CommitPhaseClosed);
+            
+            let device_key = accounts[1].key();
+            require!(task_account.assigned_device == device_key, ComputeError::DeviceNotAssigned);
+            require!(device_key != verifier_key, ComputeError::SelfVerification);
+            let device_loader = AccountLoader::<DeviceAccount>::try_from(&accounts[1])?;
+            require!(is_current(&device_loader), ComputeError::AccountNeedsMigration);
+            require!(device_loader.load()?.owner != verifier_owner, ComputeError::SelfVerification);
+            
+            let (committee_key, _) = Pubkey::find_program_address(
+                &[b"committee", task_key.as_ref(), &[task_account.verification_round]],
+                ctx.program_id,
+            );
+            require!(accounts[2].key() == committee_key, ComputeError::InvalidBatch);
+            let task_committee = Account::<TaskCommittee>::try_from(&accounts[2])?;
+            require!(task_committee.members.contains(&verifier_key), ComputeError::NotCommitteeMember);
+            
+            require!(
+                verifier_account.available_stake() >= MIN_VERIFIER_STAKE,
+                ComputeError::InsufficientVerifierStake
+            );
+            verifier_account.verifier_locked_stake += MIN_VERIFIER_STAKE;
+            
+            // Create the record at the same PDA commit_verification uses, so reveal and settlement work unchanged
+            let (record_key, record_bump) = Pubkey::find_program_address(
+                &[b"verification", task_key.as_ref(), verifier_owner.as_ref()],
+                ctx.program_id,
+            );
+            require!(accounts[3].key() == record_key, ComputeError::InvalidBatch);
+            let record_seeds = &[
+                b"verification".as_ref(),
+                task_key.as_ref(),
+                verifier_owner.as_ref(),
+                &[record_bump]
+            ];
+            let signer_seeds = &[&record_seeds[..]];
+            let cpi_accounts = CreateAccount {
+                from: ctx.accounts.verifier.to_account_info(),
+                to: accounts[3].clone(),
+            };
+            let cpi_program = ctx.accounts.system_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            system_program::create_account(cpi_ctx, lamports, space as u64, ctx.program_id)?;
+            
+            let verification_record = VerificationRecord {
+                task: task_key,
+                verifier: verifier_key,
+                verifier_owner,
+                commitment,
+                revealed: false,
+                is_valid: false,
+                locked_stake: MIN_VERIFIER_STAKE,
+                settled: false,
+                round: task_account.verification_round,
+                reward_claimed: false,
+                timestamp: clock.unix_timestamp,
+            };
+            verification_record.try_serialize(&mut &mut accounts[3].try_borrow_mut_data()?[..])?;
+            
+            emit!(VerificationCommitted {
+                task: task_key,
+                verifier: verifier_key,
+            });
+        }
+        
+        msg!("Device {} committed {} verification votes", verifier_account.device_id_str(), ctx.remaining_accounts.len() / 4);
+        Ok(())
+    }
+    
+    /// Reveals votes on several tasks at once and updates their tallies. remaining_accounts holds one
+    /// [task_account, verification_record] pair per vote; vote i is bit i of `votes` (LSB first).
+    pub fn verify_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyBatch<'info>>,
+        votes: Vec<u8>,
+        salts: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let verifier_key = ctx.accounts.verifier_account.key();
+        let mut verifier_account = ctx.accounts.verifier_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        require!(!salts.is_empty() && salts.len() <= MAX_VERIFY_BATCH, ComputeError::InvalidBatch);
+        require!(votes.len() == salts.len().div_ceil(8), ComputeError::InvalidBatch);
+        require!(ctx.remaining_accounts.len() == salts.len() * 2, ComputeError::InvalidBatch);
+        
+        for (i, (accounts, salt)) in ctx.remaining_accounts.chunks(2).zip(salts.iter()).enumerate() {
+            let is_valid = votes[i / 8] & (1 << (i % 8)) != 0;
+            let task_loader = AccountLoader::<TaskAccount>::try_from(&accounts[0])?;
+            require!(is_current(&task_loader), ComputeError::AccountNeedsMigration);
+            let task_key = task_loader.key();
+            let mut task_account = task_loader.load_mut()?;
+            let mut verification_record = Account::<VerificationRecord>::try_from(&accounts[1])?;
+            
+            require!(task_account.status == TaskStatus::Completed as u8, ComputeError::TaskNotCompleted);
+            require!(task_account.is_verified == 0, ComputeError::TaskAlreadyVerified);
+            require!(clock.unix_timestamp > task_account.commit_deadline, ComputeError::RevealPhaseNotOpen);
+            require!(clock.unix_timestamp <= task_account.reveal_deadline, ComputeError::RevealPhaseClosed);
+            require!(verification_record.task == task_key, ComputeError::InvalidBatch);
+            require!(verification_record.verifier == verifier_key, ComputeError::VerifierMismatch);
+            require!(!verification_record.revealed, ComputeError::VoteAlreadyRevealed);
+            require!(!task_account.re_executed(), ComputeError::ReExecutionRequired);
+            require!(
+                VerificationRecord::commitment_for(is_valid, salt, &verifier_key) == verification_record.commitment,
+                ComputeError::CommitmentMismatch
+            );
+            
+            verification_record.revealed = true;
+            verification_record.is_valid = is_valid;
+            verification_record.exit(ctx.program_id)?;
+            
+            task_account.verifications += 1;
+            if is_valid {
+                task_account.valid_verifications += 1;
+            }
+            
+            verifier_account.total_verifications += 1;
+            verifier_account.reputation_score = verifier_account.reputation_score.saturating_add(1);
+            
+            emit!(VerificationRevealed {
+                task: task_key,
+                verifier: verifier_key,
+                is_valid,
+            });
+        }
+        
+        msg!("Device {} revealed {} verification votes", verifier_account.device_id_str(), salts.len());
+        Ok(())
+    }
+    
+    pub fn finalize_verification(
+        ctx: Context<FinalizeVerification>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let mut device_account = ctx.accounts.device_account.load_mut()?;
+        let clock = Clock::get()?;
+        
+        require!(task_account.status == TaskStatus::Completed as u8, ComputeError::TaskNotCompleted);
+        require!(task_account.is_verified == 0, ComputeError::TaskAlreadyVerified);
+        require!(task_account.needs_committee(), ComputeError::TaskNotChallenged);
+        require!(task_account.assigned_device == device_key, ComputeError::DeviceNotAssigned);
+        require!(clock.unix_timestamp > task_account.reveal_deadline, ComputeError::RevealPhaseNotClosed);
+        
+        // Byzantine fault tolerance: need the configured share (2/3 by default) of valid verifications
+        if task_account.verifications >= task_account.quorum() {
+            task_account.verification_finalized_at = clock.unix_timestamp;
+            if task_account.valid_verifications as u64 * 10000
+                >= task_account.verifications as u64 * ctx.accounts.network_config.verification_threshold_bps
+            {
+                task_account.is_verified = 1;
+                device_account.reputation_score = device_account.reputation_score.saturating_add(task_account.scaled_reputation(2, &ctx.accounts.network_config));
+                device_account.release_collateral(task_account.collateral);
+                emit!(CollateralReleased {
+                    task: task_key,
+                    device: device_key,
+                    amount: task_account.collateral,
+                    forfeited: false,
+                });
+                task_account.collateral = 0;
+                if task_account.appealed != 0 {
+                    // Overturned on appeal: give back the reputation lost in the first round
+                    device_account.tasks_failed = device_account.tasks_failed.saturating_sub(1);
+                    device_account.reputation_score = device_account.reputation_score.saturating_add(task_account.scaled_reputation(20, &ctx.accounts.network_config));
+                }
+            } else {
+                task_account.status = TaskStatus::Failed as u8;
+                if task_account.appealed == 0 {
+                    device_account.tasks_failed += 1;
+                    device_account.reputation_score = device_account.reputation_score.saturating_sub(task_account.scaled_reputation(20, &ctx.accounts.network_config));
+                }
+            }
+        } else {
+            // Not enough revealed votes yet, open another commit round; tallies carry over
+            task_account.open_verification_round(clock.unix_timestamp);
+        }
+        
+        if task_account.is_verified != 0 || task_account.status == TaskStatus::Failed as u8 {
+            emit!(TaskVerified {
+                task: task_key,
+                device: device_key,
+                is_verified: task_account.is_verified != 0,
+                valid_verifications: task_account.valid_verifications,
+                verifications: task_account.verifications,
+            });
+        } else {
+            emit!(VerificationRoundOpened {
+                task: task_key,
+                commit_deadline: task_account.commit_deadline,
+                reveal_deadline: task_account.reveal_deadline,
+            });
+        }
+        
+        msg!("Task {} verification finalized: verified={}, votes={}/{}", 
+            task_id, task_account.is_verified, task_account.valid_verifications, task_account.verifications);
+        Ok(())
+    }
+    
+    pub fn appeal_task(
+        ctx: Context<AppealTask>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let device_key = ctx.accounts.device_account.key();
+        let device_account = ctx.accounts.device_account.load()?;
+        let clock = Clock::get()?;
+        
+        require!(task_account.status == TaskStatus::Failed as u8, ComputeError::TaskNotFailed);
+        require!(task_account.reward_released != 0, ComputeError::TaskNotSettled);
+        require!(task_account.assigned_device == device_key, ComputeError::DeviceNotAssigned);
+        require!(task_account.appealed == 0, ComputeError::AlreadyAppealed);
+        require!(
+            clock.unix_timestamp <= task_account.verification_finalized_at + APPEAL_WINDOW,
+            ComputeError::AppealWindowClosed
+        );
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.owner_token_account.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(
+            cpi_ctx,
+            with_transfer_fee(&ctx.accounts.reward_mint, APPEAL_BOND)?,
+            ctx.accounts.reward_mint.decimals,
+        )?;
+        
+        let appeal = &mut ctx.accounts.appeal;
+        appeal.task = task_key;
+        appeal.device = device_key;
+        appeal.owner_token_account = ctx.accounts.owner_token_account.key();
+        appeal.bond = APPEAL_BOND;
+        appeal.created_at = clock.unix_timestamp;
+        appeal.resolved = false;
+        
+        // Re-open the task for a fresh, larger committee; first-round voters already hold records and can't vote again
+        task_account.status = TaskStatus::Completed as u8;
+        task_account.appealed = 1;
+        task_account.verification_round += 1;
+        task_account.verifications = 0;
+        task_account.valid_verifications = 0;
+        task_account.open_verification_round(clock.unix_timestamp);
+        
+        emit!(AppealFiled {
+            task: task_key,
+            device: device_key,
+            bond: appeal.bond,
+        });
+        
+        msg!("Task {} appealed by device {}", task_id, device_account.device_id_str());
+        Ok(())
+    }
+    
+    pub fn resolve_appeal(
+        ctx: Context<ResolveAppeal>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let task_account = ctx.accounts.task_account.load()?;
+        let appeal = &mut ctx.accounts.appeal;
+        
+        require!(!appeal.resolved, ComputeError::AppealAlreadyResolved);
+        let outcome = task_account.verification_outcome(Clock::get()?.unix_timestamp)
+            .ok_or(ComputeError::VerificationNotFinalized)?;
+        appeal.resolved = true;
+        
+        // A successful appeal gets its bond back; a failed one forfeits it to the treasury
+        let destination = if outcome {
+            ctx.accounts.owner_token_account.to_account_info()
+        } else {
+            ctx.accounts.treasury.to_account_info()
+        };
+        
+        let seeds = &[
+            b"network_state".as_ref(),
+            &[ctx.bumps.network_state]
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            mint: ctx.accounts.reward_mint.to_account_info(),
+            to: destination,
+            authority: ctx.accounts.network_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, appeal.bond, ctx.accounts.reward_mint.decimals)?;
+        
+        emit!(AppealResolved {
+            task: task_key,
+            device: appeal.device,
+            overturned: outcome,
+        });
+        
+        msg!("Appeal on task {} resolved: overturned={}", task_id, outcome);
+        Ok(())
+    }
+    
+    pub fn claim_verification_reward(
+        ctx: Context<ClaimVerificationReward>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let mut task_account = ctx.accounts.task_account.load_mut()?;
+        let verification_record = &mut ctx.accounts.verification_record;
+        
+        require!(!verification_record.reward_claimed, ComputeError::RewardAlreadyClaimed);
+        require!(
+            task_account.compute_requirements.storage_proof_required == 0 || task_account.storage_proved_at != 0,
+            ComputeError::StorageNotProven
+        );
+        let outcome = task_account.verification_outcome(Clock::get()?.unix_timestamp)
+            .ok_or(ComputeError::VerificationNotFinalized)?;
+        // Only revealed votes from the deciding round that matched the outcome share the pool
+        require!(
+            verification_record.revealed &&
+            verification_record.round == task_account.verification_round &&
+            verification_record.is_valid == outcome,
+            ComputeError::NotEligibleForReward
+        );
+        
+        let share = task_account.verification_pool
+            .checked_div(task_account.winning_votes(outcome) as u64)
+            .ok_or(ComputeError::MathOverflow)?;
+        
+        verification_record.reward_claimed = true;
+        task_account.verification_claims += 1;
+        
+        if task_account.reward_kind == RewardKind::Sol as u8 {
+            SolEscrow::new(task_key, ctx.accounts.sol_escrow.as_ref(), ctx.bumps.sol_escrow, &ctx.accounts.system_program)?
+                .pay(&ctx.accounts.verifier.to_account_info(), share)?;
+        } else {
+            let seeds = &[
+                b"network_state".as_ref(),
+                &[ctx.bumps.network_state]
+            ];
+            let signer_seeds = &[&seeds[..]];
+            
+            let verifier_token_account = ctx.accounts.verifier_token_account.as_ref()
+                .ok_or(ComputeError::TokenAccountRequired)?;
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: verifier_token_account.to_account_info(),
+                authority: ctx.accounts.network_state.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_interface::transfer_checked(cpi_ctx, share, ctx.accounts.reward_mint.decimals)?;
+        }
+        
+        emit!(VerificationRewardClaimed {
+            task: task_key,
+            verifier: ctx.accounts.verifier_account.key(),
+            amount: share,
+        });
+        
+        msg!("Verifier {} claimed {} for task {}", ctx.accounts.verifier_account.load()?.device_id_str(), share, task_id);
+        Ok(())
+    }
+    
+    pub fn settle_verification(
+        ctx: Context<SettleVerification>,
+        task_id: String,
+    ) -> Result<()> {
+        let task_key = ctx.accounts.task_account.key();
+        let task_account = ctx.accounts.task_account.load()?;
+        let verifier_key = ctx.accounts.verifier_account.key();
+        let mut verifier_account = ctx.accounts.verifier_account.load_mut()?;
+        let verification_record = &mut ctx.accounts.verification_record;
+        
+        require!(!verification_record.settled, ComputeError::VerificationAlreadySettled);
+        let outcome = task_account.verification_outcome(Clock::get()?.unix_timestamp)
+            .ok_or(ComputeError::VerificationNotFinalized)?;
+        
+        verification_record.settled = true;
+        verifier_account.verifier_locked_stake = verifier_account.verifier_locked_stake
+            .saturating_sub(verification_record.locked_stake);
+        
+        // Verifiers who never revealed, or voted against the final 2/3 outcome, lose part of their stake
+        let mut slash_amount = 0;
+        if !verification_record.revealed || verification_record.is_valid != outcome {
+            slash_amount = verifier_account.staked_amount
+                .checked_mul(VERIFIER_SLASH_BPS)
+                .ok_or(ComputeError::MathOverflow)?
+                .checked_div(10000)
+                .ok_or(ComputeError::MathOverflow)?;
+        }
+        
+        if slash_amount > 0 {
+            let seeds = &[
+                b"network_state".as_ref(),
+                &[ctx.bumps.network_state]
+            ];
+            let signer_seeds = &[&seeds[..]];
+            
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.stake_vault.to_account_info(),
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.network_state.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_interface::transfer_checked(cpi_ctx, slash_amount, ctx.accounts.reward_mint.decimals)?;
+            ctx.accounts.network_state.accrue_burn(slash_amount, &ctx.accounts.network_config);
+            
+            verifier_account.slash_stake(slash_amount, &mut ctx.accounts.network_state);
+            verifier_account.tier = verifier_account.eligible_tier(&ctx.accounts.network_config) as u8;
+            
+            emit!(Slashed {
+                device: verifier_key,
+                task: task_key,
+                amount: slash_amount,
+                reason: SlashReason::MinorityVote,
+            });
+        }
+        
+        emit!(VerificationSettled {
+            task: task_key,
+            verifier: verifier_key,
+            slashed: slash_amount,
+        });
+        
+        msg!("Verification of task {} by device {} settled, slashed {}", 
+            task_id, verifier_account.device_id_str(), slash_amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
         space = 8 + NetworkState::LEN,
         seeds = [b"network_state"],
         bump
     )]
     pub network_state: Account<'info, NetworkState>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + NetworkConfig::LEN,
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + EpochState::LEN,
+        seeds = [b"epoch_state"],
+        bump
+    )]
+    pub epoch_state: Account<'info, EpochState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVaults<'info> {
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = network_state,
+        seeds = [b"reward_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = network_state,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = network_state,
+        seeds = [b"treasury", mint.key().as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(decimals: u8)]
+pub struct InitializeRewardMint<'info> {
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = decimals,
+        mint::authority = network_state,
+        seeds = [b"reward_mint"],
+        bump
+    )]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintEpochEmissions<'info> {
+    #[account(
+        mut,
+        seeds = [b"epoch_state"],
+        bump
+    )]
+    pub epoch_state: Account<'info, EpochState>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        mut,
+        address = network_state.reward_mint,
+        constraint = reward_mint.mint_authority == Some(network_state.key()).into() @ ComputeError::MintAuthorityMismatch
+    )]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump,
+        constraint = treasury.mint == network_state.reward_mint @ ComputeError::InvalidTreasury
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct BurnPending<'info> {
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        mut,
+        address = network_state.reward_mint
+    )]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        constraint = network_state.sensitive_authority() == authority.key() @ ComputeError::Unauthorized
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    #[account(address = treasury.mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSolFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump,
+        constraint = network_state.sensitive_authority() == authority.key() @ ComputeError::Unauthorized
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(mut)]
+    pub destination: SystemAccount<'info>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddRewardMint<'info> {
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        constraint = network_state.sensitive_authority() == authority.key() @ ComputeError::Unauthorized
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        mut,
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = mint,
+        token::authority = network_state,
+        seeds = [b"reward_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = mint,
+        token::authority = network_state,
+        seeds = [b"treasury", mint.key().as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitTreasury<'info> {
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        constraint = network_state.sensitive_authority() == authority.key() @ ComputeError::Unauthorized
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(address = network_state.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        token::mint = reward_mint,
+        token::authority = network_state,
+        seeds = [b"treasury", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveRewardMint<'info> {
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        constraint = network_state.sensitive_authority() == authority.key() @ ComputeError::Unauthorized
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        mut,
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeAdminCouncil<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AdminCouncil::LEN,
+        seeds = [b"admin_council"],
+        bump
+    )]
+    pub admin_council: Account<'info, AdminCouncil>,
+    /// CHECK: data-less PDA that signs the council's executed actions and becomes the network authority
+    #[account(
+        seeds = [b"admin_authority"],
+        bump
+    )]
+    pub admin_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAdminCouncil<'info> {
+    #[account(
+        mut,
+        seeds = [b"admin_council"],
+        bump
+    )]
+    pub admin_council: Account<'info, AdminCouncil>,
+    #[account(
+        seeds = [b"admin_authority"],
+        bump
+    )]
+    pub admin_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdminAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"admin_council"],
+        bump
+    )]
+    pub admin_council: Account<'info, AdminCouncil>,
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + AdminProposal::LEN,
+        seeds = [b"admin_proposal", admin_council.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveAdminAction<'info> {
+    #[account(
+        seeds = [b"admin_council"],
+        bump
+    )]
+    pub admin_council: Account<'info, AdminCouncil>,
+    #[account(
+        mut,
+        seeds = [b"admin_proposal", proposal.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+    pub member: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAdminAction<'info> {
+    /// Read-only here, so an executed update_admin_council is not overwritten on exit
+    #[account(
+        seeds = [b"admin_council"],
+        bump
+    )]
+    pub admin_council: Account<'info, AdminCouncil>,
+    #[account(
+        mut,
+        seeds = [b"admin_proposal", proposal.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+    /// CHECK: PDA signer for the proposed instruction; writable so it can also pay for new accounts
+    #[account(
+        mut,
+        seeds = [b"admin_authority"],
+        bump
+    )]
+    pub admin_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAdminAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"admin_proposal", proposal.id.to_le_bytes().as_ref()],
+        bump,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTimelock<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Timelock::LEN,
+        seeds = [b"timelock"],
+        bump
+    )]
+    pub timelock: Account<'info, Timelock>,
+    /// CHECK: data-less PDA that signs executed actions
+    #[account(
+        seeds = [b"timelock_authority"],
+        bump
+    )]
+    pub timelock_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetTimelockDelay<'info> {
+    #[account(
+        mut,
+        seeds = [b"timelock"],
+        bump
+    )]
+    pub timelock: Account<'info, Timelock>,
+    #[account(
+        seeds = [b"timelock_authority"],
+        bump
+    )]
+    pub timelock_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ScheduleAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"timelock"],
+        bump
+    )]
+    pub timelock: Account<'info, Timelock>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ScheduledAction::LEN,
+        seeds = [b"scheduled_action", timelock.action_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub action: Account<'info, ScheduledAction>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"scheduled_action", action.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub action: Account<'info, ScheduledAction>,
+    /// CHECK: PDA signer for the scheduled instruction
+    #[account(
+        seeds = [b"timelock_authority"],
+        bump
+    )]
+    pub timelock_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"scheduled_action", action.id.to_le_bytes().as_ref()],
+        bump,
+        close = authority
+    )]
+    pub action: Account<'info, ScheduledAction>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + GovernanceState::LEN,
+        seeds = [b"governance"],
+        bump
+    )]
+    pub governance: Account<'info, GovernanceState>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + GovernanceProposal::LEN,
+        seeds = [b"proposal", governance.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    #[account(
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        seeds = [b"epoch_state"],
+        bump
+    )]
+    pub epoch_state: Account<'info, EpochState>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + VoteRecord::LEN,
+        seeds = [b"vote", proposal.key().as_ref(), device_account.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    #[account(
+        mut,
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        constraint = network_state.sensitive_authority() == authority.key() @ ComputeError::Unauthorized
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(device_id: String)]
+pub struct RegisterDevice<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + DeviceAccount::LEN,
+        seeds = [b"device", owner.key().as_ref(), device_id.as_bytes()],
+        bump
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    /// Only needed when the specs carry an attester signature
+    #[account(
+        seeds = [b"attester_registry"],
+        bump
+    )]
+    pub attester_registry: Option<Account<'info, AttesterRegistry>>,
+    /// CHECK: checked to be the instructions sysvar when the attestation is read
+    pub instructions: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Every device is minted an operator license NFT; whoever holds it controls the device
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"license", device_account.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = network_state,
+        mint::freeze_authority = network_state,
+        mint::token_program = token_program
+    )]
+    pub license_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = license_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub license_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: metadata PDA of the license mint, derived and created by the metadata program
+    #[account(mut)]
+    pub license_metadata: UncheckedAccount<'info>,
+    /// CHECK: master edition PDA of the license mint, derived and created by the metadata program
+    #[account(mut)]
+    pub license_edition: UncheckedAccount<'info>,
+    /// CHECK: the Metaplex Token Metadata program
+    #[account(address = TOKEN_METADATA_PROGRAM_ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl RegisterDevice<'_> {
+    /// Mints one license token to the owner and makes it a Metaplex NFT: metadata, then a master
+    /// edition with no prints, which takes over the mint and freeze authority and fixes the supply.
+    pub fn mint_license(&self, device_id: &str, network_state_bump: u8) -> Result<Pubkey> {
+        let seeds = &[
+            b"network_state".as_ref(),
+            &[network_state_bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = MintTo {
+            mint: self.license_mint.to_account_info(),
+            to: self.license_token_account.to_account_info(),
+            authority: self.network_state.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer_seeds);
+        token_interface::mint_to(cpi_ctx, 1)?;
+        
+        let mut data = vec![CREATE_METADATA_V3_INSTRUCTION];
+        MetaplexDataV2 {
+            name: format!("SolMobile Device {}", device_id),
+            symbol: "SMDEV".to_string(),
+            uri: String::new(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        }.serialize(&mut data)?;
+        // is_mutable, then collection_details: None
+        data.extend_from_slice(&[1, 0]);
+        let ix = Instruction {
+            program_id: TOKEN_METADATA_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.license_metadata.key(), false),
+                AccountMeta::new_readonly(self.license_mint.key(), false),
+                AccountMeta::new_readonly(self.network_state.key(), true),
+                AccountMeta::new(self.owner.key(), true),
+                AccountMeta::new_readonly(self.network_state.key(), true),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data,
+        };
+        invoke_signed(
+            &ix,
+            &[
+                self.license_metadata.to_account_info(),
+                self.license_mint.to_account_info(),
+                self.network_state.to_account_info(),
+                self.owner.to_account_info(),
+                self.system_program.to_account_info(),
+                self.token_metadata_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+        
+        // max_supply: Some(0)
+        let mut data = vec![CREATE_MASTER_EDITION_V3_INSTRUCTION, 1];
+        data.extend_from_slice(&0u64.to_le_bytes());
+        let ix = Instruction {
+            program_id: TOKEN_METADATA_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.license_edition.key(), false),
+                AccountMeta::new(self.license_mint.key(), false),
+                AccountMeta::new_readonly(self.network_state.key(), true),
+                AccountMeta::new_readonly(self.network_state.key(), true),
+                AccountMeta::new(self.owner.key(), true),
+                AccountMeta::new(self.license_metadata.key(), false),
+                AccountMeta::new_readonly(self.token_program.key(), false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data,
+        };
+        invoke_signed(
+            &ix,
+            &[
+                self.license_edition.to_account_info(),
+                self.license_mint.to_account_info(),
+                self.network_state.to_account_info(),
+                self.owner.to_account_info(),
+                self.license_metadata.to_account_info(),
+                self.token_program.to_account_info(),
+                self.system_program.to_account_info(),
+                self.token_metadata_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+        
+        Ok(self.license_mint.key())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimDeviceLicense<'info> {
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        constraint = license_token_account.mint == device_account.load()?.license_mint @ ComputeError::NoDeviceLicense,
+        constraint = license_token_account.owner == new_owner.key() @ ComputeError::NoDeviceLicense,
+        constraint = license_token_account.amount == 1 @ ComputeError::NoDeviceLicense
+    )]
+    pub license_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub new_owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_id: String)]
+pub struct CreatePool<'info> {
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + Pool::LEN,
+        seeds = [b"pool", operator.key().as_ref(), pool_id.as_bytes()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut)]
+    pub operator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPoolCommission<'info> {
+    #[account(
+        mut,
+        has_one = operator
+    )]
+    pub pool: Account<'info, Pool>,
+    pub operator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct JoinPool<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        has_one = operator
+    )]
+    pub pool: Account<'info, Pool>,
+    pub owner: Signer<'info>,
+    pub operator: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct LeavePool<'info> {
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        address = device_account.load()?.pool @ ComputeError::DeviceNotInPool
+    )]
+    pub pool: Account<'info, Pool>,
+    /// Either the device owner or the pool operator
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SyncPoolStake<'info> {
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        address = device_account.load()?.pool @ ComputeError::DeviceNotInPool
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct RoutePoolTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = task_account.load()?.target_pool == pool.key() @ ComputeError::NotPoolTask,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration)]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(has_one = operator)]
+    pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    pub operator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimPoolCommission<'info> {
+    #[account(
+        mut,
+        has_one = operator
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut)]
+    pub operator: Signer<'info>,
+    #[account(
+        mut,
+        constraint = operator_token_account.mint == reward_mint.key() @ ComputeError::InvalidTokenAccount
+    )]
+    pub operator_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(address = network_state.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(device_id: String)]
+pub struct MigrateDevicePda<'info> {
+    #[account(
+        mut,
+        seeds = [b"device", device_id.as_bytes()],
+        bump,
+        has_one = owner,
+        close = owner,
+        constraint = is_current(&legacy_device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub legacy_device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + DeviceAccount::LEN,
+        seeds = [b"device", owner.key().as_ref(), device_id.as_bytes()],
+        bump
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateDevice<'info> {
+    /// CHECK: may predate the current layout, so it is checked and resized by migrate_layout
+    #[account(mut)]
+    pub device_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTask<'info> {
+    /// CHECK: may predate the current layout, so it is checked and resized by migrate_layout
+    #[account(mut)]
+    pub task_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct SubmitTask<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TaskAccount::LEN,
+        seeds = [b"task", task_id.as_bytes()],
+        bump
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + SubmitterProfile::LEN,
+        seeds = [b"submitter", submitter.key().as_ref()],
+        bump
+    )]
+    pub submitter_profile: Account<'info, SubmitterProfile>,
+    #[account(mut)]
+    pub submitter_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Prepaid credits to draw a token reward from instead of submitter_token_account
+    #[account(
+        mut,
+        seeds = [b"credits", submitter.key().as_ref(), reward_mint.key().as_ref()],
+        bump
+    )]
+    pub credit_account: Option<Account<'info, CreditAccount>>,
+    /// Lamport escrow of a SOL-paid task
+    #[account(
+        mut,
+        seeds = [b"sol_escrow", task_account.key().as_ref()],
+        bump
+    )]
+    pub sol_escrow: Option<SystemAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = is_current(queue_tail) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_tail: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        seeds = [b"pending_index".as_ref(), &[pending_index.task_type]],
+        bump
+    )]
+    pub pending_index: Account<'info, PendingIndex>,
+    #[account(
+        mut,
+        seeds = [b"priority_index"],
+        bump
+    )]
+    pub priority_index: Account<'info, PriorityIndex>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    /// The network's reward mint or one of network_config.accepted_mints
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    /// Funds the task and profile rent, so a program-derived submitter needs no lamports of its own
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+impl SubmitTask<'_> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit(
+        &mut self,
+        task_id: String,
+        task_type: TaskType,
+        compute_requirements: ComputeRequirements,
+        reward_amount: u64,
+        verification_mode: VerificationMode,
+        redundancy: u8,
+        min_reputation: u16,
+        priority_fee: u64,
+        reward_kind: RewardKind,
+        direct_device: Option<(Pubkey, &DeviceAccount)>,
+        target_pool: Option<Pubkey>,
+    ) -> Result<()> {
+        let task_key = self.task_account.key();
+        let mut task_account = self.task_account.load_init()?;
+        let clock = Clock::get()?;
+        
+        let submission = Submission {
+            submitter: self.submitter.key(),
+            reward_mint: self.reward_mint.key(),
+            task_type,
+            compute_requirements,
+            reward_amount,
+            verification_mode,
+            redundancy,
+            min_reputation,
+            priority_fee,
+            reward_kind,
+        };
+        let terms = task_account.init_submission(
+            &task_id,
+            &submission,
+            &mut self.submitter_profile,
+            &self.network_state,
+            &self.network_config,
+            &self.pending_index,
+            &clock,
+        )?;
+        let SubmissionTerms { reward_amount, total, submission_fee, .. } = terms;
+        
+        if reward_kind == RewardKind::Sol {
+            // The escrow also carries its own rent-exempt minimum, handed back by close_task
+            let sol_escrow = self.sol_escrow.as_ref().ok_or(ComputeError::SolAccountsMissing)?;
+            let cpi_accounts = system_program::Transfer {
+                from: self.submitter.to_account_info(),
+                to: sol_escrow.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+            let escrow_amount = total
+                .checked_add(Rent::get()?.minimum_balance(0))
+                .ok_or(ComputeError::MathOverflow)?;
+            system_program::transfer(cpi_ctx, escrow_amount)?;
+            
+            if submission_fee > 0 {
+                let cpi_accounts = system_program::Transfer {
+                    from: self.submitter.to_account_info(),
+                    to: self.network_state.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+                system_program::transfer(cpi_ctx, submission_fee)?;
+                self.network_state.sol_fees = self.network_state.sol_fees
+                    .checked_add(submission_fee)
+                    .ok_or(ComputeError::MathOverflow)?;
+            }
+        } else if let Some(credit_account) = self.credit_account.as_mut() {
+            // Credits already sit in the reward vault, so only the submission fee has to move
+            let debit = total
+                .checked_add(submission_fee)
+                .ok_or(ComputeError::MathOverflow)?;
+            credit_account.spend(debit)?;
+            
+            if submission_fee > 0 {
+                let (_, bump) = Pubkey::find_program_address(&[b"network_state"], &crate::ID);
+                let seeds = &[
+                    b"network_state".as_ref(),
+                    &[bump]
+                ];
+                let signer_seeds = &[&seeds[..]];
+                
+                let cpi_accounts = TransferChecked {
+                    from: self.reward_vault.to_account_info(),
+                    mint: self.reward_mint.to_account_info(),
+                    to: self.treasury.to_account_info(),
+                    authority: self.network_state.to_account_info(),
+                };
+                let cpi_program = self.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+                token_interface::transfer_checked(cpi_ctx, submission_fee, self.reward_mint.decimals)?;
+            }
+            
+            emit!(CreditsSpent {
+                credit_account: credit_account.key(),
+                task: task_key,
+                amount: debit,
+                balance: credit_account.balance,
+            });
+        } else {
+            // Escrow the reward and priority fee in the vault until the task is settled or cancelled
+            let submitter_token_account = self.submitter_token_account.as_ref()
+                .ok_or(ComputeError::TokenAccountRequired)?;
+            let cpi_accounts = TransferChecked {
+                from: submitter_token_account.to_account_info(),
+                mint: self.reward_mint.to_account_info(),
+                to: self.reward_vault.to_account_info(),
+                authority: self.submitter.to_account_info(),
+            };
+            let cpi_program = self.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token_interface::transfer_checked(
+                cpi_ctx,
+                with_transfer_fee(&self.reward_mint, total)?,
+                self.reward_mint.decimals,
+            )?;
+            
+            if submission_fee > 0 {
+                let cpi_accounts = TransferChecked {
+                    from: submitter_token_account.to_account_info(),
+                    mint: self.reward_mint.to_account_info(),
+                    to: self.treasury.to_account_info(),
+                    authority: self.submitter.to_account_info(),
+                };
+                let cpi_program = self.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+                token_interface::transfer_checked(
+                    cpi_ctx,
+                    with_transfer_fee(&self.reward_mint, submission_fee)?,
+                    self.reward_mint.decimals,
+                )?;
+            }
+        }
+        
+        // Direct tasks skip matching and are never listed; they go straight to the chosen device
+        if let Some((device_key, device_account)) = direct_device {
+            task_account.check_device(device_account, &self.network_config)?;
+            task_account.assign(device_key, clock.unix_timestamp, false, self.network_config.expiry_multiplier);
+            task_account.direct = 1;
+            emit!(TaskAssigned {
+                task: task_key,
+                device: device_key,
+                accepted: false,
+            });
+        } else if let Some(pool) = target_pool {
+            // Pool tasks stay unlisted until the pool's operator routes them with route_pool_task
+            task_account.target_pool = pool;
+            self.network_state.task_opened();
+        } else {
+            self.network_state.task_opened();
+            self.priority_index.insert(task_key, priority_fee);
+            self.pending_index.push(task_key);
+            self.network_state.enqueue(task_key, &mut task_account, self.queue_tail.as_ref())?;
+        }
+        
+        emit!(submission.submitted(task_key, &terms));
+        
+        msg!("Task submitted: {} with reward: {}", task_id, reward_amount);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct SubmitDirectTask<'info> {
+    #[account(constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration)]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    pub submit: SubmitTask<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct SubmitPoolTask<'info> {
+    pub pool: Account<'info, Pool>,
+    pub submit: SubmitTask<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct RefundDirectTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        close = submitter,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    /// CHECK: receives the task account's rent; checked against the task's submitter
+    #[account(mut)]
+    pub submitter: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"submitter", submitter.key().as_ref()],
+        bump
+    )]
+    pub submitter_profile: Account<'info, SubmitterProfile>,
+    #[account(
+        mut,
+        constraint = submitter_token_account.owner == submitter.key() @ ComputeError::InvalidTokenAccount,
+        constraint = submitter_token_account.mint == reward_mint.key() @ ComputeError::InvalidTokenAccount
+    )]
+    pub submitter_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Lamport escrow of a SOL-paid task
+    #[account(
+        mut,
+        seeds = [b"sol_escrow", task_account.key().as_ref()],
+        bump
+    )]
+    pub sol_escrow: Option<SystemAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(address = task_account.load()?.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(template_id: String)]
+pub struct CreateTaskTemplate<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + TaskTemplate::LEN,
+        seeds = [b"template", owner.key().as_ref(), template_id.as_bytes()],
+        bump
+    )]
+    pub task_template: Account<'info, TaskTemplate>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct SubmitTaskFromTemplate<'info> {
+    pub task_template: Account<'info, TaskTemplate>,
+    pub submit: SubmitTask<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String, intent: SubmissionIntent)]
+pub struct SubmitTaskWithIntent<'info> {
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + TaskAccount::LEN,
+        seeds = [b"task", task_id.as_bytes()],
+        bump
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + SubmitterProfile::LEN,
+        seeds = [b"submitter", intent.submitter.as_ref()],
+        bump
+    )]
+    pub submitter_profile: Account<'info, SubmitterProfile>,
+    #[account(
+        mut,
+        seeds = [b"credits", intent.submitter.as_ref(), intent.reward_mint.as_ref()],
+        bump
+    )]
+    pub credit_account: Account<'info, CreditAccount>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = is_current(queue_tail) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_tail: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        seeds = [b"pending_index".as_ref(), &[pending_index.task_type]],
+        bump
+    )]
+    pub pending_index: Account<'info, PendingIndex>,
+    #[account(
+        mut,
+        seeds = [b"priority_index"],
+        bump
+    )]
+    pub priority_index: Account<'info, PriorityIndex>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = intent.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: checked to be the instructions sysvar when the intent signature is read
+    pub instructions: UncheckedAccount<'info>,
+    /// Pays the transaction and rent in place of the submitter
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey)]
+pub struct DepositCredits<'info> {
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = 8 + CreditAccount::LEN,
+        seeds = [b"credits", beneficiary.as_ref(), reward_mint.key().as_ref()],
+        bump
+    )]
+    pub credit_account: Account<'info, CreditAccount>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    #[account(
+        mut,
+        constraint = depositor_token_account.mint == reward_mint.key() @ ComputeError::InvalidTokenAccount
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    /// The network's reward mint or one of network_config.accepted_mints
+    #[account(
+        constraint = reward_mint.key() == network_state.reward_mint
+            || network_config.accepted_mint(&reward_mint.key()).is_some() @ ComputeError::RewardMintNotAccepted
+    )]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawCredits<'info> {
+    #[account(
+        mut,
+        seeds = [b"credits", owner.key().as_ref(), reward_mint.key().as_ref()],
+        bump,
+        has_one = owner
+    )]
+    pub credit_account: Account<'info, CreditAccount>,
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == reward_mint.key() @ ComputeError::InvalidTokenAccount
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(address = credit_account.mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct SetTaskAllowlist<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    pub submitter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String, total_len: u32)]
+pub struct InitializeTaskData<'info> {
+    #[account(
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        init,
+        payer = submitter,
+        space = 8 + TaskData::space(total_len as usize),
+        seeds = [b"task_data", task_account.key().as_ref()],
+        bump
+    )]
+    pub task_data: Account<'info, TaskData>,
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct AppendTaskData<'info> {
+    #[account(
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        seeds = [b"task_data", task_account.key().as_ref()],
+        bump
+    )]
+    pub task_data: Account<'info, TaskData>,
+    pub submitter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct FinalizeTaskData<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        seeds = [b"task_data", task_account.key().as_ref()],
+        bump
+    )]
+    pub task_data: Account<'info, TaskData>,
+    pub submitter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct SetZkVerifier<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    pub submitter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct SetUsdReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    pub submitter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct VerifyZkProof<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    /// CHECK: must match the task's zk_verifier, checked in the handler
+    #[account(executable)]
+    pub zk_verifier: UncheckedAccount<'info>,
+    /// CHECK: must match the task's zk_verifying_key, read only by the verifier program
+    pub zk_verifying_key: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct ProveStorage<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        seeds = [b"network_config"],
+        bump,
+        constraint = network_config.storage_oracle == oracle.key() @ ComputeError::Unauthorized
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    pub oracle: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct PostTaskKey<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration)]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    pub submitter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct CancelTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        close = submitter,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"submitter", submitter.key().as_ref()],
+        bump
+    )]
+    pub submitter_profile: Account<'info, SubmitterProfile>,
+    #[account(mut)]
+    pub submitter_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Lamport escrow of a SOL-paid task
+    #[account(
+        mut,
+        seeds = [b"sol_escrow", task_account.key().as_ref()],
+        bump
+    )]
+    pub sol_escrow: Option<SystemAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = is_current(queue_prev) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_prev: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        constraint = is_current(queue_next) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_next: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        seeds = [b"pending_index".as_ref(), &[task_account.load()?.task_type]],
+        bump
+    )]
+    pub pending_index: Account<'info, PendingIndex>,
+    #[account(
+        mut,
+        seeds = [b"priority_index"],
+        bump
+    )]
+    pub priority_index: Account<'info, PriorityIndex>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(address = task_account.load()?.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct AssignTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        constraint = is_current(queue_prev) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_prev: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        constraint = is_current(queue_next) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_next: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        seeds = [b"pending_index".as_ref(), &[task_account.load()?.task_type]],
+        bump
+    )]
+    pub pending_index: Account<'info, PendingIndex>,
+    #[account(
+        mut,
+        seeds = [b"priority_index"],
+        bump
+    )]
+    pub priority_index: Account<'info, PriorityIndex>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct ClaimTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(constraint = is_current(queue_head) @ ComputeError::AccountNeedsMigration)]
+    pub queue_head: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        constraint = is_current(queue_prev) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_prev: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        constraint = is_current(queue_next) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_next: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        seeds = [b"pending_index".as_ref(), &[task_account.load()?.task_type]],
+        bump
+    )]
+    pub pending_index: Account<'info, PendingIndex>,
+    #[account(
+        mut,
+        seeds = [b"priority_index"],
+        bump
+    )]
+    pub priority_index: Account<'info, PriorityIndex>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    pub owner: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct ClaimRedundantTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Execution::LEN,
+        seeds = [b"execution", task_account.key().as_ref(), device_account.key().as_ref()],
+        bump
+    )]
+    pub execution: Account<'info, Execution>,
+    #[account(
+        mut,
+        constraint = is_current(queue_prev) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_prev: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        constraint = is_current(queue_next) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_next: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        seeds = [b"pending_index".as_ref(), &[task_account.load()?.task_type]],
+        bump
+    )]
+    pub pending_index: Account<'info, PendingIndex>,
+    #[account(
+        mut,
+        seeds = [b"priority_index"],
+        bump
+    )]
+    pub priority_index: Account<'info, PriorityIndex>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct SubmitRedundantResult<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"execution", task_account.key().as_ref(), device_account.key().as_ref()],
+        bump
+    )]
+    pub execution: Account<'info, Execution>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    pub owner: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct SettleRedundantTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        seeds = [b"submitter", task_account.load()?.submitter.as_ref()],
+        bump
+    )]
+    pub submitter_profile: Account<'info, SubmitterProfile>,
+    /// Receives the refunded escrow, and forfeited collateral when the task pays in the network mint
+    #[account(
+        mut,
+        constraint = submitter_token_account.owner == task_account.load()?.submitter @ ComputeError::InvalidTokenAccount,
+        constraint = submitter_token_account.mint == task_account.load()?.reward_mint @ ComputeError::InvalidTokenAccount
+    )]
+    pub submitter_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury", network_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = task_account.load()?.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    #[account(address = network_state.reward_mint)]
+    pub network_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct ClaimRedundantReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"execution", task_account.key().as_ref(), device_account.key().as_ref()],
+        bump
+    )]
+    pub execution: Account<'info, Execution>,
+    /// Required while reward vesting is enabled
+    #[account(
+        mut,
+        seeds = [b"vesting", device_account.key().as_ref()],
+        bump
+    )]
+    pub vesting_account: Option<Account<'info, VestingAccount>>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    /// Receives the reward directly when the task is paid in a mint other than the network's
+    #[account(
+        mut,
+        constraint = device_token_account.owner == device_account.load()?.owner @ ComputeError::InvalidTokenAccount,
+        constraint = device_token_account.mint == reward_mint.key() @ ComputeError::InvalidTokenAccount
+    )]
+    pub device_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = task_account.load()?.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct RespondToAssignment<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        constraint = is_current(queue_tail) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_tail: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        seeds = [b"pending_index".as_ref(), &[task_account.load()?.task_type]],
+        bump
+    )]
+    pub pending_index: Account<'info, PendingIndex>,
+    #[account(
+        mut,
+        seeds = [b"priority_index"],
+        bump
+    )]
+    pub priority_index: Account<'info, PriorityIndex>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    pub owner: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct StartTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration)]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    /// Delegated hot key, required when `authority` is not the device owner
+    #[account(
+        seeds = [b"agent", device_account.key().as_ref()],
+        bump
+    )]
+    pub agent_authority: Option<Account<'info, AgentAuthority>>,
+    pub authority: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct ReportProgress<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration)]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    /// Delegated hot key, required when `authority` is not the device owner
+    #[account(
+        seeds = [b"agent", device_account.key().as_ref()],
+        bump
+    )]
+    pub agent_authority: Option<Account<'info, AgentAuthority>>,
+    pub authority: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct CompleteTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    /// Required while reward vesting is enabled
+    #[account(
+        mut,
+        seeds = [b"vesting", device_account.key().as_ref()],
+        bump
+    )]
+    pub vesting_account: Option<Account<'info, VestingAccount>>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = submitter_token_account.owner == task_account.load()?.submitter @ ComputeError::InvalidTokenAccount,
+        constraint = submitter_token_account.mint == reward_mint.key() @ ComputeError::InvalidTokenAccount
+    )]
+    pub submitter_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Lamport escrow of a SOL-paid task
+    #[account(
+        mut,
+        seeds = [b"sol_escrow", task_account.key().as_ref()],
+        bump
+    )]
+    pub sol_escrow: Option<SystemAccount<'info>>,
+    /// CHECK: the device owner, who receives the reward of a SOL-paid task
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+    /// Delegated hot key, required when `authority` is not the device owner
+    #[account(
+        seeds = [b"agent", device_account.key().as_ref()],
+        bump
+    )]
+    pub agent_authority: Option<Account<'info, AgentAuthority>>,
+    pub authority: Signer<'info>,
+    /// CHECK: receives the late refund of a SOL-paid task
+    #[account(
+        mut,
+        address = task_account.load()?.submitter
+    )]
+    pub submitter: Option<UncheckedAccount<'info>>,
+    /// CHECK: Pyth PriceUpdateV2 account, required for USD-priced tasks and validated by usd_to_tokens
+    pub price_update: Option<UncheckedAccount<'info>>,
+    /// Receives the reward directly when the task is paid in a mint other than the network's
+    #[account(
+        mut,
+        constraint = device_token_account.owner == device_account.load()?.owner @ ComputeError::InvalidTokenAccount,
+        constraint = device_token_account.mint == reward_mint.key() @ ComputeError::InvalidTokenAccount
+    )]
+    pub device_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = task_account.load()?.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"epoch_state"],
+        bump
+    )]
+    pub epoch_state: Account<'info, EpochState>,
+    /// The current epoch's leaderboard, once open_leaderboard has created it
+    #[account(
+        mut,
+        seeds = [b"leaderboard", epoch_state.epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
+    /// Required when the device belongs to a pool
+    #[account(
+        mut,
+        address = device_account.load()?.pool @ ComputeError::DeviceNotInPool
+    )]
+    pub pool: Option<Account<'info, Pool>>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct ExpireTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = keeper,
+        space = 8 + SlashRecord::LEN,
+        seeds = [b"slash", task_account.key().as_ref(), &[task_account.load()?.retry_count]],
+        bump
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+    /// Only needed when the task pays in the network mint and has collateral to refund
+    #[account(
+        mut,
+        constraint = submitter_token_account.owner == task_account.load()?.submitter @ ComputeError::InvalidTokenAccount,
+        constraint = submitter_token_account.mint == task_account.load()?.reward_mint @ ComputeError::InvalidTokenAccount
+    )]
+    pub submitter_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub keeper_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    #[account(address = network_state.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct SlashDevice<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SlashRecord::LEN,
+        seeds = [b"slash", task_account.key().as_ref(), &[task_account.load()?.retry_count]],
+        bump
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+    #[account(
+        mut,
+        constraint = submitter_token_account.owner == task_account.load()?.submitter @ ComputeError::InvalidTokenAccount,
+        constraint = submitter_token_account.mint == network_state.reward_mint @ ComputeError::InvalidTokenAccount
+    )]
+    pub submitter_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(address = network_state.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct RefundFailedTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        close = submitter,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"submitter", submitter.key().as_ref()],
+        bump
+    )]
+    pub submitter_profile: Account<'info, SubmitterProfile>,
+    #[account(mut)]
+    pub submitter_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Lamport escrow of a SOL-paid task
+    #[account(
+        mut,
+        seeds = [b"sol_escrow", task_account.key().as_ref()],
+        bump
+    )]
+    pub sol_escrow: Option<SystemAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(address = task_account.load()?.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct ReassignTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        constraint = is_current(queue_tail) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_tail: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        seeds = [b"pending_index".as_ref(), &[task_account.load()?.task_type]],
+        bump
+    )]
+    pub pending_index: Account<'info, PendingIndex>,
+    #[account(
+        mut,
+        seeds = [b"priority_index"],
+        bump
+    )]
+    pub priority_index: Account<'info, PriorityIndex>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    pub submitter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct OpenBidding<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    pub submitter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct PlaceBid<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Bid::LEN,
+        seeds = [b"bid", task_account.key().as_ref(), device_account.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, Bid>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct AwardTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        seeds = [b"bid", task_account.key().as_ref(), device_account.key().as_ref()],
+        bump,
+        close = bidder
+    )]
+    pub bid: Account<'info, Bid>,
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    /// CHECK: receives the winning bid's rent
+    #[account(
+        mut,
+        address = bid.owner
+    )]
+    pub bidder: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = submitter_token_account.owner == task_account.load()?.submitter @ ComputeError::InvalidTokenAccount,
+        constraint = submitter_token_account.mint == reward_mint.key() @ ComputeError::InvalidTokenAccount
+    )]
+    pub submitter_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = is_current(queue_prev) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_prev: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        constraint = is_current(queue_next) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_next: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        seeds = [b"pending_index".as_ref(), &[task_account.load()?.task_type]],
+        bump
+    )]
+    pub pending_index: Account<'info, PendingIndex>,
+    #[account(
+        mut,
+        seeds = [b"priority_index"],
+        bump
+    )]
+    pub priority_index: Account<'info, PriorityIndex>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    pub awarder: Signer<'info>,
+    #[account(address = task_account.load()?.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawBid<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        close = owner
+    )]
+    pub bid: Account<'info, Bid>,
+    /// CHECK: the bid's task, which may already be closed
+    #[account(address = bid.task)]
+    pub task_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct ExtendTaskDeadline<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    pub submitter: Signer<'info>,
+    #[account(mut)]
+    pub submitter_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(address = task_account.load()?.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct SplitTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    #[account(
+        mut,
+        constraint = is_current(queue_prev) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_prev: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        constraint = is_current(queue_next) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_next: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        constraint = is_current(queue_tail) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_tail: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        seeds = [b"pending_index".as_ref(), &[task_account.load()?.task_type]],
+        bump
+    )]
+    pub pending_index: Account<'info, PendingIndex>,
+    #[account(
+        mut,
+        seeds = [b"priority_index"],
+        bump
+    )]
+    pub priority_index: Account<'info, PriorityIndex>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct AggregateResults<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(pipeline_id: String, stages: Vec<PipelineStage>)]
+pub struct CreatePipeline<'info> {
+    #[account(
+        init,
+        payer = submitter,
+        space = 8 + PipelineAccount::LEN,
+        seeds = [b"pipeline", pipeline_id.as_bytes()],
+        bump
+    )]
+    pub pipeline: Account<'info, PipelineAccount>,
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = 8 + SubmitterProfile::LEN,
+        seeds = [b"submitter", submitter.key().as_ref()],
+        bump
+    )]
+    pub submitter_profile: Account<'info, SubmitterProfile>,
+    #[account(mut)]
+    pub submitter_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = is_current(queue_tail) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_tail: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        seeds = [b"pending_index".as_ref(), &[stages.first().map_or(0, |stage| stage.task_type as u8)]],
+        bump
+    )]
+    pub pending_index: Account<'info, PendingIndex>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    /// The network's reward mint or one of network_config.accepted_mints
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(pipeline_id: String)]
+pub struct AdvancePipeline<'info> {
+    #[account(
+        mut,
+        seeds = [b"pipeline", pipeline_id.as_bytes()],
+        bump
+    )]
+    pub pipeline: Account<'info, PipelineAccount>,
+    #[account(
+        constraint = Some(&current_task.key()) == pipeline.stages.get(pipeline.current_stage as usize) @ ComputeError::StageMismatch,
+        constraint = is_current(&current_task) @ ComputeError::AccountNeedsMigration
+    )]
+    pub current_task: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        constraint = Some(&next_task.key()) == pipeline.stages.get(pipeline.current_stage as usize + 1) @ ComputeError::StageMismatch,
+        constraint = is_current(&next_task) @ ComputeError::AccountNeedsMigration
+    )]
+    pub next_task: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        constraint = is_current(queue_tail) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_tail: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        seeds = [b"pending_index".as_ref(), &[next_task.load()?.task_type]],
+        bump
+    )]
+    pub pending_index: Account<'info, PendingIndex>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+}
+
+#[derive(Accounts)]
+#[instruction(recurring_id: String)]
+pub struct CreateRecurringTask<'info> {
+    #[account(
+        init,
+        payer = submitter,
+        space = 8 + RecurringTask::LEN,
+        seeds = [b"recurring", submitter.key().as_ref(), recurring_id.as_bytes()],
+        bump
+    )]
+    pub recurring_task: Account<'info, RecurringTask>,
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = 8 + SubmitterProfile::LEN,
+        seeds = [b"submitter", submitter.key().as_ref()],
+        bump
+    )]
+    pub submitter_profile: Account<'info, SubmitterProfile>,
+    #[account(mut)]
+    pub submitter_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    /// The network's reward mint or one of network_config.accepted_mints
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(recurring_id: String, task_id: String)]
+pub struct SpawnRecurringInstance<'info> {
+    #[account(
+        mut,
+        seeds = [b"recurring", recurring_task.submitter.as_ref(), recurring_id.as_bytes()],
+        bump
+    )]
+    pub recurring_task: Account<'info, RecurringTask>,
+    #[account(
+        init,
+        payer = keeper,
+        space = 8 + TaskAccount::LEN,
+        seeds = [b"task", task_id.as_bytes()],
+        bump
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    #[account(mut)]
+    pub keeper_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = is_current(queue_tail) @ ComputeError::AccountNeedsMigration
+    )]
+    pub queue_tail: Option<AccountLoader<'info, TaskAccount>>,
+    #[account(
+        mut,
+        seeds = [b"pending_index".as_ref(), &[recurring_task.task_type]],
+        bump
+    )]
+    pub pending_index: Account<'info, PendingIndex>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(address = recurring_task.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(recurring_id: String)]
+pub struct CancelRecurringTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"recurring", submitter.key().as_ref(), recurring_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        close = submitter
+    )]
+    pub recurring_task: Account<'info, RecurringTask>,
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"submitter", submitter.key().as_ref()],
+        bump
+    )]
+    pub submitter_profile: Account<'info, SubmitterProfile>,
+    #[account(
+        mut,
+        constraint = submitter_token_account.owner == submitter.key() @ ComputeError::InvalidTokenAccount,
+        constraint = submitter_token_account.mint == reward_mint.key() @ ComputeError::InvalidTokenAccount
+    )]
+    pub submitter_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(address = recurring_task.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct CloseTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        close = submitter,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    /// Lamport escrow of a SOL-paid task
+    #[account(
+        mut,
+        seeds = [b"sol_escrow", task_account.key().as_ref()],
+        bump
+    )]
+    pub sol_escrow: Option<SystemAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitArchiveTree<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ArchiveTree::LEN,
+        seeds = [b"archive_tree"],
+        bump
+    )]
+    pub archive_tree: Account<'info, ArchiveTree>,
+    /// CHECK: allocated by the caller for the compression program, which initializes it
+    #[account(
+        mut,
+        owner = SPL_ACCOUNT_COMPRESSION_ID
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        constraint = network_state.sensitive_authority() == authority.key() @ ComputeError::Unauthorized
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    /// CHECK: the SPL account compression program
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    /// CHECK: the SPL noop program
+    #[account(address = SPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct ArchiveTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        has_one = submitter,
+        close = submitter,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    /// CHECK: receives the task's rent and any SOL escrow left over; matched by has_one
+    #[account(mut)]
+    pub submitter: UncheckedAccount<'info>,
+    /// Lamport escrow of a SOL-paid task
+    #[account(
+        mut,
+        seeds = [b"sol_escrow", task_account.key().as_ref()],
+        bump
+    )]
+    pub sol_escrow: Option<SystemAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        mut,
+        seeds = [b"archive_tree"],
+        bump,
+        has_one = merkle_tree
+    )]
+    pub archive_tree: Account<'info, ArchiveTree>,
+    /// CHECK: the archive's concurrent merkle tree, matched by has_one
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: the SPL account compression program
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    /// CHECK: the SPL noop program
+    #[account(address = SPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeregisterDevice<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        close = owner,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        mut,
+        seeds = [b"epoch_state"],
+        bump
+    )]
+    pub epoch_state: Account<'info, EpochState>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDeviceSpecs<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    /// Only needed when the specs carry an attester signature
+    #[account(
+        seeds = [b"attester_registry"],
+        bump
+    )]
+    pub attester_registry: Option<Account<'info, AttesterRegistry>>,
+    /// CHECK: checked to be the instructions sysvar when the attestation is read
+    pub instructions: Option<UncheckedAccount<'info>>,
+    pub owner: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct AttestDeviceRegion<'info> {
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(benchmark_id: u16)]
+pub struct PublishBenchmark<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BenchmarkSpec::LEN,
+        seeds = [b"benchmark", benchmark_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub benchmark: Account<'info, BenchmarkSpec>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(benchmark_id: u16)]
+pub struct RetireBenchmark<'info> {
+    #[account(
+        mut,
+        seeds = [b"benchmark", benchmark_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub benchmark: Account<'info, BenchmarkSpec>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeAttesterRegistry<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AttesterRegistry::LEN,
+        seeds = [b"attester_registry"],
+        bump
+    )]
+    pub attester_registry: Account<'info, AttesterRegistry>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AttestDeviceIntegrity<'info> {
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        seeds = [b"attester_registry"],
+        bump,
+        constraint = attester_registry.attesters.contains(&attester.key()) @ ComputeError::UnknownAttester
+    )]
+    pub attester_registry: Account<'info, AttesterRegistry>,
+    pub attester: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ManageAttesters<'info> {
+    #[account(
+        mut,
+        seeds = [b"attester_registry"],
+        bump
+    )]
+    pub attester_registry: Account<'info, AttesterRegistry>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(benchmark_id: u16)]
+pub struct SubmitBenchmark<'info> {
+    #[account(
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        seeds = [b"benchmark", benchmark_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub benchmark: Account<'info, BenchmarkSpec>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + BenchmarkResult::LEN,
+        seeds = [b"benchmark_result", device_account.key().as_ref()],
+        bump
+    )]
+    pub benchmark_result: Account<'info, BenchmarkResult>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct AttestBenchmark<'info> {
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"benchmark_result", device_account.key().as_ref()],
+        bump
+    )]
+    pub benchmark_result: Account<'info, BenchmarkResult>,
+    #[account(
+        constraint = verifier_account.load()?.owner == verifier.key() @ ComputeError::VerifierNotOwner,
+        constraint = verifier_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = verifier_account.key() != device_account.key() @ ComputeError::SelfVerification,
+        constraint = verifier_account.load()?.owner != device_account.load()?.owner @ ComputeError::SelfVerification,
+        constraint = is_current(&verifier_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub verifier_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        seeds = [b"verifier_pool"],
+        bump
+    )]
+    pub verifier_pool: Account<'info, VerifierPool>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    pub verifier: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct IssueAvailabilityChallenge<'info> {
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = 8 + AvailabilityChallenge::LEN,
+        seeds = [b"availability", device_account.key().as_ref()],
+        bump
+    )]
+    pub availability_challenge: Account<'info, AvailabilityChallenge>,
+    /// CHECK: read raw for the most recent slot hash
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RespondAvailabilityChallenge<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"availability", device_account.key().as_ref()],
+        bump
+    )]
+    pub availability_challenge: Account<'info, AvailabilityChallenge>,
+    pub owner: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveAvailabilityChallenge<'info> {
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"availability", device_account.key().as_ref()],
+        bump
+    )]
+    pub availability_challenge: Account<'info, AvailabilityChallenge>,
+    #[account(
+        mut,
+        seeds = [b"epoch_state"],
+        bump
+    )]
+    pub epoch_state: Account<'info, EpochState>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+}
+
+#[derive(Accounts)]
+pub struct SetEncryptionKey<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    pub owner: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDeviceStatus<'info> {
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"epoch_state"],
+        bump
+    )]
+    pub epoch_state: Account<'info, EpochState>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    /// Delegated hot key, required when `authority` is not the device owner
+    #[account(
+        seeds = [b"agent", device_account.key().as_ref()],
+        bump
+    )]
+    pub agent_authority: Option<Account<'info, AgentAuthority>>,
+    pub authority: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct SetAgentAuthority<'info> {
+    #[account(
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + AgentAuthority::LEN,
+        seeds = [b"agent", device_account.key().as_ref()],
+        bump
+    )]
+    pub agent_authority: Account<'info, AgentAuthority>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct RotateAgentKey<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"agent", device_account.key().as_ref()],
+        bump
+    )]
+    pub agent_authority: Account<'info, AgentAuthority>,
+    pub owner: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAgentKey<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"agent", device_account.key().as_ref()],
+        bump,
+        close = owner
+    )]
+    pub agent_authority: Option<Account<'info, AgentAuthority>>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(submitter: Pubkey)]
+pub struct AddSubmitter<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + SubmitterProfile::LEN,
+        seeds = [b"submitter", submitter.as_ref()],
+        bump
+    )]
+    pub submitter_profile: Account<'info, SubmitterProfile>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(submitter: Pubkey)]
+pub struct RemoveSubmitter<'info> {
+    #[account(
+        mut,
+        seeds = [b"submitter", submitter.as_ref()],
+        bump
+    )]
+    pub submitter_profile: Account<'info, SubmitterProfile>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BanDevice<'info> {
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        mut,
+        seeds = [b"epoch_state"],
+        bump
+    )]
+    pub epoch_state: Account<'info, EpochState>,
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    #[account(address = network_state.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct UnbanDevice<'info> {
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitStakeReceiptMint<'info> {
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"stake_receipt"],
+        bump,
+        mint::decimals = reward_mint.decimals,
+        mint::authority = network_state,
+        mint::token_program = token_program
+    )]
+    pub receipt_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        constraint = network_state.sensitive_authority() == authority.key() @ ComputeError::Unauthorized
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(address = network_state.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakeTokens<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        mut,
+        seeds = [b"epoch_state"],
+        bump
+    )]
+    pub epoch_state: Account<'info, EpochState>,
+    #[account(address = stake_vault.mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    /// Receipt mint and the account receiving receipts; leave both out to stake without receipts
+    #[account(
+        mut,
+        seeds = [b"stake_receipt"],
+        bump
+    )]
+    pub receipt_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = receipt_mint.as_ref().is_some_and(|mint| mint.key() == receipt_token_account.mint) @ ComputeError::InvalidTokenAccount
+    )]
+    pub receipt_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + PendingWithdrawal::LEN,
+        seeds = [
+            b"withdrawal",
+            device_account.key().as_ref(),
+            &device_account.load()?.withdrawal_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub withdrawal: Account<'info, PendingWithdrawal>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        mut,
+        seeds = [b"epoch_state"],
+        bump
+    )]
+    pub epoch_state: Account<'info, EpochState>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    /// Needed when the unstaked amount reaches into receipt-backed stake; the owner's receipts are burned
+    #[account(
+        mut,
+        seeds = [b"stake_receipt"],
+        bump
+    )]
+    pub receipt_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = receipt_mint.as_ref().is_some_and(|mint| mint.key() == receipt_token_account.mint) @ ComputeError::InvalidTokenAccount
+    )]
+    pub receipt_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawUnstaked<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        close = owner
+    )]
+    pub withdrawal: Account<'info, PendingWithdrawal>,
+    /// CHECK: the withdrawal's device; may already be closed by deregister_device
+    #[account(address = withdrawal.device)]
+    pub device_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ ComputeError::InvalidTokenAccount
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(address = network_state.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    pub owner: Signer<'info>,
+    /// Any token account of the reward mint chosen by the owner
+    #[account(
+        mut,
+        constraint = destination.mint == network_state.reward_mint @ ComputeError::InvalidTokenAccount
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(address = network_state.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVesting<'info> {
+    #[account(
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + VestingAccount::LEN,
+        seeds = [b"vesting", device_account.key().as_ref()],
+        bump
+    )]
+    pub vesting_account: Account<'info, VestingAccount>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(
+        mut,
+        has_one = owner
+    )]
+    pub vesting_account: Account<'info, VestingAccount>,
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        constraint = destination.mint == network_state.reward_mint @ ComputeError::InvalidTokenAccount
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(address = network_state.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireTasks<'info> {
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = keeper_token_account.owner == thread.key() @ ComputeError::InvalidTokenAccount
+    )]
+    pub keeper_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// The automation thread (or any keeper) running the crank
+    pub thread: Signer<'info>,
+    #[account(address = network_state.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct DecayReputation<'info> {
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+}
+
+#[derive(Accounts)]
+pub struct AdvanceEpoch<'info> {
+    #[account(
+        mut,
+        seeds = [b"epoch_state"],
+        bump
+    )]
+    pub epoch_state: Account<'info, EpochState>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + EpochStats::LEN,
+        seeds = [b"epoch_stats", epoch_state.epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub epoch_stats: Account<'info, EpochStats>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimStakingRewards<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"epoch_state"],
+        bump
+    )]
+    pub epoch_state: Account<'info, EpochState>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = network_state.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAvailabilityReward<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"epoch_state"],
+        bump
+    )]
+    pub epoch_state: Account<'info, EpochState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = network_state.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct OpenLeaderboard<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Leaderboard::LEN,
+        seeds = [b"leaderboard", epoch_state.epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+    #[account(
+        seeds = [b"epoch_state"],
+        bump
+    )]
+    pub epoch_state: Account<'info, EpochState>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct DistributeLeaderboardPrizes<'info> {
+    #[account(
+        mut,
+        seeds = [b"leaderboard", epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+    #[account(
+        seeds = [b"epoch_state"],
+        bump
+    )]
+    pub epoch_state: Account<'info, EpochState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = network_state.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct InitBadgeTree<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BadgeTree::LEN,
+        seeds = [b"badge_tree"],
+        bump
+    )]
+    pub badge_tree: Account<'info, BadgeTree>,
+    /// CHECK: Bubblegum's config PDA for the tree, created by create_tree
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key()
+    )]
+    pub tree_authority: UncheckedAccount<'info>,
+    /// CHECK: allocated by the caller for the compression program, which initializes it
+    #[account(
+        mut,
+        owner = SPL_ACCOUNT_COMPRESSION_ID
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        constraint = network_state.sensitive_authority() == authority.key() @ ComputeError::Unauthorized
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    /// CHECK: the Metaplex Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    /// CHECK: the SPL account compression program
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    /// CHECK: the SPL noop program
+    #[account(address = SPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(kind: AchievementKind)]
+pub struct ClaimAchievement<'info> {
+    #[account(constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration)]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Achievement::LEN,
+        seeds = [b"achievement", device_account.key().as_ref(), &[kind as u8]],
+        bump
+    )]
+    pub achievement: Account<'info, Achievement>,
+    /// CHECK: the device owner, who receives the badge
+    #[account(address = device_account.load()?.owner)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"badge_tree"],
+        bump,
+        has_one = merkle_tree
+    )]
+    pub badge_tree: Account<'info, BadgeTree>,
+    /// CHECK: Bubblegum's config PDA for the tree
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key()
+    )]
+    pub tree_authority: UncheckedAccount<'info>,
+    /// CHECK: the badge tree, matched by has_one
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: the Metaplex Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    /// CHECK: the SPL account compression program
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    /// CHECK: the SPL noop program
+    #[account(address = SPL_NOOP_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct ChallengeResult<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        init,
+        payer = challenger,
+        space = 8 + Challenge::LEN,
+        seeds = [b"challenge", task_account.key().as_ref()],
+        bump
+    )]
+    pub challenge: Account<'info, Challenge>,
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    #[account(mut)]
+    pub challenger_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = stake_vault.mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct AcceptOptimisticResult<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct ResolveChallenge<'info> {
+    #[account(
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        seeds = [b"challenge", task_account.key().as_ref()],
+        bump,
+        has_one = challenger_token_account
+    )]
+    pub challenge: Account<'info, Challenge>,
+    #[account(mut)]
+    pub challenger_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// The challenger's profile, required when the challenger is the task's submitter
+    #[account(
+        mut,
+        seeds = [b"submitter", challenge.challenger.as_ref()],
+        bump
+    )]
+    pub challenger_profile: Option<Account<'info, SubmitterProfile>>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = network_state.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_type: TaskType)]
+pub struct InitializePendingIndex<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingIndex::LEN,
+        seeds = [b"pending_index".as_ref(), &[task_type as u8]],
+        bump
+    )]
+    pub pending_index: Account<'info, PendingIndex>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializePriorityIndex<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PriorityIndex::LEN,
+        seeds = [b"priority_index"],
+        bump
+    )]
+    pub priority_index: Account<'info, PriorityIndex>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVerifierPool<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + VerifierPool::LEN,
+        seeds = [b"verifier_pool"],
+        bump
+    )]
+    pub verifier_pool: Account<'info, VerifierPool>,
+    #[account(
+        seeds = [b"network_state"],
+        bump,
+        has_one = authority
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct JoinVerifierPool<'info> {
+    #[account(
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"verifier_pool"],
+        bump
+    )]
+    pub verifier_pool: Account<'info, VerifierPool>,
+    pub owner: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveVerifierPool<'info> {
+    #[account(
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"verifier_pool"],
+        bump
+    )]
+    pub verifier_pool: Account<'info, VerifierPool>,
+    pub owner: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct SelectCommittee<'info> {
+    #[account(
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        constraint = task_account.load()?.assigned_device == device_account.key() @ ComputeError::DeviceNotAssigned,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        seeds = [b"verifier_pool"],
+        bump
+    )]
+    pub verifier_pool: Account<'info, VerifierPool>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TaskCommittee::LEN,
+        seeds = [b"committee", task_account.key().as_ref(), &[task_account.load()?.verification_round]],
+        bump
+    )]
+    pub task_committee: Account<'info, TaskCommittee>,
+    /// CHECK: read raw for the most recent slot hash
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct CommitVerification<'info> {
+    #[account(
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        constraint = task_account.load()?.assigned_device == device_account.key() @ ComputeError::DeviceNotAssigned,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        constraint = verifier_account.load()?.owner == verifier.key() @ ComputeError::VerifierNotOwner,
+        constraint = verifier_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = verifier_account.key() != device_account.key() @ ComputeError::SelfVerification,
+        constraint = verifier_account.load()?.owner != device_account.load()?.owner @ ComputeError::SelfVerification,
+        constraint = is_current(&verifier_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub verifier_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        seeds = [b"committee", task_account.key().as_ref(), &[task_account.load()?.verification_round]],
+        bump,
+        constraint = task_committee.members.contains(&verifier_account.key()) @ ComputeError::NotCommitteeMember
+    )]
+    pub task_committee: Account<'info, TaskCommittee>,
+    #[account(
+        init,
+        payer = verifier,
+        space = 8 + VerificationRecord::LEN,
+        seeds = [b"verification", task_account.key().as_ref(), verifier_account.load()?.owner.as_ref()],
+        bump
+    )]
+    pub verification_record: Account<'info, VerificationRecord>,
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct RevealVerification<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        constraint = verifier_account.load()?.owner == verifier.key() @ ComputeError::VerifierNotOwner,
+        constraint = verifier_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&verifier_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub verifier_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"verification", task_account.key().as_ref(), verifier_account.load()?.owner.as_ref()],
+        bump,
+        constraint = verification_record.verifier == verifier_account.key() @ ComputeError::VerifierMismatch
+    )]
+    pub verification_record: Account<'info, VerificationRecord>,
+    pub verifier: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct ClaimShadowTask<'info> {
+    #[account(
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        constraint = task_account.load()?.assigned_device == device_account.key() @ ComputeError::DeviceNotAssigned,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        constraint = verifier_account.load()?.owner == verifier.key() @ ComputeError::VerifierNotOwner,
+        constraint = verifier_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = verifier_account.key() != device_account.key() @ ComputeError::SelfVerification,
+        constraint = verifier_account.load()?.owner != device_account.load()?.owner @ ComputeError::SelfVerification,
+        constraint = is_current(&verifier_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub verifier_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        seeds = [b"committee", task_account.key().as_ref(), &[task_account.load()?.verification_round]],
+        bump,
+        constraint = task_committee.members.contains(&verifier_account.key()) @ ComputeError::NotCommitteeMember
+    )]
+    pub task_committee: Account<'info, TaskCommittee>,
+    #[account(
+        init,
+        payer = verifier,
+        space = 8 + ShadowTask::LEN,
+        seeds = [
+            b"shadow",
+            task_account.key().as_ref(),
+            verifier_account.key().as_ref(),
+            &[task_account.load()?.verification_round]
+        ],
+        bump
+    )]
+    pub shadow_task: Account<'info, ShadowTask>,
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct RevealReExecution<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        constraint = verifier_account.load()?.owner == verifier.key() @ ComputeError::VerifierNotOwner,
+        constraint = verifier_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&verifier_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub verifier_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"verification", task_account.key().as_ref(), verifier_account.load()?.owner.as_ref()],
+        bump,
+        constraint = verification_record.verifier == verifier_account.key() @ ComputeError::VerifierMismatch
+    )]
+    pub verification_record: Account<'info, VerificationRecord>,
+    #[account(
+        mut,
+        seeds = [
+            b"shadow",
+            task_account.key().as_ref(),
+            verifier_account.key().as_ref(),
+            &[task_account.load()?.verification_round]
+        ],
+        bump
+    )]
+    pub shadow_task: Account<'info, ShadowTask>,
+    pub verifier: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct CommitBatch<'info> {
+    #[account(
+        mut,
+        constraint = verifier_account.load()?.owner == verifier.key() @ ComputeError::VerifierNotOwner,
+        constraint = verifier_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&verifier_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub verifier_account: AccountLoader<'info, DeviceAccount>,
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyBatch<'info> {
+    #[account(
+        mut,
+        constraint = verifier_account.load()?.owner == verifier.key() @ ComputeError::VerifierNotOwner,
+        constraint = verifier_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&verifier_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub verifier_account: AccountLoader<'info, DeviceAccount>,
+    pub verifier: Signer<'info>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct FinalizeVerification<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct AppealTask<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        has_one = owner,
+        constraint = device_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&device_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub device_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Appeal::LEN,
+        seeds = [b"appeal", task_account.key().as_ref()],
+        bump
+    )]
+    pub appeal: Account<'info, Appeal>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = stake_vault.mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct ResolveAppeal<'info> {
+    #[account(
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        seeds = [b"appeal", task_account.key().as_ref()],
+        bump,
+        has_one = owner_token_account
+    )]
+    pub appeal: Account<'info, Appeal>,
+    #[account(mut)]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = network_state.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct ClaimVerificationReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        constraint = verifier_account.load()?.owner == verifier.key() @ ComputeError::VerifierNotOwner,
+        constraint = verifier_account.load()?.holds_license(license_token_account.as_deref()) @ ComputeError::NoDeviceLicense,
+        constraint = is_current(&verifier_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub verifier_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"verification", task_account.key().as_ref(), verifier_account.load()?.owner.as_ref()],
+        bump,
+        constraint = verification_record.verifier == verifier_account.key() @ ComputeError::VerifierMismatch
+    )]
+    pub verification_record: Account<'info, VerificationRecord>,
+    #[account(
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault", reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub verifier_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Lamport escrow of a SOL-paid task
+    #[account(
+        mut,
+        seeds = [b"sol_escrow", task_account.key().as_ref()],
+        bump
+    )]
+    pub sol_escrow: Option<SystemAccount<'info>>,
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+    #[account(address = task_account.load()?.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+    /// Token account holding the license NFT, needed once the device has one
+    pub license_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: String)]
+pub struct SettleVerification<'info> {
+    #[account(
+        seeds = [b"task", task_id.as_bytes()],
+        bump,
+        constraint = is_current(&task_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub task_account: AccountLoader<'info, TaskAccount>,
+    #[account(
+        mut,
+        constraint = is_current(&verifier_account) @ ComputeError::AccountNeedsMigration
+    )]
+    pub verifier_account: AccountLoader<'info, DeviceAccount>,
+    #[account(
+        mut,
+        seeds = [b"verification", task_account.key().as_ref(), verifier_account.load()?.owner.as_ref()],
+        bump,
+        constraint = verification_record.verifier == verifier_account.key() @ ComputeError::VerifierMismatch
+    )]
+    pub verification_record: Account<'info, VerificationRecord>,
+    #[account(
+        mut,
+        seeds = [b"network_state"],
+        bump
+    )]
+    pub network_state: Account<'info, NetworkState>,
+    #[account(
+        seeds = [b"network_config"],
+        bump
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = network_state.reward_mint)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[account]
+pub struct NetworkState {
+    pub authority: Pubkey,
+    pub total_devices: u32,
+    pub total_tasks_completed: u64,
+    pub total_tokens_distributed: u64,
+    pub network_utilization: u8,
+    pub reward_mint: Pubkey,
+    pub reward_vault: Pubkey,
+    pub stake_vault: Pubkey,
+    pub active_devices: u32,
+    pub pending_tasks: u32,
+    /// Oldest and newest tasks in the FIFO queue of pending tasks
+    pub queue_head: Pubkey,
+    pub queue_tail: Pubkey,
+    pub queue_len: u32,
+    /// PDA that signs timelocked actions; once set, config changes and treasury withdrawals need it
+    pub timelock_authority: Pubkey,
+    /// Assignment-to-result time summed over tasks finished through complete_task, and their count
+    pub total_completion_seconds: u64,
+    pub timed_completions: u64,
+    /// Accepted assignments devices are currently working on
+    pub in_flight_assignments: u32,
+    /// Treasury tokens earmarked for burn_pending, and everything burned so far
+    pub pending_burn: u64,
+    pub total_burned: u64,
+    /// Fees from SOL-paid tasks, held as lamports on this account until withdraw_sol_fees
+    pub sol_fees: u64,
+    /// Stake receipts outstanding and the stake backing them; their ratio is the receipt swap rate,
+    /// which starts at 1:1 and only moves when slashing writes receipt-backed stake off
+    pub receipt_supply: u64,
+    pub receipted_stake: u64,
+}
+
+impl NetworkState {
+    pub const LEN: usize = 32 + 4 + 8 + 8 + 1 + 32 + 32 + 32 + 4 + 4 + 32 + 32 + 4 + 32 + 8 + 8 + 4 + 8 + 8 + 8 + 8 + 8;
+
+    /// Signer required for config changes and treasury withdrawals
+    pub fn sensitive_authority(&self) -> Pubkey {
+        if self.timelock_authority == Pubkey::default() { self.authority } else { self.timelock_authority }
+    }
+
+    /// Pending and running work per active device as a percentage, capped at 100
+    pub fn refresh_utilization(&mut self) {
+        let demand = self.pending_tasks as u64 + self.in_flight_assignments as u64;
+        self.network_utilization = if self.active_devices == 0 {
+            if demand > 0 { 100 } else { 0 }
+        } else {
+            (demand * 100 / self.active_devices as u64).min(100) as u8
+        };
+    }
+
+    /// Earmarks the configured share of fees or slashed stake that just reached the treasury for burning
+    pub fn accrue_burn(&mut self, amount: u64, config: &NetworkConfig) {
+        self.pending_burn += (amount as u128 * config.burn_bps as u128 / 10000) as u64;
+    }
+
+    /// Receipts minted for newly receipted stake, at the current swap rate
+    pub fn receipts_for_stake(&self, amount: u64) -> Result<u64> {
+        if self.receipt_supply == 0 {
+            return Ok(amount);
+        }
+        (amount as u128)
+            .checked_mul(self.receipt_supply as u128)
+            .and_then(|receipts| receipts.checked_div(self.receipted_stake as u128))
+            .and_then(|receipts| u64::try_from(receipts).ok())
+            .ok_or(ComputeError::MathOverflow.into())
+    }
+
+    /// Receipts that must be burned to release receipt-backed stake, rounded up
+    pub fn receipts_to_burn(&self, amount: u64) -> Result<u64> {
+        (amount as u128)
+            .checked_mul(self.receipt_supply as u128)
+            .and_then(|receipts| receipts.checked_add(self.receipted_stake as u128 - 1))
+            .and_then(|receipts| receipts.checked_div(self.receipted_stake as u128))
+            .and_then(|receipts| u64::try_from(receipts).ok())
+            .ok_or(ComputeError::MathOverflow.into())
+    }
+
+    pub fn assignment_started(&mut self) {
+        self.in_flight_assignments += 1;
+        self.refresh_utilization();
+    }
+
+    pub fn assignment_ended(&mut self) {
+        self.in_flight_assignments = self.in_flight_assignments.saturating_sub(1);
+        self.refresh_utilization();
+    }
+
+    pub fn task_opened(&mut self) {
+        self.pending_tasks += 1;
+        self.refresh_utilization();
+    }
+
+    pub fn task_taken(&mut self) {
+        self.pending_tasks = self.pending_tasks.saturating_sub(1);
+        self.refresh_utilization();
+    }
+
+    /// Appends a task to the tail of the pending queue; `tail` must be the current tail unless the queue is empty
+    pub fn enqueue(
+        &mut self,
+        task_key: Pubkey,
+        task: &mut TaskAccount,
+        tail: Option<&AccountLoader<TaskAccount>>,
+    ) -> Result<()> {
+        if task.queued != 0 {
+            return Ok(());
+        }
+        if self.queue_tail == Pubkey::default() {
+            self.queue_head = task_key;
+        } else {
+            let tail = tail
+                .filter(|tail| tail.key() == self.queue_tail)
+                .ok_or(ComputeError::InvalidQueueLink)?;
+            tail.load_mut()?.queue_next = task_key;
+        }
+        task.queue_prev = self.queue_tail;
+        task.queue_next = Pubkey::default();
+        task.queued = 1;
+        self.queue_tail = task_key;
+        self.queue_len += 1;
+        Ok(())
+    }
+
+    /// Unlinks a task from the pending queue; `prev` and `next` must be its current neighbours, where it has them
+    pub fn dequeue(
+        &mut self,
+        task_key: Pubkey,
+        task: &mut TaskAccount,
+        prev: Option<&AccountLoader<TaskAccount>>,
+        next: Option<&AccountLoader<TaskAccount>>,
+    ) -> Result<()> {
+        if task.queued == 0 {
+            return Ok(());
+        }
+        if task.queue_prev == Pubkey::default() {
+            require!(self.queue_head == task_key, ComputeError::InvalidQueueLink);
+            self.queue_head = task.queue_next;
+        } else {
+            let prev = prev
+                .filter(|prev| prev.key() == task.queue_prev)
+                .ok_or(ComputeError::InvalidQueueLink)?;
+            prev.load_mut()?.queue_next = task.queue_next;
+        }
+        if task.queue_next == Pubkey::default() {
+            require!(self.queue_tail == task_key, ComputeError::InvalidQueueLink);
+            self.queue_tail = task.queue_prev;
+        } else {
+            let next = next
+                .filter(|next| next.key() == task.queue_next)
+                .ok_or(ComputeError::InvalidQueueLink)?;
+            next.load_mut()?.queue_prev = task.queue_prev;
+        }
+        task.queue_prev = Pubkey::default();
+        task.queue_next = Pubkey::default();
+        task.queued = 0;
+        self.queue_len -= 1;
+        Ok(())
+    }
+
+    pub fn device_status_changed(&mut self, was_active: bool, is_active: bool) {
+        match (was_active, is_active) {
+            (false, true) => self.active_devices += 1,
+            (true, false) => self.active_devices = self.active_devices.saturating_sub(1),
+            _ => return,
+        }
+        self.refresh_utilization();
+    }
+}
+
+/// Staking yield accumulator. Each epoch's emission is spread over the stake of
+/// active devices by bumping `reward_per_share`; devices settle lazily on sync.
+#[account]
+pub struct EpochState {
+    pub epoch: u64,
+    pub epoch_started_at: i64,
+    /// Cumulative yield per staked token, scaled by YIELD_PRECISION
+    pub reward_per_share: u128,
+    /// Sum of the yield weights of all devices
+    pub total_weight: u64,
+    /// Yield distributed but not yet claimed, still held by the treasury
+    pub unclaimed_yield: u64,
+    /// Availability rewards still payable in the current epoch
+    pub availability_budget: u64,
+    /// NetworkState counters when the current epoch started, so EpochStats can report deltas
+    pub tasks_completed_at_start: u64,
+    pub tokens_distributed_at_start: u64,
+    pub completion_seconds_at_start: u64,
+    pub timed_completions_at_start: u64,
+    /// First epoch mint_epoch_emissions has not minted for yet
+    pub next_mint_epoch: u64,
+}
+
+impl EpochState {
+    pub const LEN: usize = 8 + 8 + 16 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8;
+}
+
+/// Canonical record of one finished epoch, written by advance_epoch
+#[account]
+pub struct EpochStats {
+    pub epoch: u64,
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub tasks_completed: u64,
+    pub tokens_distributed: u64,
+    /// Devices online when the epoch closed
+    pub active_devices: u32,
+    /// Mean assignment-to-result time of tasks finished through complete_task; 0 if none
+    pub avg_completion_seconds: u64,
+    /// Staking yield emitted at the close of the epoch
+    pub emission: u64,
+    pub total_weight: u64,
+    /// network_utilization when the epoch closed
+    pub network_utilization: u8,
+}
+
+impl EpochStats {
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 8 + 4 + 8 + 8 + 8 + 1;
+}
+
+/// A mint tasks may be paid in besides the network's reward mint. Its floors are set in the
+/// mint's own base units since its decimals and value differ from the network token's.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct AcceptedMint {
+    pub mint: Pubkey,
+    pub min_reward: u64,
+    pub submission_fee: u64,
+}
+
+impl AcceptedMint {
+    pub const LEN: usize = 32 + 8 + 8;
+}
+
+/// Economic parameters the network authority can tune without a program upgrade
+#[account]
+pub struct NetworkConfig {
+    /// Stake above which a device reaches Silver, Gold and Platinum
+    pub tier_thresholds: [u64; 3],
+    /// Cooldown between request_unstake and withdraw_unstaked (seconds)
+    pub unstake_period: i64,
+    /// Task deadline as a multiple of its estimated duration
+    pub expiry_multiplier: u8,
+    /// Reward bonus for finishing faster than estimated (basis points)
+    pub speed_bonus_bps: u64,
+    /// Share of revealed votes that must be valid to accept a result (basis points)
+    pub verification_threshold_bps: u64,
+    /// Share of each task reward paid to the treasury on completion (basis points)
+    pub protocol_fee_bps: u64,
+    /// Off-chain matching service allowed to assign tasks to devices
+    pub matcher: Pubkey,
+    /// Stake locked per task while it is being worked on, relative to its reward (basis points)
+    pub collateral_bps: u64,
+    /// Minimum length of a staking epoch (seconds)
+    pub epoch_duration: i64,
+    /// Treasury tokens shared among active stakers each epoch
+    pub epoch_emission: u64,
+    /// Seconds before vested task rewards start to unlock
+    pub vesting_cliff: i64,
+    /// Seconds over which vested task rewards unlock linearly; 0 pays rewards out directly
+    pub vesting_duration: i64,
+    /// Reward multiplier for Bronze, Silver, Gold and Platinum devices (basis points)
+    pub tier_reward_bps: [u64; 4],
+    /// Utilization (percent) above which new task rewards are surged
+    pub surge_threshold: u8,
+    /// Reward multiplier at full utilization (basis points)
+    pub max_surge_bps: u64,
+    /// Reputation weight of each TaskType (basis points)
+    pub task_type_reputation_bps: [u64; 5],
+    /// Extra reputation weight for tasks that need a GPU (basis points)
+    pub gpu_reputation_bps: u64,
+    /// Reward size that adds another 10% of reputation weight; 0 ignores the reward
+    pub reputation_reward_unit: u64,
+    /// Reward at or above which a task only goes to devices with high_value_min_reputation
+    pub high_value_reward: u64,
+    pub high_value_min_reputation: u16,
+    /// Submitters who lost this many disputes may no longer post tasks; 0 disables the limit
+    pub max_disputes_lost: u64,
+    /// Non-refundable fee charged to the treasury on every submission
+    pub submission_fee: u64,
+    /// Length of a submitter's rate limit window (slots)
+    pub rate_limit_window_slots: u64,
+    /// Submissions allowed per window; 0 disables the limit
+    pub max_submissions_per_window: u64,
+    /// Smallest reward accepted for each TaskType
+    pub min_reward_by_type: [u64; 5],
+    /// Total time a submitter may add to one assignment's deadline (seconds)
+    pub max_deadline_extension: i64,
+    /// Time after expires_at during which a late result is still accepted (seconds)
+    pub grace_period: i64,
+    /// Share of the reward left at the end of the grace period (basis points)
+    pub late_reward_floor_bps: u64,
+    /// Assignments a Bronze, Silver, Gold and Platinum device may work on at once
+    pub max_concurrent_by_tier: [u16; 4],
+    /// Verified benchmark score a device needs for Silver, Gold and Platinum
+    pub tier_benchmark_scores: [u64; 3],
+    /// Oracle that confirms content-addressed results are retrievable through prove_storage
+    pub storage_oracle: Pubkey,
+    /// Treasury tokens set aside each epoch for availability rewards
+    pub availability_emission: u64,
+    /// Availability reward for a Bronze device online for a whole epoch; scaled by tier_reward_bps
+    pub availability_reward: u64,
+    /// Permissioned mode: only submitters added through add_submitter may post tasks
+    pub submitter_allowlist_enabled: bool,
+    /// Reward tokens mint_epoch_emissions creates in epoch 0; 0 disables minting
+    pub initial_epoch_mint: u64,
+    /// Reduction of the minted amount from one epoch to the next (basis points)
+    pub emission_decay_bps: u64,
+    /// Share of protocol fees and slashed stake set aside for burning (basis points)
+    pub burn_bps: u64,
+    /// Other mints tasks may be paid in, managed with add_reward_mint; default entries are unused
+    pub accepted_mints: [AcceptedMint; MAX_REWARD_MINTS],
+    /// Pyth feed id of the network token's USD price; all zero disables USD-priced tasks
+    pub usd_price_feed_id: [u8; 32],
+    /// Oldest price, in seconds, a USD-priced task may settle at
+    pub max_price_age: i64,
+    /// Widest confidence interval accepted, relative to the price (basis points)
+    pub max_price_conf_bps: u64,
+    /// Treasury tokens shared out over each of an epoch's leaderboards; 0 disables prizes
+    pub leaderboard_prize: u64,
+}
+
+impl NetworkConfig {
+    pub const LEN: usize = 3 * 8 + 8 + 1 + 8 + 8 + 8 + 32 + 8 + 8 + 8 + 8 + 8 + 4 * 8 + 1 + 8 + 5 * 8 + 8 + 8 + 8 + 2 + 8 + 8 + 8 + 8 + 5 * 8 + 8 + 8 + 8 + 4 * 2 + 3 * 8 + 32 + 8 + 8 + 1 + 8 + 8 + 8 + MAX_REWARD_MINTS * AcceptedMint::LEN + 32 + 8 + 8 + 8;
+
+    /// Rules every config has to satisfy, checked after update_config and governance changes
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.tier_thresholds[0] < self.tier_thresholds[1] && self.tier_thresholds[1] < self.tier_thresholds[2],
+            ComputeError::InvalidConfig
+        );
+        require!(
+            self.tier_benchmark_scores[0] <= self.tier_benchmark_scores[1] && self.tier_benchmark_scores[1] <= self.tier_benchmark_scores[2],
+            ComputeError::InvalidConfig
+        );
+        require!(self.unstake_period >= 0 && self.expiry_multiplier >= 1, ComputeError::InvalidConfig);
+        require!(self.epoch_duration > 0 && self.max_deadline_extension >= 0, ComputeError::InvalidConfig);
+        require!(self.grace_period >= 0 && self.late_reward_floor_bps <= 10000, ComputeError::InvalidConfig);
+        require!(self.emission_decay_bps <= 10000 && self.burn_bps <= 10000, ComputeError::InvalidConfig);
+        require!(self.max_price_age >= 0 && self.max_price_conf_bps <= 10000, ComputeError::InvalidConfig);
+        require!(self.vesting_cliff >= 0 && self.vesting_cliff <= self.vesting_duration, ComputeError::InvalidConfig);
+        // Collateral can't exceed the reward and the speed bonus at most doubles it
+        require!(self.collateral_bps <= 10000 && self.speed_bonus_bps <= 10000, ComputeError::InvalidConfig);
+        require!(
+            self.gpu_reputation_bps <= MAX_REPUTATION_WEIGHT_BPS
+                && self.task_type_reputation_bps.iter().all(|&bps| bps <= MAX_REPUTATION_WEIGHT_BPS),
+            ComputeError::InvalidConfig
+        );
+        // A higher tier never earns less than a lower one
+        require!(self.tier_reward_bps.windows(2).all(|pair| pair[0] <= pair[1]), ComputeError::InvalidConfig);
+        // Every tier can run at least one task, and a higher tier never fewer than a lower one
+        require!(
+            self.max_concurrent_by_tier[0] >= 1 && self.max_concurrent_by_tier.windows(2).all(|pair| pair[0] <= pair[1]),
+            ComputeError::InvalidConfig
+        );
+        require!(self.surge_threshold < 100 && self.max_surge_bps >= 10000, ComputeError::InvalidConfig);
+        // A simple majority is the least a verification vote may require
+        require!(
+            self.verification_threshold_bps > 5000 && self.verification_threshold_bps <= 10000,
+            ComputeError::InvalidConfig
+        );
+        require!(self.protocol_fee_bps + VERIFICATION_FEE_BPS <= 10000, ComputeError::InvalidConfig);
+        Ok(())
+    }
+
+    pub fn accepted_mint(&self, mint: &Pubkey) -> Option<&AcceptedMint> {
+        self.accepted_mints.iter().find(|entry| entry.mint == *mint && *mint != Pubkey::default())
+    }
+
+    /// Minimum reward and submission fee for a task of `task_type` paid in `mint`, in that mint's units
+    pub fn reward_terms(&self, network_mint: &Pubkey, mint: &Pubkey, task_type: u8) -> Result<(u64, u64)> {
+        if mint == network_mint {
+            return Ok((self.min_reward_by_type[task_type as usize], self.submission_fee));
+        }
+        let entry = self.accepted_mint(mint).ok_or(ComputeError::RewardMintNotAccepted)?;
+        Ok((entry.min_reward, entry.submission_fee))
+    }
+
+    /// Scales linearly from 1x at the surge threshold up to max_surge_bps at 100% utilization
+    pub fn surge_multiplier_bps(&self, utilization: u8) -> u64 {
+        if utilization <= self.surge_threshold {
+            return 10000;
+        }
+        let over = (utilization.min(100) - self.surge_threshold) as u64;
+        let range = (100 - self.surge_threshold) as u64;
+        10000 + (self.max_surge_bps - 10000) * over / range
+    }
+
+    pub fn tier_multiplier_bps(&self, tier: u8) -> u64 {
+        self.tier_reward_bps.get(tier as usize).copied().unwrap_or(10000)
+    }
+
+    /// initial_epoch_mint reduced by emission_decay_bps once per epoch elapsed
+    pub fn scheduled_mint(&self, epoch: u64) -> u64 {
+        const SCALE: u128 = 1_000_000_000_000;
+        let mut factor = (10000 - self.emission_decay_bps.min(10000)) as u128 * SCALE / 10000;
+        let mut remaining = SCALE;
+        let mut exponent = epoch;
+        while exponent > 0 && remaining > 0 {
+            if exponent & 1 == 1 {
+                remaining = remaining * factor / SCALE;
+            }
+            factor = factor * factor / SCALE;
+            exponent >>= 1;
+        }
+        (self.initial_epoch_mint as u128 * remaining / SCALE) as u64
+    }
+
+    pub fn max_concurrent_for(&self, tier: u8) -> u16 {
+        self.max_concurrent_by_tier.get(tier as usize).copied().unwrap_or(1)
+    }
+
+    pub fn collateral_for(&self, reward_amount: u64) -> Result<u64> {
+        reward_amount
+            .checked_mul(self.collateral_bps)
+            .ok_or(ComputeError::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ComputeError::MathOverflow.into())
+    }
+}
+
+/// Zero-copy: fields are ordered by alignment so the layout has no implicit padding.
+/// Enums are stored as their `u8` discriminant and flags as 0/1.
+#[account(zero_copy)]
+pub struct DeviceAccount {
+    pub total_tokens_earned: u64,
+    pub last_active: i64,
+    pub staked_amount: u64,
+    pub stake_timestamp: i64,
+    pub specs_updated_at: i64,
+    pub verifier_locked_stake: u64,
+    pub locked_collateral: u64,
+    pub withdrawal_count: u64,
+    pub yield_weight: u64,
+    pub pending_yield: u64,
+    /// Task rewards credited but not yet withdrawn with claim_rewards
+    pub pending_rewards: u64,
+    /// Sum of assignment-to-result times over completed tasks
+    pub cumulative_completion_seconds: u64,
+    /// Time spent online, accrued whenever the device reports activity
+    pub uptime_seconds: u64,
+    /// Score of the device's last benchmark accepted by the verifiers; 0 if none
+    pub benchmark_score: u64,
+    pub benchmarked_at: i64,
+    /// End of the current integrity verdict's validity
+    pub integrity_expires_at: i64,
+    pub last_availability_challenge: i64,
+    /// Part of uptime_seconds already paid out by claim_availability_reward
+    pub uptime_claimed_seconds: u64,
+    pub availability_claimed_epoch: u64,
+    pub last_reputation_decay: i64,
+    /// When the authority banned the device pending investigation; 0 if not banned
+    pub banned_at: i64,
+    /// End of the latest voting window the device's stake voted in; unstaking waits until then
+    pub vote_locked_until: i64,
+    /// Bumped by rotate_agent_key and revoke_agent_key; agent keys from earlier epochs are rejected
+    pub agent_key_epoch: u64,
+    pub owner: Pubkey,
+    pub device_id: [u8; 32],
+    /// Hash of the last integrity verdict (e.g. a Play Integrity token) posted by an attester
+    pub integrity_verdict_hash: [u8; 32],
+    /// X25519 key submitters seal confidential task keys to; zero if not set
+    pub encryption_pubkey: [u8; 32],
+    /// Last seen EpochState::reward_per_share, stored as little-endian u128 bytes
+    pub yield_per_share_paid: [u8; 16],
+    pub specs: DeviceSpecs,
+    pub total_tasks_completed: u32,
+    pub total_verifications: u32,
+    pub tasks_failed: u32,
+    pub tasks_expired: u32,
+    pub reputation_score: u16,
+    pub active_assignments: u16,
+    /// CPU cores and RAM held by the device's running assignments
+    pub reserved_cpu_cores: u16,
+    pub reserved_ram_gb: u16,
+    pub is_active: u8,
+    pub current_load: u8,
+    pub tier: u8,
+    /// Power and thermal state as last reported through update_device_status
+    pub battery_percent: u8,
+    pub is_charging: u8,
+    pub thermal_state: u8,
+    /// Whether the last integrity verdict found the app running on genuine hardware
+    pub integrity_verified: u8,
+    /// Availability challenges missed in a row
+    pub availability_failures: u8,
+    /// Layout version, see Versioned
+    pub version: u8,
+    pub padding: [u8; 7],
+    /// Mint of the operator license NFT; whoever holds it can claim the device. Default for devices
+    /// registered before every device was minted one.
+    pub license_mint: Pubkey,
+    /// Part of the stake that stake receipts were minted against
+    pub receipted_stake: u64,
+    /// Epoch the leaderboard tallies below belong to
+    pub leaderboard_epoch: u64,
+    pub epoch_tasks_completed: u64,
+    /// Network tokens earned from tasks during leaderboard_epoch
+    pub epoch_tokens_earned: u64,
+    /// Pool the device operates in; default if none
+    pub pool: Pubkey,
+    /// Stake last counted towards the pool's total_stake
+    pub pool_synced_stake: u64,
+}
+
+impl Versioned for DeviceAccount {
+    const VERSION: u8 = 5;
+    
+    fn version(&self) -> u8 {
+        self.version
+    }
+    
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
+
+impl DeviceAccount {
+    pub const LEN: usize = std::mem::size_of::<DeviceAccount>();
+
+    /// Whether enough unreserved cores and memory remain to run the task alongside current assignments
+    pub fn has_capacity(&self, requirements: &ComputeRequirements) -> bool {
+        self.reserved_cpu_cores + requirements.cpu_cores_required as u16 <= self.specs.cpu_cores as u16 &&
+        self.reserved_ram_gb + requirements.ram_gb_required as u16 <= self.specs.ram_gb as u16
+    }
+
+    /// Whether the owner still holds the license NFT. Checked wherever the owner signs for the
+    /// device, so selling the license cuts the seller off at once until the buyer claims it.
+    /// Devices registered before licenses were minted have none to hold.
+    pub fn holds_license(&self, license_token_account: Option<&TokenAccount>) -> bool {
+        self.license_mint == Pubkey::default() || license_token_account.is_some_and(|account| {
+            account.mint == self.license_mint && account.owner == self.owner && account.amount == 1
+        })
+    }
+
+    /// A device only reaches a tier once both its stake and its verified benchmark score qualify
+    pub fn eligible_tier(&self, config: &NetworkConfig) -> DeviceTier {
+        let by_stake = DeviceTier::from_stake(self.staked_amount, config);
+        let by_benchmark = DeviceTier::from_benchmark(self.benchmark_score, config);
+        if by_benchmark < by_stake { by_benchmark } else { by_stake }
+    }
+
+    pub fn is_banned(&self) -> bool {
+        self.banned_at != 0
+    }
+
+    pub fn has_valid_integrity(&self, now: i64) -> bool {
+        self.integrity_verified != 0 && now < self.integrity_expires_at
+    }
+
+    pub fn has_free_slot(&self, config: &NetworkConfig) -> bool {
+        self.active_assignments < config.max_concurrent_for(self.tier)
+    }
+
+    /// Takes one of the tier's concurrent slots and reserves the task's cores and memory
+    pub fn start_assignment(&mut self, requirements: &ComputeRequirements, config: &NetworkConfig) -> Result<()> {
+        require!(self.has_free_slot(config), ComputeError::TooManyConcurrentTasks);
+        self.active_assignments += 1;
+        self.reserve(requirements)
+    }
+
+    pub fn reserve(&mut self, requirements: &ComputeRequirements) -> Result<()> {
+        require!(self.has_capacity(requirements), ComputeError::DeviceOversubscribed);
+        self.reserved_cpu_cores += requirements.cpu_cores_required as u16;
+        self.reserved_ram_gb += requirements.ram_gb_required as u16;
+        Ok(())
+    }
+
+    pub fn release(&mut self, requirements: &ComputeRequirements) {
+        self.reserved_cpu_cores = self.reserved_cpu_cores.saturating_sub(requirements.cpu_cores_required as u16);
+        self.reserved_ram_gb = self.reserved_ram_gb.saturating_sub(requirements.ram_gb_required as u16);
+    }
+
+    /// Stake not currently bonded to an open verification or held as task collateral
+    pub fn available_stake(&self) -> u64 {
+        self.staked_amount
+            .saturating_sub(self.verifier_locked_stake)
+            .saturating_sub(self.locked_collateral)
+    }
+
+    /// Bonds part of the free stake to a task until its result is settled
+    pub fn lock_collateral(&mut self, amount: u64) -> Result<()> {
+        require!(self.available_stake() >= amount, ComputeError::InsufficientStake);
+        self.locked_collateral += amount;
+        Ok(())
+    }
+
+    pub fn release_collateral(&mut self, amount: u64) {
+        self.locked_collateral = self.locked_collateral.saturating_sub(amount);
+    }
+
+    /// Counts a completed task towards the device's tallies for `epoch`, starting over in a new epoch
+    pub fn record_epoch_completion(&mut self, epoch: u64, tokens_earned: u64) {
+        if self.leaderboard_epoch != epoch {
+            self.leaderboard_epoch = epoch;
+            self.epoch_tasks_completed = 0;
+            self.epoch_tokens_earned = 0;
+        }
+        self.epoch_tasks_completed += 1;
+        self.epoch_tokens_earned += tokens_earned;
+    }
+
+    /// Brings the pool's total stake up to date with the device's current stake
+    pub fn sync_pool_stake(&mut self, pool: &mut Pool) {
+        pool.total_stake = (pool.total_stake + self.staked_amount).saturating_sub(self.pool_synced_stake);
+        self.pool_synced_stake = self.staked_amount;
+    }
+
+    /// Takes forfeited or slashed stake out of the device. The receipt-backed share of the loss is
+    /// written off the network's receipt backing, so every receipt holder takes it through a lower
+    /// swap rate rather than leaving receipts the vault can no longer cover.
+    pub fn slash_stake(&mut self, amount: u64, network_state: &mut NetworkState) {
+        let receipted = if self.staked_amount == 0 {
+            0
+        } else {
+            (amount as u128 * self.receipted_stake as u128 / self.staked_amount as u128) as u64
+        };
+        self.staked_amount -= amount;
+        self.receipted_stake -= receipted;
+        network_state.receipted_stake = network_state.receipted_stake.saturating_sub(receipted);
+    }
+
+    /// Credits the yield accrued since the last sync, then re-weights the device:
+    /// it earns on its full stake while online and nothing while offline.
+    pub fn sync_yield(&mut self, epoch_state: &mut EpochState) -> Result<()> {
+        // Slashing lowers stake without a sync, so never credit more than what is still staked
+        let earning_weight = self.yield_weight.min(self.staked_amount) as u128;
+        let paid = u128::from_le_bytes(self.yield_per_share_paid);
+        let accrued = earning_weight
+            .checked_mul(epoch_state.reward_per_share - paid)
+            .ok_or(ComputeError::MathOverflow)?
+            / YIELD_PRECISION;
+        self.pending_yield = self.pending_yield
+            .checked_add(u64::try_from(accrued).map_err(|_| ComputeError::MathOverflow)?)
+            .ok_or(ComputeError::MathOverflow)?;
+        self.yield_per_share_paid = epoch_state.reward_per_share.to_le_bytes();
+        
+        let weight = if self.is_active != 0 { self.staked_amount } else { 0 };
+        epoch_state.total_weight = epoch_state.total_weight
+            .saturating_sub(self.yield_weight)
+            .saturating_add(weight);
+        self.yield_weight = weight;
+        Ok(())
+    }
+
+    /// Accepts the owner, or the device's unexpired agent key when it holds `permission`, as long as
+    /// the owner still holds the license NFT
+    pub fn check_authority(
+        &self,
+        signer: &Pubkey,
+        agent_authority: Option<&AgentAuthority>,
+        license_token_account: Option<&TokenAccount>,
+        permission: u8,
+        now: i64,
+    ) -> Result<()> {
+        require!(self.holds_license(license_token_account), ComputeError::NoDeviceLicense);
+        if *signer == self.owner {
+            return Ok(());
+        }
+        let agent_authority = agent_authority.ok_or(ComputeError::Unauthorized)?;
+        require!(agent_authority.agent == *signer, ComputeError::Unauthorized);
+        require!(agent_authority.key_epoch == self.agent_key_epoch, ComputeError::StaleAgentKey);
+        require!(agent_authority.permissions & permission != 0, ComputeError::AgentNotPermitted);
+        require!(now < agent_authority.expires_at, ComputeError::AgentExpired);
+        Ok(())
+    }
+
+    /// Records activity at `now`, crediting the time since the last report as uptime while online
+    pub fn touch(&mut self, now: i64) {
+        if self.is_active != 0 {
+            self.uptime_seconds += (now - self.last_active).max(0) as u64;
+        }
+        self.last_active = now;
+    }
+
+    pub fn device_id_str(&self) -> String {
+        from_fixed_bytes(&self.device_id)
+    }
+}
+
+/// Zero-copy: fields are ordered by alignment so the layout has no implicit padding.
+/// Enums are stored as their `u8` discriminant, flags as 0/1 and an unassigned device as the default key.
+#[account(zero_copy)]
+pub struct TaskAccount {
+    pub reward_amount: u64,
+    pub created_at: i64,
+    pub assigned_at: i64,
+    pub started_at: i64,
+    pub completed_at: i64,
+    pub expires_at: i64,
+    pub last_heartbeat: i64,
+    pub commit_deadline: i64,
+    pub reveal_deadline: i64,
+    pub challenge_deadline: i64,
+    pub verification_finalized_at: i64,
+    pub verification_pool: u64,
+    pub collateral: u64,
+    /// Time added to the current assignment's deadline by extend_task_deadline
+    pub deadline_extended: i64,
+    /// End of the bid window while the task is up for auction, 0 for fixed-price tasks
+    pub bid_deadline: i64,
+    /// When the storage oracle confirmed the result is retrievable, 0 until then
+    pub storage_proved_at: i64,
+    pub best_bid: u64,
+    /// Extra payment escrowed by the submitter for faster matching; doubles as the task's priority
+    pub priority_fee: u64,
+    pub result_size_bytes: u64,
+    /// Reward in USD cents, converted to tokens at completion; 0 if the reward is priced in tokens
+    pub usd_reward_cents: u64,
+    pub submitter: Pubkey,
+    pub assigned_device: Pubkey,
+    /// Lowest bid placed so far while the task is up for auction
+    pub best_bid_device: Pubkey,
+    /// Neighbours in the FIFO queue of pending tasks while `queued` is set
+    pub queue_prev: Pubkey,
+    pub queue_next: Pubkey,
+    /// Pipeline this task is a stage of, default if none
+    pub pipeline: Pubkey,
+    /// Task this one was split from by split_task, default if none
+    pub parent_task: Pubkey,
+    pub task_id: [u8; 32],
+    pub result_hash: [u8; 64],
+    /// Result hash of the previous pipeline stage, which this stage takes as its input
+    pub input_hash: [u8; 64],
+    /// Where the output is stored (e.g. ipfs://, ar://), with its MIME type
+    pub result_uri: [u8; MAX_RESULT_URI_LEN],
+    pub result_mime: [u8; MAX_RESULT_MIME_LEN],
+    pub checkpoint_hash: [u8; 32],
+    /// Merkle root of the devices allowed to run the task (leaves are hash(device)); zero for public tasks
+    pub allowlist_root: [u8; 32],
+    /// Submitter's X25519 key used to seal encrypted_key; zero for public payloads
+    pub submitter_pubkey: [u8; 32],
+    /// Payload key sealed to key_device's encryption_pubkey by post_task_key
+    pub encrypted_key: [u8; ENCRYPTED_KEY_LEN],
+    pub key_device: Pubkey,
+    /// Finalized TaskData account holding the task's input; default if the input is off-chain
+    pub task_data: Pubkey,
+    /// Program that checks ZkProof results, and the verifying key account it reads
+    pub zk_verifier: Pubkey,
+    pub zk_verifying_key: Pubkey,
+    /// Mint the reward is escrowed and paid out in; the native mint for SOL-paid tasks
+    pub reward_mint: Pubkey,
+    pub compute_requirements: ComputeRequirements,
+    pub min_reputation: u16,
+    pub task_type: u8,
+    pub status: u8,
+    pub verifications: u8,
+    pub valid_verifications: u8,
+    pub is_verified: u8,
+    pub reward_released: u8,
+    pub progress: u8,
+    pub retry_count: u8,
+    pub assignment_accepted: u8,
+    pub verification_mode: u8,
+    pub challenged: u8,
+    pub verification_round: u8,
+    pub appealed: u8,
+    pub redundancy: u8,
+    pub assigned_count: u8,
+    pub results_submitted: u8,
+    pub agreeing_count: u8,
+    pub queued: u8,
+    pub subtask_count: u8,
+    pub subtask_index: u8,
+    pub pipeline_stage: u8,
+    /// Submitted straight to a device with submit_direct_task
+    pub direct: u8,
+    /// RewardKind the reward is escrowed as
+    pub reward_kind: u8,
+    /// Layout version, see Versioned
+    pub version: u8,
+    /// Verifier and replica shares paid out so far, which must all be claimed before the task closes
+    pub verification_claims: u8,
+    pub replica_claims: u8,
+    pub padding: [u8; 4],
+    /// Pool whose operator routes this task to one of its members; default for open tasks
+    pub target_pool: Pubkey,
+}
+
+impl Versioned for TaskAccount {
+    const VERSION: u8 = 2;
+    
+    fn version(&self) -> u8 {
+        self.version
+    }
+    
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
+
+impl TaskAccount {
+    pub const LEN: usize = std::mem::size_of::<TaskAccount>();
+
+    /// Fills in a freshly created task once the submission passes the network's terms and the
+    /// submitter's limits, and records it on their profile. Payment and listing are the caller's.
+    #[allow(clippy::too_many_arguments)]
+    pub fn init_submission(
+        &mut self,
+        task_id: &str,
+        submission: &Submission,
+        submitter_profile: &mut SubmitterProfile,
+        network_state: &NetworkState,
+        network_config: &NetworkConfig,
+        pending_index: &PendingIndex,
+        clock: &Clock,
+    ) -> Result<SubmissionTerms> {
+        check_task_id(task_id)?;
+        require!((1..=MAX_REDUNDANCY).contains(&submission.redundancy), ComputeError::InvalidRedundancy);
+        require!(pending_index.task_type == submission.task_type as u8, ComputeError::PendingIndexMismatch);
+        require!(
+            submission.verification_mode != VerificationMode::ZkProof || submission.task_type == TaskType::MLInference,
+            ComputeError::ZkNotSupported
+        );
+        let (min_reward, submission_fee) = network_config.reward_terms(
+            &network_state.reward_mint,
+            &submission.reward_mint,
+            submission.task_type as u8,
+        )?;
+        require!(submission.reward_amount > 0 && submission.reward_amount >= min_reward, ComputeError::RewardTooLow);
+        // SOL rewards are governed by the native mint's accepted_mints entry and never touch its vault
+        if submission.reward_kind == RewardKind::Sol {
+            require!(submission.reward_mint == native_mint::ID, ComputeError::RewardMintNotAccepted);
+            require!(submission.redundancy == 1, ComputeError::SolRewardUnsupported);
+        }
+        
+        if submitter_profile.submitter == Pubkey::default() {
+            submitter_profile.submitter = submission.submitter;
+            submitter_profile.created_at = clock.unix_timestamp;
+        }
+        submitter_profile.check_allowed(network_config)?;
+        let max_disputes_lost = network_config.max_disputes_lost;
+        require!(
+            max_disputes_lost == 0 || submitter_profile.disputes_lost < max_disputes_lost,
+            ComputeError::SubmitterRestricted
+        );
+        submitter_profile.record_submission(clock.slot, network_config)?;
+        
+        // While the network is congested the submitter pays a surge on top of the offered reward
+        let surge_bps = network_config.surge_multiplier_bps(network_state.network_utilization);
+        let reward_amount = submission.reward_amount
+            .checked_mul(surge_bps)
+            .ok_or(ComputeError::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ComputeError::MathOverflow)?;
+        let total = reward_amount
+            .checked_add(submission.priority_fee)
+            .ok_or(ComputeError::MathOverflow)?;
+        submitter_profile.total_rewards_paid = submitter_profile.total_rewards_paid
+            .checked_add(total)
+            .ok_or(ComputeError::MathOverflow)?;
+        
+        // The account was zeroed on creation, so only non-zero fields are filled in
+        self.submitter = submission.submitter;
+        self.task_id = to_fixed_bytes(task_id)?;
+        self.task_type = submission.task_type as u8;
+        self.compute_requirements = submission.compute_requirements;
+        self.reward_amount = reward_amount;
+        self.status = TaskStatus::Pending as u8;
+        self.created_at = clock.unix_timestamp;
+        self.verification_mode = submission.verification_mode as u8;
+        self.redundancy = submission.redundancy;
+        self.min_reputation = submission.min_reputation;
+        self.priority_fee = submission.priority_fee;
+        self.reward_mint = submission.reward_mint;
+        self.reward_kind = submission.reward_kind as u8;
+        self.version = TaskAccount::VERSION;
+        
+        Ok(SubmissionTerms {
+            reward_amount,
+            total,
+            submission_fee,
+            surge_bps,
+        })
+    }
+
+    pub fn task_id_str(&self) -> String {
+        from_fixed_bytes(&self.task_id)
+    }
+
+    pub fn result_hash_str(&self) -> String {
+        from_fixed_bytes(&self.result_hash)
+    }
+
+    pub fn result_uri_str(&self) -> String {
+        from_fixed_bytes(&self.result_uri)
+    }
+
+    /// The assigned device, if any
+    pub fn assigned_device(&self) -> Option<Pubkey> {
+        (self.assigned_device != Pubkey::default()).then_some(self.assigned_device)
+    }
+
+    /// Minimum device tier allowed to run this task type
+    pub fn min_tier(&self) -> DeviceTier {
+        match self.task_type {
+            t if t == TaskType::MLInference as u8 => DeviceTier::Silver,
+            t if t == TaskType::ImageProcessing as u8 => DeviceTier::Silver,
+            t if t == TaskType::VideoTranscoding as u8 => DeviceTier::Gold,
+            _ => DeviceTier::Bronze,
+        }
+    }
+
+    /// Checks that the task is open and the device meets its requirements
+    pub fn check_eligibility(&self, device: &DeviceAccount, config: &NetworkConfig, now: i64) -> Result<()> {
+        require!(self.is_open(now), ComputeError::TaskNotPending);
+        // Tasks up for auction only go out through award_task
+        require!(self.bid_deadline == 0, ComputeError::BiddingTask);
+        require!(self.direct == 0, ComputeError::DirectTask);
+        require!(!self.is_pool_task(), ComputeError::PoolTask);
+        self.check_device(device, config)
+    }
+
+    /// Private tasks only go to devices proven to be in the submitter's allowlist
+    pub fn check_allowlist(&self, device: &Pubkey, proof: &[[u8; 32]]) -> Result<()> {
+        if self.allowlist_root == [0; 32] {
+            return Ok(());
+        }
+        require!(
+            proof.len() <= MAX_ALLOWLIST_PROOF_LEN &&
+            verify_merkle_proof(hashv(&[device.as_ref()]).to_bytes(), proof, &self.allowlist_root),
+            ComputeError::DeviceNotAllowed
+        );
+        Ok(())
+    }
+
+    /// Whether the device can run this task at all, regardless of the task's state
+    pub fn check_device(&self, device: &DeviceAccount, config: &NetworkConfig) -> Result<()> {
+        require!(!device.is_banned(), ComputeError::DeviceBanned);
+        require!(device.is_active != 0, ComputeError::DeviceNotActive);
+        
+        // Check device capabilities match task requirements
+        let requirements = &self.compute_requirements;
+        let specs = &device.specs;
+        require!(
+            specs.cpu_cores >= requirements.cpu_cores_required &&
+            specs.ram_gb >= requirements.ram_gb_required &&
+            specs.storage_gb >= requirements.storage_gb_required &&
+            (requirements.gpu_required == 0 || specs.gpu_available != 0),
+            ComputeError::InsufficientCapabilities
+        );
+        
+        // GPU work needs enough memory, the right numeric formats and, if pinned, the exact model
+        require!(
+            specs.gpu_vram_gb >= requirements.min_gpu_vram_gb &&
+            specs.gpu_precisions & requirements.required_gpu_precisions == requirements.required_gpu_precisions &&
+            (requirements.required_gpu_model == 0 || specs.gpu_model == requirements.required_gpu_model),
+            ComputeError::InsufficientGpu
+        );
+        
+        require!(device.has_free_slot(config), ComputeError::TooManyConcurrentTasks);
+        require!(device.has_capacity(requirements), ComputeError::DeviceOversubscribed);
+        
+        // Large inputs and outputs need a connection that can move them within the task's time budget
+        require!(specs.network_speed >= requirements.min_network_speed, ComputeError::InsufficientBandwidth);
+        let data_megabits = (requirements.input_size_mb as u64 + requirements.output_size_mb as u64) * 8;
+        if data_megabits > 0 {
+            require!(
+                specs.network_speed > 0 &&
+                data_megabits / specs.network_speed as u64 <= requirements.estimated_duration as u64,
+                ComputeError::InsufficientBandwidth
+            );
+        }
+        
+        // Mobile devices shouldn't start work that would drain or overheat them
+        require!(device.thermal_state < ThermalState::Critical as u8, ComputeError::DeviceOverheated);
+        require!(
+            device.is_charging != 0 ||
+            (requirements.charging_required == 0 && device.battery_percent >= requirements.min_battery_percent),
+            ComputeError::InsufficientBattery
+        );
+        
+        // Platform-specific binaries only run on matching platforms and architectures
+        require!(
+            ComputeRequirements::mask_allows(requirements.allowed_platforms, specs.platform) &&
+            ComputeRequirements::mask_allows(requirements.allowed_archs, specs.arch),
+            ComputeError::IncompatiblePlatform
+        );
+        
+        require!(
+            requirements.specs_attestation_required == 0 || specs.specs_attested != 0,
+            ComputeError::SpecsNotAttested
+        );
+        if requirements.integrity_required != 0 {
+            require!(device.has_valid_integrity(Clock::get()?.unix_timestamp), ComputeError::IntegrityNotVerified);
+        }
+        
+        // Region-restricted tasks only run on devices in that region
+        if requirements.required_region != 0 {
+            require!(
+                specs.region == requirements.required_region &&
+                (requirements.region_attestation_required == 0 || specs.region_attested != 0),
+                ComputeError::RegionMismatch
+            );
+        }
+        
+        // Check device tier for task eligibility
+        require!(device.tier >= self.min_tier() as u8, ComputeError::InsufficientTier);
+        
+        // Unproven devices start on smaller work
+        let mut min_reputation = self.min_reputation;
+        if self.reward_amount >= config.high_value_reward {
+            min_reputation = min_reputation.max(config.high_value_min_reputation);
+        }
+        require!(device.reputation_score >= min_reputation, ComputeError::ReputationTooLow);
+        Ok(())
+    }
+
+    /// Token task paid in the network mint, the mint stake and collateral are held in
+    pub fn pays_in_network_mint(&self, network_mint: &Pubkey) -> bool {
+        self.reward_kind == RewardKind::Token as u8 && self.reward_mint == *network_mint
+    }
+
+    /// Reserved for a pool, so it is routed by the pool's operator instead of being listed
+    pub fn is_pool_task(&self) -> bool {
+        self.target_pool != Pubkey::default()
+    }
+
+    /// Pending, or assigned by a third party and not accepted within the acceptance window
+    pub fn is_open(&self, now: i64) -> bool {
+        if self.status == TaskStatus::Pending as u8 {
+            true
+        } else if self.status == TaskStatus::Assigned as u8 {
+            self.assignment_accepted == 0 && now > self.assigned_at + ACCEPT_TIMEOUT
+        } else {
+            false
+        }
+    }
+
+    /// An in-progress task whose device has stopped sending heartbeats
+    pub fn is_stalled(&self, now: i64) -> bool {
+        self.status == TaskStatus::InProgress as u8 && now > self.last_heartbeat + HEARTBEAT_TIMEOUT
+    }
+
+    /// An accepted assignment that expire_task would fail right now
+    pub fn is_expired(&self, now: i64, config: &NetworkConfig) -> bool {
+        [TaskStatus::Assigned as u8, TaskStatus::InProgress as u8].contains(&self.status) &&
+        self.assignment_accepted != 0 &&
+        (now > self.expires_at + config.grace_period || self.is_stalled(now))
+    }
+
+    /// Clears all assignment state and puts the task back in the pending pool
+    pub fn reset_assignment(&mut self) {
+        self.status = TaskStatus::Pending as u8;
+        self.assigned_device = Pubkey::default();
+        self.assignment_accepted = 0;
+        self.assigned_at = 0;
+        self.started_at = 0;
+        self.expires_at = 0;
+        self.deadline_extended = 0;
+        self.last_heartbeat = 0;
+        self.progress = 0;
+        self.checkpoint_hash = [0; 32];
+    }
+
+    /// Committee tasks always go to a vote; optimistic ones only once challenged
+    /// How much this task's difficulty scales reputation changes (basis points)
+    pub fn reputation_weight_bps(&self, config: &NetworkConfig) -> u64 {
+        let mut weight = config.task_type_reputation_bps
+            .get(self.task_type as usize)
+            .copied()
+            .unwrap_or(10000);
+        if self.compute_requirements.gpu_required != 0 {
+            weight += config.gpu_reputation_bps;
+        }
+        if let Some(units) = self.reward_amount.checked_div(config.reputation_reward_unit) {
+            weight += units.min(10) * 1000;
+        }
+        weight.min(MAX_REPUTATION_WEIGHT_BPS)
+    }
+
+    /// Scales a base reputation change by the task's difficulty; never rounds below one point
+    pub fn scaled_reputation(&self, base: u16, config: &NetworkConfig) -> u16 {
+        let scaled = base as u64 * self.reputation_weight_bps(config) / 10000;
+        scaled.clamp(1, u16::MAX as u64) as u16
+    }
+
+    /// Share of the reward still earned at `now`: full until expires_at, then decaying
+    /// linearly to late_reward_floor_bps over the grace period (basis points)
+    pub fn late_reward_bps(&self, now: i64, config: &NetworkConfig) -> u64 {
+        let late_by = now - self.expires_at;
+        if late_by <= 0 || config.grace_period == 0 {
+            return 10000;
+        }
+        let decay = (10000 - config.late_reward_floor_bps) * late_by.min(config.grace_period) as u64
+            / config.grace_period as u64;
+        10000 - decay
+    }
+
+    pub fn needs_committee(&self) -> bool {
+        self.verification_mode == VerificationMode::Committee as u8 || self.re_executed() || self.challenged != 0
+    }
+
+    /// Whether committee votes are re-execution results compared against result_hash rather than booleans
+    pub fn re_executed(&self) -> bool {
+        self.verification_mode == VerificationMode::ReExecution as u8
+    }
+
+    /// Counts a revealed vote towards the current round's tally
+    pub fn record_vote(&mut self, is_valid: bool) {
+        self.verifications += 1;
+        if is_valid {
+            self.valid_verifications += 1;
+        }
+    }
+
+    /// Starts a verification round: commits are accepted first, then reveals
+    pub fn open_verification_round(&mut self, now: i64) {
+        self.commit_deadline = now + COMMIT_PERIOD;
+        self.reveal_deadline = self.commit_deadline + REVEAL_PERIOD;
+    }
+
+    /// Final result of the verification vote: Some(true) if accepted, Some(false) if rejected.
+    /// A rejection only becomes final once it was appealed or the appeal window has passed.
+    pub fn verification_outcome(&self, now: i64) -> Option<bool> {
+        if self.is_verified != 0 {
+            Some(true)
+        } else if self.status == TaskStatus::Failed as u8
+            && self.reward_released != 0
+            && (self.appealed != 0 || now > self.verification_finalized_at + APPEAL_WINDOW)
+        {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// One replica's slice of a redundant task's reward
+    pub fn replica_reward(&self) -> Result<u64> {
+        self.reward_amount
+            .checked_div(self.redundancy as u64)
+            .ok_or(ComputeError::MathOverflow.into())
+    }
+
+    /// One replica's slice of the priority fee
+    pub fn replica_priority_fee(&self) -> u64 {
+        self.priority_fee.checked_div(self.redundancy as u64).unwrap_or(0)
+    }
+
+    /// Revealed votes that matched the final outcome and split the verification pool
+    pub fn winning_votes(&self, outcome: bool) -> u8 {
+        if outcome {
+            self.valid_verifications
+        } else {
+            self.verifications - self.valid_verifications
+        }
+    }
+
+    /// Verification pool still owed to verifiers who have not claimed their share
+    pub fn unclaimed_verification_rewards(&self, now: i64) -> u64 {
+        let winning_votes = self.verification_outcome(now)
+            .map_or(0, |outcome| self.winning_votes(outcome));
+        if winning_votes == 0 {
+            return 0;
+        }
+        self.verification_pool / winning_votes as u64 * winning_votes.saturating_sub(self.verification_claims) as u64
+    }
+
+    /// A settled task can only be closed once its collateral is released and every verifier and
+    /// replica share is claimed, or REWARD_CLAIM_PERIOD has passed since it settled
+    pub fn check_closable(&self, now: i64) -> Result<()> {
+        require!(self.reward_released != 0, ComputeError::TaskNotSettled);
+        require!(self.collateral == 0, ComputeError::CollateralNotSettled);
+        let replicas_claimed = self.redundancy <= 1 || self.is_verified == 0 || self.replica_claims >= self.agreeing_count;
+        let settled_at = self.completed_at.max(self.verification_finalized_at);
+        require!(
+            (replicas_claimed && self.unclaimed_verification_rewards(now) == 0) || now >= settled_at + REWARD_CLAIM_PERIOD,
+            ComputeError::RewardsUnclaimed
+        );
+        Ok(())
+    }
+
+    /// Revealed votes needed to decide the current round; appeals are judged by a larger committee
+    pub fn quorum(&self) -> u8 {
+        if self.appealed != 0 { APPEAL_QUORUM } else { VERIFICATION_QUORUM }
+    }
+
+    pub fn committee_size(&self) -> usize {
+        if self.appealed != 0 { APPEAL_COMMITTEE_SIZE } else { COMMITTEE_SIZE }
+    }
+
+    pub fn assign(&mut self, device: Pubkey, now: i64, accepted: bool, expiry_multiplier: u8) {
+        self.assigned_device = device;
+        self.assignment_accepted = accepted as u8;
+        self.status = TaskStatus::Assigned as u8;
+        self.assigned_at = now;
+        self.expires_at = now + self.compute_requirements.estimated_duration as i64 * expiry_multiplier as i64;
+    }
+}
+
+/// The keeper behind an expiry and the token accounts it pays out of the stake vault to
+pub struct ExpiryTransfers<'info> {
+    pub keeper: Pubkey,
+    pub token_program: AccountInfo<'info>,
+    pub reward_mint: AccountInfo<'info>,
+    pub decimals: u8,
+    pub network_state: AccountInfo<'info>,
+    pub network_state_bump: u8,
+    pub stake_vault: AccountInfo<'info>,
+    pub treasury: AccountInfo<'info>,
+    pub submitter_token_account: Option<AccountInfo<'info>>,
+    pub keeper_token_account: AccountInfo<'info>,
+}
+
+impl<'info> ExpiryTransfers<'info> {
+    fn pay(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let seeds = &[
+            b"network_state".as_ref(),
+            &[self.network_state_bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_accounts = TransferChecked {
+            from: self.stake_vault.clone(),
+            mint: self.reward_mint.clone(),
+            to: to.clone(),
+            authority: self.network_state.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.clone(), cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, amount, self.decimals)
+    }
+}
+
+/// Lamport escrow of a SOL-paid task: the system-owned PDA [b"sol_escrow", task]
+pub struct SolEscrow<'info> {
+    pub task: Pubkey,
+    pub escrow: AccountInfo<'info>,
+    pub bump: u8,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> SolEscrow<'info> {
+    pub fn new(
+        task: Pubkey,
+        escrow: Option<&SystemAccount<'info>>,
+        bump: Option<u8>,
+        system_program: &Program<'info, System>,
+    ) -> Result<Self> {
+        let (Some(escrow), Some(bump)) = (escrow, bump) else {
+            return Err(ComputeError::SolAccountsMissing.into());
+        };
+        Ok(Self {
+            task,
+            escrow: escrow.to_account_info(),
+            bump,
+            system_program: system_program.to_account_info(),
+        })
+    }
+
+    /// Empties the escrow of a task being closed. Shares verifiers could still have claimed were
+    /// forfeited when the claim period ran out and go to the treasury; the rest, rent deposit
+    /// included, goes back to the submitter.
+    pub fn close(
+        &self,
+        task_account: &TaskAccount,
+        submitter: &AccountInfo<'info>,
+        network_state: &mut Account<'info, NetworkState>,
+        now: i64,
+    ) -> Result<()> {
+        let forfeited = task_account.unclaimed_verification_rewards(now).min(self.escrow.lamports());
+        self.pay(&network_state.to_account_info(), forfeited)?;
+        network_state.sol_fees += forfeited;
+        self.pay(submitter, self.escrow.lamports())
+    }
+
+    pub fn pay(&self, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let seeds = &[
+            b"sol_escrow".as_ref(),
+            self.task.as_ref(),
+            &[self.bump]
+        ];
+        let signer_seeds = &[&seeds[..]];
+        let cpi_accounts = system_program::Transfer {
+            from: self.escrow.clone(),
+            to: to.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.system_program.clone(), cpi_accounts, signer_seeds);
+        system_program::transfer(cpi_ctx, amount)
+    }
+}
+
+/// Fails an accepted assignment that ran past its deadline: the device loses reputation, the task's
+/// collateral goes to the submitter and part of the stake is slashed, with a cut for the keeper.
+/// Returns the slashed amount and the part of it that went to the treasury.
+#[allow(clippy::too_many_arguments)]
+pub fn expire_assignment(
+    task_key: Pubkey,
+    task_account: &mut TaskAccount,
+    device_key: Pubkey,
+    device_account: &mut DeviceAccount,
+    network_state: &mut NetworkState,
+    config: &NetworkConfig,
+    now: i64,
+    transfers: &ExpiryTransfers,
+) -> Result<(u64, u64)> {
+    require!(
+        [TaskStatus::Assigned as u8, TaskStatus::InProgress as u8].contains(&task_account.status),
+        ComputeError::TaskNotAssigned
+    );
+    // Unaccepted assignments lapse back to the pending pool instead of being penalised
+    require!(task_account.assignment_accepted != 0, ComputeError::AssignmentNotAccepted);
+    require!(
+        now > task_account.expires_at + config.grace_period || task_account.is_stalled(now),
+        ComputeError::TaskNotExpired
+    );
+    
+    task_account.status = TaskStatus::Failed as u8;
+    device_account.reputation_score = device_account.reputation_score.saturating_sub(task_account.scaled_reputation(10, config));
+    device_account.active_assignments = device_account.active_assignments.saturating_sub(1);
+    device_account.release(&task_account.compute_requirements);
+    device_account.tasks_expired += 1;
+    
+    // The task's collateral compensates the submitter. It is staked in the network mint, so
+    // submitters paying in SOL or another mint can't take it and it goes to the treasury instead
+    let forfeited = task_account.collateral.min(device_account.staked_amount);
+    device_account.release_collateral(task_account.collateral);
+    task_account.collateral = 0;
+    let mut forfeited_to_treasury = 0;
+    if forfeited > 0 {
+        if task_account.pays_in_network_mint(&network_state.reward_mint) {
+            let submitter_token_account = transfers.submitter_token_account
+                .as_ref()
+                .ok_or(ComputeError::SubmitterAccountMissing)?;
+            transfers.pay(submitter_token_account, forfeited)?;
+        } else {
+            transfers.pay(&transfers.treasury, forfeited)?;
+            forfeited_to_treasury = forfeited;
+        }
+        device_account.slash_stake(forfeited, network_state);
+    }
+    
+    // Slash part of the device's stake, paying the keeper a cut and sending the rest to the treasury
+    let slash_amount = device_account.staked_amount
+        .checked_mul(EXPIRY_SLASH_BPS)
+        .ok_or(ComputeError::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(ComputeError::MathOverflow)?;
+    let keeper_reward = slash_amount
+        .checked_mul(KEEPER_REWARD_BPS)
+        .ok_or(ComputeError::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(ComputeError::MathOverflow)?;
+    
+    if slash_amount > 0 {
+        if keeper_reward > 0 {
+            transfers.pay(&transfers.keeper_token_account, keeper_reward)?;
+        }
+        transfers.pay(&transfers.treasury, slash_amount - keeper_reward)?;
+        
+        device_account.slash_stake(slash_amount, network_state);
+    }
+    device_account.tier = device_account.eligible_tier(config) as u8;
+    
+    emit!(Slashed {
+        device: device_key,
+        task: task_key,
+        amount: slash_amount,
+        reason: SlashReason::TaskExpired,
+    });
+    
+    emit!(CollateralReleased {
+        task: task_key,
+        device: device_key,
+        amount: forfeited,
+        forfeited: true,
+    });
+    
+    emit!(TaskExpired {
+        task: task_key,
+        device: device_key,
+        keeper: transfers.keeper,
+        slashed: slash_amount,
+        keeper_reward,
+    });
+    
+    Ok((slash_amount, slash_amount - keeper_reward + forfeited_to_treasury))
+}
+
+/// Credits a task reward to the device, or into its vesting account while vesting is enabled
+pub fn credit_reward(
+    device_key: Pubkey,
+    device_account: &mut DeviceAccount,
+    vesting_account: Option<&mut VestingAccount>,
+    config: &NetworkConfig,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    if config.vesting_duration == 0 {
+        device_account.pending_rewards += amount;
+        return Ok(());
+    }
+    
+    let vesting_account = vesting_account.ok_or(ComputeError::VestingAccountRequired)?;
+    vesting_account.add(amount, now)?;
+    emit!(RewardVested {
+        device: device_key,
+        amount,
+        total: vesting_account.total,
+    });
+    Ok(())
+}
+
+/// An instruction of this program, as stored by admin proposals and scheduled actions
+pub fn program_instruction(accounts: &[AdminAccountMeta], data: &[u8]) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts.iter()
+            .map(|meta| AccountMeta {
+                pubkey: meta.pubkey,
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data: data.to_vec(),
+    }
+}
+
+/// Verification level of a Pyth price update, as stored by the receiver program
+#[derive(AnchorDeserialize)]
+pub enum PythVerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+/// Leading fields of the Pyth receiver's PriceUpdateV2 account, after its discriminator
+#[derive(AnchorDeserialize)]
+pub struct PythPriceUpdate {
+    pub write_authority: Pubkey,
+    pub verification_level: PythVerificationLevel,
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+/// Converts USD cents to network-token base units at the price in a fully verified PriceUpdateV2
+/// for the configured feed, rejecting prices older than max_price_age or wider than max_price_conf_bps
+pub fn usd_to_tokens(
+    price_update: &AccountInfo,
+    config: &NetworkConfig,
+    usd_cents: u64,
+    decimals: u8,
+    now: i64,
+) -> Result<u64> {
+    require!(price_update.owner == &PYTH_RECEIVER_PROGRAM_ID, ComputeError::InvalidPriceAccount);
+    let data = price_update.try_borrow_data()?;
+    require!(data.len() > 8 && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR, ComputeError::InvalidPriceAccount);
+    let update = PythPriceUpdate::deserialize(&mut &data[8..])
+        .map_err(|_| ComputeError::InvalidPriceAccount)?;
+    require!(
+        matches!(update.verification_level, PythVerificationLevel::Full) && update.price > 0,
+        ComputeError::InvalidPriceAccount
+    );
+    require!(update.feed_id == config.usd_price_feed_id, ComputeError::PriceFeedMismatch);
+    require!(update.publish_time + config.max_price_age >= now, ComputeError::StalePrice);
+    require!(
+        update.conf as u128 * 10000 <= update.price as u128 * config.max_price_conf_bps as u128,
+        ComputeError::PriceTooUncertain
+    );
+    
+    // One whole token is worth price * 10^exponent USD
+    let scale = decimals as i32 - update.exponent;
+    let power = 10u128.checked_pow(scale.unsigned_abs()).ok_or(ComputeError::MathOverflow)?;
+    let (numerator, denominator) = if scale >= 0 {
+        (usd_cents as u128 * power, update.price as u128 * 100)
+    } else {
+        (usd_cents as u128, update.price as u128 * 100 * power)
+    };
+    u64::try_from(numerator / denominator).map_err(|_| ComputeError::MathOverflow.into())
+}
+
+/// Amount to send so the recipient gets `net` after the mint's Token-2022 transfer fee, if it has one
+pub fn with_transfer_fee(mint: &InterfaceAccount<Mint>, net: u64) -> Result<u64> {
+    let mint_info = mint.to_account_info();
+    let data = mint_info.try_borrow_data()?;
+    let Ok(mint_state) = StateWithExtensions::<anchor_spl::token_2022::spl_token_2022::state::Mint>::unpack(&data) else {
+        return Ok(net);
+    };
+    let Ok(fee_config) = mint_state.get_extension::<TransferFeeConfig>() else {
+        return Ok(net);
+    };
+    let fee = fee_config
+        .calculate_inverse_epoch_fee(Clock::get()?.epoch, net)
+        .ok_or(ComputeError::MathOverflow)?;
+    net.checked_add(fee).ok_or(ComputeError::MathOverflow.into())
+}
+
+/// Rejects task ids in the namespace reserved for recurring task instances
+pub fn check_task_id(task_id: &str) -> Result<()> {
+    require!(!task_id.starts_with(RECURRING_INSTANCE_PREFIX), ComputeError::ReservedTaskId);
+    Ok(())
+}
+
+/// Copies a string into a zero-padded fixed-size field
+pub fn to_fixed_bytes<const N: usize>(value: &str) -> Result<[u8; N]> {
+    require!(value.len() <= N, ComputeError::FieldTooLong);
+    let mut bytes = [0u8; N];
+    bytes[..value.len()].copy_from_slice(value.as_bytes());
+    Ok(bytes)
+}
+
+/// Accepts a URI under one of RESULT_URI_SCHEMES with a non-empty, printable, space-free location
+pub fn validate_result_uri(uri: &str) -> Result<()> {
+    require!(uri.len() <= MAX_RESULT_URI_LEN, ComputeError::ResultUriTooLong);
+    let location = RESULT_URI_SCHEMES.iter()
+        .find_map(|scheme| uri.strip_prefix(scheme))
+        .ok_or(ComputeError::InvalidResultUri)?;
+    require!(
+        !location.is_empty() && location.bytes().all(|byte| byte.is_ascii_graphic()),
+        ComputeError::InvalidResultUri
+    );
+    // Content-addressed locations must start with a well-formed identifier
+    let content_id = location.split('/').next().unwrap_or_default();
+    if uri.starts_with("ipfs://") {
+        require!(is_valid_ipfs_cid(content_id), ComputeError::InvalidContentId);
+    } else if uri.starts_with("ar://") {
+        require!(is_valid_arweave_id(content_id), ComputeError::InvalidContentId);
+    }
+    Ok(())
+}
+
+pub fn is_content_addressed(uri: &str) -> bool {
+    uri.starts_with("ipfs://") || uri.starts_with("ar://")
+}
+
+/// CIDv0 ("Qm" + base58btc sha256 multihash) or CIDv1 in the default base32 multibase ('b' prefix)
+pub fn is_valid_ipfs_cid(cid: &str) -> bool {
+    const BASE58: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    if cid.len() == 46 && cid.starts_with("Qm") {
+        return cid.bytes().all(|byte| BASE58.contains(&byte));
+    }
+    match cid.strip_prefix('b') {
+        Some(encoded) => encoded.len() >= 58 && encoded.bytes().all(|byte| matches!(byte, b'a'..=b'z' | b'2'..=b'7')),
+        None => false,
+    }
+}
+
+/// Arweave transaction ids are 32 bytes in unpadded base64url, i.e. 43 characters
+pub fn is_valid_arweave_id(id: &str) -> bool {
+    id.len() == 43 && id.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_')
+}
+
+/// Accepts a `type/subtype` MIME type made of RFC 6838 token characters
+pub fn validate_mime_type(mime: &str) -> Result<()> {
+    require!(mime.len() <= MAX_RESULT_MIME_LEN, ComputeError::InvalidResultMime);
+    let is_token = |part: &str| !part.is_empty() && part.bytes().all(|byte| {
+        byte.is_ascii_alphanumeric() || b"!#$&-^_.+".contains(&byte)
+    });
+    let (kind, subtype) = mime.split_once('/').ok_or(ComputeError::InvalidResultMime)?;
+    require!(is_token(kind) && is_token(subtype), ComputeError::InvalidResultMime);
+    Ok(())
+}
+
+/// Clears the top three bits of a hash so it fits below the BN254 scalar field modulus
+pub fn to_field_element(mut hash: [u8; 32]) -> [u8; 32] {
+    hash[0] &= 0x1f;
+    hash
+}
+
+/// Checks a merkle proof built with sorted pairs, hash(min(a, b) || max(a, b)), up to `root`
+pub fn verify_merkle_proof(leaf: [u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed == *root
+}
+
+/// Returns the key whose signature over `message` the Ed25519 program checked in the instruction
+/// just before the current one. A bad signature already failed the transaction there, so only the
+/// signed key and message are read back here.
+#[allow(deprecated)]
+pub fn ed25519_signer(instructions_sysvar: &AccountInfo, message: &[u8]) -> Result<Pubkey> {
+    use anchor_lang::solana_program::{ed25519_program, sysvar::instructions};
+    
+    let current = instructions::load_current_index_checked(instructions_sysvar)? as usize;
+    require!(current > 0, ComputeError::InvalidAttestation);
+    let ix = instructions::load_instruction_at_checked(current - 1, instructions_sysvar)?;
+    require!(ix.program_id == ed25519_program::ID, ComputeError::InvalidAttestation);
+    
+    // A single signature whose key and message live inside the Ed25519 instruction itself
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, ComputeError::InvalidAttestation);
+    let read = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+    let this_instruction = u16::MAX as usize;
+    require!(
+        read(4) == this_instruction && read(8) == this_instruction && read(14) == this_instruction,
+        ComputeError::InvalidAttestation
+    );
+    let (key_offset, message_offset, message_size) = (read(6), read(10), read(12));
+    require!(
+        data.get(message_offset..message_offset + message_size) == Some(message),
+        ComputeError::InvalidAttestation
+    );
+    let key: [u8; 32] = data.get(key_offset..key_offset + 32)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ComputeError::InvalidAttestation)?;
+    Ok(Pubkey::new_from_array(key))
+}
+
+/// Creates the task PDA for `task_id` at an uninitialized account passed in remaining_accounts.
+/// The account is zeroed apart from its discriminator and version; the caller fills in the task.
+pub fn create_task_account<'info>(
+    task_info: &'info AccountInfo<'info>,
+    task_id: &str,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> Result<AccountLoader<'info, TaskAccount>> {
+    check_task_id(task_id)?;
+    let (task_key, bump) = Pubkey::find_program_address(&[b"task", task_id.as_bytes()], program_id);
+    require!(task_info.key() == task_key, ComputeError::TaskAddressMismatch);
+    
+    let space = 8 + TaskAccount::LEN;
+    let seeds = &[
+        b"task".as_ref(),
+        task_id.as_bytes(),
+        &[bump]
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let rent = Rent::get()?.minimum_balance(space);
+    let current_lamports = task_info.lamports();
+    if current_lamports == 0 {
+        let cpi_accounts = CreateAccount {
+            from: payer,
+            to: task_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(system_program, cpi_accounts, signer_seeds);
+        system_program::create_account(cpi_ctx, rent, space as u64, program_id)?;
+    } else {
+        // Lamports sent to the predictable address beforehand would make create_account fail, so
+        // top the balance up and allocate and assign it instead, as Anchor's init does
+        if current_lamports < rent {
+            let cpi_accounts = system_program::Transfer {
+                from: payer,
+                to: task_info.clone(),
+            };
+            let cpi_ctx = CpiContext::new(system_program.clone(), cpi_accounts);
+            system_program::transfer(cpi_ctx, rent - current_lamports)?;
+        }
+        let cpi_accounts = system_program::Allocate {
+            account_to_allocate: task_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(system_program.clone(), cpi_accounts, signer_seeds);
+        system_program::allocate(cpi_ctx, space as u64)?;
+        let cpi_accounts = system_program::Assign {
+            account_to_assign: task_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(system_program, cpi_accounts, signer_seeds);
+        system_program::assign(cpi_ctx, program_id)?;
+    }
+    task_info.try_borrow_mut_data()?[..8].copy_from_slice(TaskAccount::DISCRIMINATOR);
+    
+    let task_loader = AccountLoader::<TaskAccount>::try_from(task_info)?;
+    task_loader.load_mut()?.version = TaskAccount::VERSION;
+    Ok(task_loader)
+}
+
+/// Zero-copy accounts that grow by appending fields, so an older layout is a prefix of the current
+/// one with a lower `version`. Instructions only load current accounts; migrate_device and
+/// migrate_task upgrade the rest in place.
+pub trait Versioned: anchor_lang::ZeroCopy + anchor_lang::Owner {
+    const VERSION: u8;
+    
+    fn version(&self) -> u8;
+    fn set_version(&mut self, version: u8);
+}
+
+/// Whether the account is at the current size and version
+pub fn is_current<T: Versioned>(loader: &AccountLoader<T>) -> bool {
+    loader.as_ref().data_len() >= 8 + std::mem::size_of::<T>()
+        && loader.load().is_ok_and(|account| account.version() == T::VERSION)
+}
+
+/// Grows an account written under an older layout to the current size, zero-filling the new
+/// fields and topping up rent from `payer`, then stamps the current version. Returns the old version.
+pub fn migrate_layout<'info, T: Versioned>(
+    info: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<u8> {
+    let space = 8 + std::mem::size_of::<T>();
+    {
+        let data = info.try_borrow_data()?;
+        require!(
+            info.owner == &crate::ID && data.len() >= 8 && data[..8] == *T::DISCRIMINATOR && data.len() <= space,
+            ComputeError::InvalidMigration
+        );
+    }
+    
+    if info.data_len() < space {
+        let shortfall = Rent::get()?.minimum_balance(space).saturating_sub(info.lamports());
+        if shortfall > 0 {
+            let cpi_accounts = system_program::Transfer {
+                from: payer.to_account_info(),
+                to: info.clone(),
+            };
+            let cpi_ctx = CpiContext::new(system_program.to_account_info(), cpi_accounts);
+            system_program::transfer(cpi_ctx, shortfall)?;
+        }
+        info.resize(space)?;
+    }
+    
+    let mut data = info.try_borrow_mut_data()?;
+    let account: &mut T = bytemuck::from_bytes_mut(&mut data[8..space]);
+    let from_version = account.version();
+    require!(from_version < T::VERSION, ComputeError::AccountAlreadyCurrent);
+    account.set_version(T::VERSION);
+    Ok(from_version)
+}
+
+/// Reads a unit enum back from the discriminant stored in a zero-copy account
+pub fn enum_from_u8<T: AnchorDeserialize>(value: u8) -> Result<T> {
+    T::try_from_slice(&[value]).map_err(|_| error!(ComputeError::InvalidEnumValue))
+}
+
+/// Reads a zero-padded fixed-size field back into a string
+pub fn from_fixed_bytes(bytes: &[u8]) -> String {
+    let len = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..len]).into_owned()
+}
+
+/// One replica of a redundantly executed task
+#[account]
+pub struct Execution {
+    pub task: Pubkey,
+    pub device: Pubkey,
+    pub result_hash: String,
+    pub submitted: bool,
+    pub rewarded: bool,
+    pub submitted_at: i64,
+    /// Stake locked by the replica's claim until settle_redundant_task
+    pub collateral: u64,
+}
+
+impl Execution {
+    pub const LEN: usize = 32 + 32 + 4 + 64 + 1 + 1 + 8 + 8;
+}
+
+/// Task rewards held back under the NetworkConfig vesting schedule.
+/// Each credit moves `start` to the amount-weighted average, so older rewards
+/// keep most of their progress instead of restarting the cliff.
+#[account]
+pub struct VestingAccount {
+    pub device: Pubkey,
+    pub owner: Pubkey,
+    pub total: u64,
+    pub released: u64,
+    pub start: i64,
+}
+
+impl VestingAccount {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 8;
+
+    pub fn add(&mut self, amount: u64, now: i64) -> Result<()> {
+        let new_total = self.total.checked_add(amount).ok_or(ComputeError::MathOverflow)?;
+        let weighted = (self.start as i128 * self.total as i128 + now as i128 * amount as i128)
+            / (new_total.max(1) as i128);
+        self.start = weighted as i64;
+        self.total = new_total;
+        Ok(())
+    }
+
+    pub fn vested(&self, config: &NetworkConfig, now: i64) -> u64 {
+        let elapsed = now - self.start;
+        if config.vesting_duration == 0 || elapsed >= config.vesting_duration {
+            self.total
+        } else if elapsed < config.vesting_cliff {
+            0
+        } else {
+            (self.total as u128 * elapsed as u128 / config.vesting_duration as u128) as u64
+        }
+    }
+
+    pub fn claimable(&self, config: &NetworkConfig, now: i64) -> Result<u64> {
+        Ok(self.vested(config, now).saturating_sub(self.released))
+    }
+}
+
+/// A device's offer to run an auctioned task for `amount` within `eta` seconds
+#[account]
+pub struct Bid {
+    pub task: Pubkey,
+    pub device: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub eta: u32,
+    pub created_at: i64,
+}
+
+impl Bid {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 4 + 8;
+}
+
+/// Per-submitter history, created on the first submission
+#[account]
+pub struct SubmitterProfile {
+    pub submitter: Pubkey,
+    pub tasks_submitted: u64,
+    pub tasks_cancelled: u64,
+    /// Rewards escrowed by the submitter, net of refunds
+    pub total_rewards_paid: u64,
+    /// Challenges raised by the submitter that the committee rejected
+    pub disputes_lost: u64,
+    pub created_at: i64,
+    /// Slot at which the current rate limit window opened
+    pub window_start_slot: u64,
+    pub window_submissions: u64,
+    /// Set by the authority through add_submitter; required while the network is permissioned
+    pub allowlisted: bool,
+}
+
+impl SubmitterProfile {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    pub fn check_allowed(&self, config: &NetworkConfig) -> Result<()> {
+        require!(
+            !config.submitter_allowlist_enabled || self.allowlisted,
+            ComputeError::SubmitterNotAllowed
+        );
+        Ok(())
+    }
+
+    /// Records a task withdrawn before it ran, taking its refunded escrow off the rewards paid
+    pub fn record_cancellation(&mut self, refund: u64) -> Result<()> {
+        self.tasks_cancelled = self.tasks_cancelled.checked_add(1).ok_or(ComputeError::MathOverflow)?;
+        self.total_rewards_paid = self.total_rewards_paid.saturating_sub(refund);
+        Ok(())
+    }
+
+    /// Counts a submission against the rate limit, opening a new window once the old one has passed
+    pub fn record_submission(&mut self, slot: u64, config: &NetworkConfig) -> Result<()> {
+        if slot >= self.window_start_slot + config.rate_limit_window_slots {
+            self.window_start_slot = slot;
+            self.window_submissions = 0;
+        }
+        require!(
+            config.max_submissions_per_window == 0 || self.window_submissions < config.max_submissions_per_window,
+            ComputeError::RateLimited
+        );
+        self.window_submissions += 1;
+        self.tasks_submitted += 1;
+        Ok(())
+    }
+}
+
+/// Prepaid balance of one mint that submit_task draws on; the tokens themselves sit in the reward vault
+#[account]
+pub struct CreditAccount {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub balance: u64,
+    pub total_deposited: u64,
+    pub total_spent: u64,
+    /// Nonce the owner's next submission intent must carry
+    pub intent_nonce: u64,
+}
+
+impl CreditAccount {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 8;
+    
+    pub fn spend(&mut self, amount: u64) -> Result<()> {
+        require!(amount <= self.balance, ComputeError::InsufficientCredits);
+        self.balance -= amount;
+        self.total_spent += amount;
+        Ok(())
+    }
+}
+
+/// Hot key a device owner lets sign routine device instructions, scoped by AGENT_PERMISSION_* flags
+#[account]
+pub struct AgentAuthority {
+    pub device: Pubkey,
+    pub agent: Pubkey,
+    pub permissions: u8,
+    pub expires_at: i64,
+    /// DeviceAccount::agent_key_epoch the key was issued under
+    pub key_epoch: u64,
+}
+
+impl AgentAuthority {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 8;
+}
+
+/// A fleet of devices run under one operator, who routes the pool's tasks to its members and
+/// takes commission_bps of the network-token and SOL rewards they earn
+#[account]
+pub struct Pool {
+    pub operator: Pubkey,
+    pub commission_bps: u16,
+    pub member_count: u32,
+    /// Members' stake as of their last sync_pool_stake
+    pub total_stake: u64,
+    /// Tasks members finished through complete_task while in the pool
+    pub tasks_completed: u64,
+    /// Commission in network tokens, held in the reward vault until claim_pool_commission
+    pub pending_commission: u64,
+    /// Commission from SOL-paid tasks, held as lamports on this account
+    pub sol_commission: u64,
+    pub created_at: i64,
+}
+
+impl Pool {
+    pub const LEN: usize = 32 + 2 + 4 + 8 + 8 + 8 + 8 + 8;
+
+    pub fn commission(&self, reward: u64) -> u64 {
+        (reward as u128 * self.commission_bps as u128 / 10000) as u64
+    }
+}
+
+/// Program-held authority over the concurrent merkle tree archived tasks are appended to
+#[account]
+pub struct ArchiveTree {
+    pub merkle_tree: Pubkey,
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    /// Tasks archived so far, which is also the index of the next leaf
+    pub leaf_count: u64,
+    pub created_at: i64,
+}
+
+impl ArchiveTree {
+    pub const LEN: usize = 32 + 4 + 4 + 8 + 8;
+    
+    /// Calls the compression program on the tree with the archive PDA signing as its authority
+    pub fn compression_cpi<'info>(
+        archive_tree: AccountInfo<'info>,
+        bump: u8,
+        data: Vec<u8>,
+        merkle_tree: &UncheckedAccount<'info>,
+        log_wrapper: &UncheckedAccount<'info>,
+        compression_program: &UncheckedAccount<'info>,
+    ) -> Result<()> {
+        let ix = Instruction {
+            program_id: SPL_ACCOUNT_COMPRESSION_ID,
+            accounts: vec![
+                AccountMeta::new(merkle_tree.key(), false),
+                AccountMeta::new_readonly(archive_tree.key(), true),
+                AccountMeta::new_readonly(log_wrapper.key(), false),
+            ],
+            data,
+        };
+        invoke_signed(
+            &ix,
+            &[
+                merkle_tree.to_account_info(),
+                archive_tree,
+                log_wrapper.to_account_info(),
+                compression_program.to_account_info(),
+            ],
+            &[&[b"archive_tree".as_ref(), &[bump]]],
+        )?;
+        Ok(())
+    }
+}
+
+/// Program-held creator of the Bubblegum tree achievement badges are minted into
+#[account]
+pub struct BadgeTree {
+    pub merkle_tree: Pubkey,
+    /// Badge metadata lives at `<uri_prefix><kind>.json`
+    pub uri_prefix: String,
+    pub badges_minted: u64,
+}
+
+impl BadgeTree {
+    pub const LEN: usize = 32 + 4 + MAX_BADGE_URI_LEN + 8;
+}
+
+/// Record that a device's badge for `kind` was minted; its existence blocks a second mint
+#[account]
+pub struct Achievement {
+    pub device: Pubkey,
+    pub kind: AchievementKind,
+    pub recipient: Pubkey,
+    pub awarded_at: i64,
+}
+
+impl Achievement {
+    pub const LEN: usize = 32 + 1 + 32 + 8;
+}
+
+/// Borsh mirror of Bubblegum's MetadataArgs; enums are written as their u8 discriminant
+#[derive(AnchorSerialize)]
+pub struct BubblegumMetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    /// TokenStandard; 0 is NonFungible
+    pub token_standard: Option<u8>,
+    pub collection: Option<MetaplexCollection>,
+    pub uses: Option<MetaplexUses>,
+    /// TokenProgramVersion; 0 is Original
+    pub token_program_version: u8,
+    pub creators: Vec<MetaplexCreator>,
+}
+
+/// Borsh mirror of Token Metadata's DataV2
+#[derive(AnchorSerialize)]
+pub struct MetaplexDataV2 {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<MetaplexCreator>>,
+    pub collection: Option<MetaplexCollection>,
+    pub uses: Option<MetaplexUses>,
+}
+
+#[derive(AnchorSerialize)]
+pub struct MetaplexCollection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[derive(AnchorSerialize)]
+pub struct MetaplexUses {
+    pub use_method: u8,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(AnchorSerialize)]
+pub struct MetaplexCreator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+/// Stake queued by request_unstake, withdrawable once `unlock_at` has passed
+#[account]
+pub struct PendingWithdrawal {
+    pub device: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub unlock_at: i64,
+}
+
+impl PendingWithdrawal {
+    pub const LEN: usize = 32 + 32 + 8 + 8;
+}
+
+#[account]
+pub struct SlashRecord {
+    pub device: Pubkey,
+    pub task: Pubkey,
+    pub amount: u64,
+    pub reason: SlashReason,
+    pub timestamp: i64,
+}
+
+impl SlashRecord {
+    pub const LEN: usize = 32 + 32 + 8 + 1 + 8;
+}
+
+#[account]
+pub struct Challenge {
+    pub task: Pubkey,
+    pub challenger: Pubkey,
+    pub challenger_token_account: Pubkey,
+    pub bond: u64,
+    pub created_at: i64,
+    pub resolved: bool,
+}
+
+impl Challenge {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct Appeal {
+    pub task: Pubkey,
+    pub device: Pubkey,
+    pub owner_token_account: Pubkey,
+    pub bond: u64,
+    pub created_at: i64,
+    pub resolved: bool,
+}
+
+impl Appeal {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct VerifierPool {
+    pub entries: Vec<VerifierEntry>,
+}
+
+impl VerifierPool {
+    pub const LEN: usize = 4 + MAX_VERIFIER_POOL_SIZE * VerifierEntry::LEN;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct VerifierEntry {
+    pub device: Pubkey,
+    pub owner: Pubkey,
+}
+
+impl VerifierEntry {
+    pub const LEN: usize = 32 + 32;
+}
+
+/// Reusable task settings that submit_task_from_template fills in, so submitters don't repeat them
+#[account]
+pub struct TaskTemplate {
+    pub owner: Pubkey,
+    pub template_id: String,
+    pub preset: TaskPreset,
+    pub created_at: i64,
+}
+
+impl TaskTemplate {
+    pub const LEN: usize = 32 + 4 + 32 + TaskPreset::LEN + 8;
+}
+
+/// The latest availability spot check sent to a device; reused for every challenge to it
+#[account]
+pub struct AvailabilityChallenge {
+    pub device: Pubkey,
+    pub keeper: Pubkey,
+    pub nonce: [u8; 32],
+    pub issued_at: i64,
+    pub deadline: i64,
+    pub open: bool,
+}
+
+impl AvailabilityChallenge {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 1;
+}
+
+/// A task's input stored on-chain, written in chunks and sealed with a hash check
+#[account]
+pub struct TaskData {
+    pub task: Pubkey,
+    /// sha256 of `data`, set on finalize
+    pub data_hash: [u8; 32],
+    pub total_len: u32,
+    pub finalized: bool,
+    pub data: Vec<u8>,
+}
+
+impl TaskData {
+    pub fn space(total_len: usize) -> usize {
+        32 + 32 + 4 + 1 + 4 + total_len
+    }
+}
+
+/// M-of-N council whose executed proposals act as the network authority
+#[account]
+pub struct AdminCouncil {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub proposal_count: u64,
+}
+
+impl AdminCouncil {
+    pub const LEN: usize = 4 + 32 * MAX_COUNCIL_MEMBERS + 1 + 8;
+
+    pub fn validate(members: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            members.len() <= MAX_COUNCIL_MEMBERS &&
+            threshold >= 1 && threshold as usize <= members.len() &&
+            members.iter().enumerate().all(|(i, member)| !members[..i].contains(member)),
+            ComputeError::InvalidCouncil
+        );
+        Ok(())
+    }
+
+    /// Approvals on `proposal` from keys that are still members
+    pub fn approvals_of(&self, proposal: &AdminProposal) -> u8 {
+        proposal.approvals.iter().filter(|member| self.members.contains(member)).count() as u8
+    }
+}
+
+/// One account of a proposed admin instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AdminAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// An instruction of this program awaiting council approval
+#[account]
+pub struct AdminProposal {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub accounts: Vec<AdminAccountMeta>,
+    pub data: Vec<u8>,
+    pub approvals: Vec<Pubkey>,
+    pub created_at: i64,
+    pub executed: bool,
+}
+
+impl AdminProposal {
+    pub const LEN: usize = 8 + 32 + 4 + 34 * MAX_ADMIN_ACCOUNTS + 4 + MAX_ADMIN_DATA_LEN + 4 + 32 * MAX_COUNCIL_MEMBERS + 8 + 1;
+}
+
+/// Delay applied to config changes and treasury withdrawals
+#[account]
+pub struct Timelock {
+    pub delay: i64,
+    pub action_count: u64,
+}
+
+impl Timelock {
+    pub const LEN: usize = 8 + 8;
+}
+
+/// An instruction queued behind the timelock
+#[account]
+pub struct ScheduledAction {
+    pub id: u64,
+    pub accounts: Vec<AdminAccountMeta>,
+    pub data: Vec<u8>,
+    pub scheduled_at: i64,
+    /// Earliest time execute_action may run it
+    pub eta: i64,
+    pub executed: bool,
+}
+
+impl ScheduledAction {
+    pub const LEN: usize = 8 + 4 + 34 * MAX_ADMIN_ACCOUNTS + 4 + MAX_ADMIN_DATA_LEN + 8 + 8 + 1;
+}
+
+/// Counter for stake-weighted governance proposals, created with the first proposal
+#[account]
+pub struct GovernanceState {
+    pub proposal_count: u64,
+}
+
+impl GovernanceState {
+    pub const LEN: usize = 8;
+}
+
+/// Argument to update_config; each field left as `None` keeps its current value
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ConfigUpdate {
+    pub tier_thresholds: Option<[u64; 3]>,
+    pub unstake_period: Option<i64>,
+    pub expiry_multiplier: Option<u8>,
+    pub speed_bonus_bps: Option<u64>,
+    pub verification_threshold_bps: Option<u64>,
+    pub protocol_fee_bps: Option<u64>,
+    pub matcher: Option<Pubkey>,
+    pub collateral_bps: Option<u64>,
+    pub epoch_duration: Option<i64>,
+    pub epoch_emission: Option<u64>,
+    pub vesting_cliff: Option<i64>,
+    pub vesting_duration: Option<i64>,
+    pub tier_reward_bps: Option<[u64; 4]>,
+    pub surge_threshold: Option<u8>,
+    pub max_surge_bps: Option<u64>,
+    pub task_type_reputation_bps: Option<[u64; 5]>,
+    pub gpu_reputation_bps: Option<u64>,
+    pub reputation_reward_unit: Option<u64>,
+    pub high_value_reward: Option<u64>,
+    pub high_value_min_reputation: Option<u16>,
+    pub max_disputes_lost: Option<u64>,
+    pub submission_fee: Option<u64>,
+    pub rate_limit_window_slots: Option<u64>,
+    pub max_submissions_per_window: Option<u64>,
+    pub min_reward_by_type: Option<[u64; 5]>,
+    pub max_deadline_extension: Option<i64>,
+    pub grace_period: Option<i64>,
+    pub late_reward_floor_bps: Option<u64>,
+    pub max_concurrent_by_tier: Option<[u16; 4]>,
+    pub tier_benchmark_scores: Option<[u64; 3]>,
+    pub storage_oracle: Option<Pubkey>,
+    pub availability_emission: Option<u64>,
+    pub availability_reward: Option<u64>,
+    pub submitter_allowlist_enabled: Option<bool>,
+    pub initial_epoch_mint: Option<u64>,
+    pub emission_decay_bps: Option<u64>,
+    pub burn_bps: Option<u64>,
+    pub usd_price_feed_id: Option<[u8; 32]>,
+    pub max_price_age: Option<i64>,
+    pub max_price_conf_bps: Option<u64>,
+    pub leaderboard_prize: Option<u64>,
+}
+
+impl ConfigUpdate {
+    pub fn apply(&self, config: &mut NetworkConfig) {
+        if let Some(tier_thresholds) = self.tier_thresholds {
+            config.tier_thresholds = tier_thresholds;
+        }
+        if let Some(unstake_period) = self.unstake_period {
+            config.unstake_period = unstake_period;
+        }
+        if let Some(expiry_multiplier) = self.expiry_multiplier {
+            config.expiry_multiplier = expiry_multiplier;
+        }
+        if let Some(speed_bonus_bps) = self.speed_bonus_bps {
+            config.speed_bonus_bps = speed_bonus_bps;
+        }
+        if let Some(verification_threshold_bps) = self.verification_threshold_bps {
+            config.verification_threshold_bps = verification_threshold_bps;
+        }
+        if let Some(protocol_fee_bps) = self.protocol_fee_bps {
+            config.protocol_fee_bps = protocol_fee_bps;
+        }
+        if let Some(matcher) = self.matcher {
+            config.matcher = matcher;
+        }
+        if let Some(collateral_bps) = self.collateral_bps {
+            config.collateral_bps = collateral_bps;
+        }
+        if let Some(epoch_duration) = self.epoch_duration {
+            config.epoch_duration = epoch_duration;
+        }
+        if let Some(epoch_emission) = self.epoch_emission {
+            config.epoch_emission = epoch_emission;
+        }
+        if let Some(vesting_cliff) = self.vesting_cliff {
+            config.vesting_cliff = vesting_cliff;
+        }
+        if let Some(vesting_duration) = self.vesting_duration {
+            config.vesting_duration = vesting_duration;
+        }
+        if let Some(tier_reward_bps) = self.tier_reward_bps {
+            config.tier_reward_bps = tier_reward_bps;
+        }
+        if let Some(surge_threshold) = self.surge_threshold {
+            config.surge_threshold = surge_threshold;
+        }
+        if let Some(max_surge_bps) = self.max_surge_bps {
+            config.max_surge_bps = max_surge_bps;
+        }
+        if let Some(task_type_reputation_bps) = self.task_type_reputation_bps {
+            config.task_type_reputation_bps = task_type_reputation_bps;
+        }
+        if let Some(gpu_reputation_bps) = self.gpu_reputation_bps {
+            config.gpu_reputation_bps = gpu_reputation_bps;
+        }
+        if let Some(reputation_reward_unit) = self.reputation_reward_unit {
+            config.reputation_reward_unit = reputation_reward_unit;
+        }
+        if let Some(high_value_reward) = self.high_value_reward {
+            config.high_value_reward = high_value_reward;
+        }
+        if let Some(high_value_min_reputation) = self.high_value_min_reputation {
+            config.high_value_min_reputation = high_value_min_reputation;
+        }
+        if let Some(max_disputes_lost) = self.max_disputes_lost {
+            config.max_disputes_lost = max_disputes_lost;
+        }
+        if let Some(submission_fee) = self.submission_fee {
+            config.submission_fee = submission_fee;
+        }
+        if let Some(rate_limit_window_slots) = self.rate_limit_window_slots {
+            config.rate_limit_window_slots = rate_limit_window_slots;
+        }
+        if let Some(max_submissions_per_window) = self.max_submissions_per_window {
+            config.max_submissions_per_window = max_submissions_per_window;
+        }
+        if let Some(min_reward_by_type) = self.min_reward_by_type {
+            config.min_reward_by_type = min_reward_by_type;
+        }
+        if let Some(max_deadline_extension) = self.max_deadline_extension {
+            config.max_deadline_extension = max_deadline_extension;
+        }
+        if let Some(grace_period) = self.grace_period {
+            config.grace_period = grace_period;
+        }
+        if let Some(late_reward_floor_bps) = self.late_reward_floor_bps {
+            config.late_reward_floor_bps = late_reward_floor_bps;
+        }
+        if let Some(max_concurrent_by_tier) = self.max_concurrent_by_tier {
+            config.max_concurrent_by_tier = max_concurrent_by_tier;
+        }
+        if let Some(tier_benchmark_scores) = self.tier_benchmark_scores {
+            config.tier_benchmark_scores = tier_benchmark_scores;
+        }
+        if let Some(storage_oracle) = self.storage_oracle {
+            config.storage_oracle = storage_oracle;
+        }
+        if let Some(availability_emission) = self.availability_emission {
+            config.availability_emission = availability_emission;
+        }
+        if let Some(availability_reward) = self.availability_reward {
+            config.availability_reward = availability_reward;
+        }
+        if let Some(submitter_allowlist_enabled) = self.submitter_allowlist_enabled {
+            config.submitter_allowlist_enabled = submitter_allowlist_enabled;
+        }
+        if let Some(initial_epoch_mint) = self.initial_epoch_mint {
+            config.initial_epoch_mint = initial_epoch_mint;
+        }
+        if let Some(emission_decay_bps) = self.emission_decay_bps {
+            config.emission_decay_bps = emission_decay_bps;
+        }
+        if let Some(burn_bps) = self.burn_bps {
+            config.burn_bps = burn_bps;
+        }
+        if let Some(usd_price_feed_id) = self.usd_price_feed_id {
+            config.usd_price_feed_id = usd_price_feed_id;
+        }
+        if let Some(max_price_age) = self.max_price_age {
+            config.max_price_age = max_price_age;
+        }
+        if let Some(max_price_conf_bps) = self.max_price_conf_bps {
+            config.max_price_conf_bps = max_price_conf_bps;
+        }
+        if let Some(leaderboard_prize) = self.leaderboard_prize {
+            config.leaderboard_prize = leaderboard_prize;
+        }
+    }
+}
+
+/// Config parameters stakers can change through governance
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum ConfigChange {
+    ProtocolFeeBps(u64),
+    SubmissionFee(u64),
+    CollateralBps(u64),
+    TierThresholds([u64; 3]),
+    TierRewardBps([u64; 4]),
+    UnstakePeriod(i64),
+    GracePeriod(i64),
+}
+
+impl ConfigChange {
+    pub const LEN: usize = 1 + 4 * 8;
+
+    /// Applies the change under the same rules update_config enforces
+    pub fn apply(&self, config: &mut NetworkConfig) -> Result<()> {
+        match *self {
+            ConfigChange::ProtocolFeeBps(bps) => config.protocol_fee_bps = bps,
+            ConfigChange::SubmissionFee(fee) => config.submission_fee = fee,
+            ConfigChange::CollateralBps(bps) => config.collateral_bps = bps,
+            ConfigChange::TierThresholds(thresholds) => config.tier_thresholds = thresholds,
+            ConfigChange::TierRewardBps(bps) => config.tier_reward_bps = bps,
+            ConfigChange::UnstakePeriod(period) => config.unstake_period = period,
+            ConfigChange::GracePeriod(period) => config.grace_period = period,
+        }
+        config.validate()
+    }
+}
+
+#[account]
+pub struct GovernanceProposal {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub change: ConfigChange,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    /// Votes needed for the outcome to count, fixed when the proposal is created
+    pub quorum: u64,
+    pub created_at: i64,
+    pub voting_ends_at: i64,
+    pub executed: bool,
+}
+
+impl GovernanceProposal {
+    pub const LEN: usize = 8 + 32 + ConfigChange::LEN + 8 + 8 + 8 + 8 + 8 + 1;
+
+    pub fn passed(&self) -> bool {
+        self.votes_for > self.votes_against && self.votes_for + self.votes_against >= self.quorum
+    }
+}
+
+/// One device's vote on a governance proposal
+#[account]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub device: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+}
+
+impl VoteRecord {
+    pub const LEN: usize = 32 + 32 + 1 + 8;
+}
+
+/// Keys trusted to sign hashes of a device's real hardware specs
+#[account]
+pub struct AttesterRegistry {
+    pub attesters: Vec<Pubkey>,
+}
+
+impl AttesterRegistry {
+    pub const LEN: usize = 4 + 32 * MAX_ATTESTERS;
+    
+    /// Checks the attester signature over `specs` when the registry and instructions sysvar are
+    /// passed; without them the specs are simply unattested
+    pub fn check_specs_attestation(
+        registry: Option<&Account<AttesterRegistry>>,
+        instructions: Option<&UncheckedAccount>,
+        device: &Pubkey,
+        specs: &DeviceSpecs,
+    ) -> Result<bool> {
+        let (Some(registry), Some(instructions)) = (registry, instructions) else {
+            return Ok(false);
+        };
+        let attester = ed25519_signer(instructions, &specs.attestation_message(device))?;
+        require!(registry.attesters.contains(&attester), ComputeError::UnknownAttester);
+        Ok(true)
+    }
+}
+
+/// A benchmark workload devices run off-chain to prove their declared performance
+#[account]
+pub struct BenchmarkSpec {
+    pub benchmark_id: u16,
+    /// Hash of the workload and its inputs, so every device runs the same job
+    pub workload_hash: [u8; 32],
+    pub active: bool,
+    pub created_at: i64,
+}
+
+impl BenchmarkSpec {
+    pub const LEN: usize = 2 + 32 + 1 + 8;
+}
+
+/// A device's latest benchmark run and the verifier attestations collected for it
+#[account]
+pub struct BenchmarkResult {
+    pub device: Pubkey,
+    pub benchmark: Pubkey,
+    pub score: u64,
+    /// Hash of the run's output, checked by verifiers re-running the workload
+    pub proof_hash: [u8; 32],
+    pub submitted_at: i64,
+    pub status: BenchmarkStatus,
+    pub approvals: u8,
+    pub rejections: u8,
+    pub attesters: Vec<Pubkey>,
+}
+
+impl BenchmarkResult {
+    pub const LEN: usize = 32 + 32 + 8 + 32 + 8 + 1 + 1 + 1 + 4 + 32 * MAX_BENCHMARK_ATTESTERS;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct TaskPreset {
+    pub task_type: TaskType,
+    pub compute_requirements: ComputeRequirements,
+    pub default_reward: u64,
+    pub verification_mode: VerificationMode,
+    pub redundancy: u8,
+    pub min_reputation: u16,
+}
+
+impl TaskPreset {
+    pub const LEN: usize = 1 + std::mem::size_of::<ComputeRequirements>() + 8 + 1 + 1 + 2;
+}
+
+/// What a submitter asks for in a new task, however it reaches the program
+#[derive(Clone, Copy)]
+pub struct Submission {
+    pub submitter: Pubkey,
+    pub reward_mint: Pubkey,
+    pub task_type: TaskType,
+    pub compute_requirements: ComputeRequirements,
+    pub reward_amount: u64,
+    pub verification_mode: VerificationMode,
+    pub redundancy: u8,
+    pub min_reputation: u16,
+    pub priority_fee: u64,
+    pub reward_kind: RewardKind,
+}
+
+/// The amounts a submission settles on once the network's terms and surge are applied
+#[derive(Clone, Copy)]
+pub struct SubmissionTerms {
+    /// The offered reward marked up by the surge
+    pub reward_amount: u64,
+    /// Reward plus priority fee, escrowed until the task settles
+    pub total: u64,
+    pub submission_fee: u64,
+    pub surge_bps: u64,
+}
+
+impl Submission {
+    pub fn submitted(&self, task: Pubkey, terms: &SubmissionTerms) -> TaskSubmitted {
+        TaskSubmitted {
+            task,
+            submitter: self.submitter,
+            task_type: self.task_type,
+            reward_mint: self.reward_mint,
+            reward_kind: self.reward_kind,
+            reward_amount: terms.reward_amount,
+            verification_mode: self.verification_mode,
+            redundancy: self.redundancy,
+            surge_bps: terms.surge_bps,
+            submission_fee: terms.submission_fee,
+            priority_fee: self.priority_fee,
+        }
+    }
+}
+
+/// A task a submitter signs off-chain for a relayer to post through submit_task_with_intent
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SubmissionIntent {
+    pub submitter: Pubkey,
+    pub task_type: TaskType,
+    pub compute_requirements: ComputeRequirements,
+    pub reward_mint: Pubkey,
+    pub reward_amount: u64,
+    pub verification_mode: VerificationMode,
+    pub redundancy: u8,
+    pub min_reputation: u16,
+    pub priority_fee: u64,
+    /// Most the submitter's credits may be debited, covering surge pricing and the submission fee
+    pub max_spend: u64,
+    /// Must equal the credit account's intent_nonce, which advances on use
+    pub nonce: u64,
+    pub expires_at: i64,
+}
+
+impl SubmissionIntent {
+    pub fn submission(&self) -> Submission {
+        Submission {
+            submitter: self.submitter,
+            reward_mint: self.reward_mint,
+            task_type: self.task_type,
+            compute_requirements: self.compute_requirements,
+            reward_amount: self.reward_amount,
+            verification_mode: self.verification_mode,
+            redundancy: self.redundancy,
+            min_reputation: self.min_reputation,
+            priority_fee: self.priority_fee,
+            reward_kind: RewardKind::Token,
+        }
+    }
+
+    /// Digest the submitter signs with Ed25519, binding the intent to this program and task id
+    pub fn message(&self, task_id: &str) -> Result<[u8; 32]> {
+        Ok(hashv(&[b"submission_intent", crate::ID.as_ref(), task_id.as_bytes(), &self.try_to_vec()?]).to_bytes())
+    }
+}
+
+/// A task template re-submitted on a fixed interval until its escrowed budget runs out
+#[account]
+pub struct RecurringTask {
+    pub submitter: Pubkey,
+    pub reward_mint: Pubkey,
+    pub recurring_id: String,
+    pub task_type: u8,
+    pub compute_requirements: ComputeRequirements,
+    pub verification_mode: u8,
+    pub reward_per_run: u64,
+    pub keeper_reward: u64,
+    pub interval: i64,
+    pub next_run_at: i64,
+    pub budget_remaining: u64,
+    pub runs: u64,
+}
+
+impl RecurringTask {
+    pub const LEN: usize = 32 + 32 + 4 + MAX_RECURRING_ID_LEN + 1 + std::mem::size_of::<ComputeRequirements>() + 1 + 8 + 8 + 8 + 8 + 8 + 8;
+
+    /// Task id of the next run, derived from the recurring task's address under RECURRING_INSTANCE_PREFIX
+    pub fn instance_id(&self, recurring_task: &Pubkey) -> String {
+        let hash = hashv(&[b"recurring_instance", recurring_task.as_ref(), &self.runs.to_le_bytes()]).to_bytes();
+        let digest: String = hash[..15].iter().map(|byte| format!("{:02x}", byte)).collect();
+        format!("{}{}", RECURRING_INSTANCE_PREFIX, digest)
+    }
+}
+
+/// An ordered chain of tasks where each stage is released once the previous one is verified
+#[account]
+pub struct PipelineAccount {
+    pub submitter: Pubkey,
+    pub pipeline_id: String,
+    pub stages: Vec<Pubkey>,
+    pub current_stage: u8,
+    pub created_at: i64,
+}
+
+impl PipelineAccount {
+    pub const LEN: usize = 32 + 4 + 32 + 4 + MAX_PIPELINE_STAGES * 32 + 1 + 8;
+}
+
+/// Task parameters for one stage of a pipeline
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PipelineStage {
+    pub task_id: String,
+    pub task_type: TaskType,
+    pub compute_requirements: ComputeRequirements,
+    pub reward_amount: u64,
+    pub verification_mode: VerificationMode,
+}
+
+/// Pending tasks of a single task type in submission order, so devices can discover work from one account
+#[account]
+pub struct PendingIndex {
+    pub task_type: u8,
+    pub tasks: Vec<Pubkey>,
+}
+
+impl PendingIndex {
+    pub const LEN: usize = 1 + 4 + MAX_PENDING_INDEX_SIZE * 32;
+
+    /// Lists a pending task; once the index is full, further tasks are only discoverable by scanning
+    pub fn push(&mut self, task: Pubkey) {
+        if self.tasks.len() < MAX_PENDING_INDEX_SIZE && !self.tasks.contains(&task) {
+            self.tasks.push(task);
+        }
+    }
+
+    pub fn remove(&mut self, task: &Pubkey) {
+        self.tasks.retain(|pending| pending != task);
+    }
+}
+
+/// Highest-priority pending tasks, ordered by priority fee (descending, FIFO among equals)
+#[account]
+pub struct PriorityIndex {
+    pub entries: Vec<PriorityEntry>,
+}
+
+impl PriorityIndex {
+    pub const LEN: usize = 4 + MAX_PRIORITY_INDEX_SIZE * PriorityEntry::LEN;
+
+    /// Tracks a pending task if it ranks among the top entries; tasks without a fee are not indexed
+    pub fn insert(&mut self, task: Pubkey, priority_fee: u64) {
+        if priority_fee == 0 {
+            return;
+        }
+        let position = self
+            .entries
+            .iter()
+            .position(|entry| entry.priority_fee < priority_fee)
+            .unwrap_or(self.entries.len());
+        if position >= MAX_PRIORITY_INDEX_SIZE {
+            return;
+        }
+        self.entries.insert(position, PriorityEntry { task, priority_fee });
+        self.entries.truncate(MAX_PRIORITY_INDEX_SIZE);
+    }
+
+    pub fn remove(&mut self, task: &Pubkey) {
+        self.entries.retain(|entry| entry.task != *task);
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct PriorityEntry {
+    pub task: Pubkey,
+    pub priority_fee: u64,
+}
+
+impl PriorityEntry {
+    pub const LEN: usize = 32 + 8;
+}
+
+/// Top devices of one epoch, ranked highest first by tasks completed and by network tokens earned
+#[account]
+pub struct Leaderboard {
+    pub epoch: u64,
+    pub by_tasks: Vec<LeaderboardEntry>,
+    pub by_tokens: Vec<LeaderboardEntry>,
+    pub prizes_paid: bool,
+}
+
+impl Leaderboard {
+    pub const LEN: usize = 8 + 2 * (4 + LEADERBOARD_SIZE * LeaderboardEntry::LEN) + 1;
+
+    /// Re-ranks the device on both boards with its latest epoch tallies
+    pub fn record(&mut self, device: Pubkey, owner: Pubkey, tasks_completed: u64, tokens_earned: u64) {
+        Self::rank(&mut self.by_tasks, LeaderboardEntry { device, owner, score: tasks_completed });
+        Self::rank(&mut self.by_tokens, LeaderboardEntry { device, owner, score: tokens_earned });
+    }
+
+    /// Moves the device to where its score now places it (after earlier equal scores), dropping
+    /// whoever falls off the end. Scores only grow within an epoch, so it never moves down.
+    fn rank(board: &mut Vec<LeaderboardEntry>, entry: LeaderboardEntry) {
+        if entry.score == 0 {
+            return;
+        }
+        board.retain(|ranked| ranked.device != entry.device);
+        let position = board
+            .iter()
+            .position(|ranked| ranked.score < entry.score)
+            .unwrap_or(board.len());
+        if position >= LEADERBOARD_SIZE {
+            return;
+        }
+        board.insert(position, entry);
+        board.truncate(LEADERBOARD_SIZE);
+    }
+
+    /// Share of `pool` for `rank` on a board of `len` devices: first place weighs `len`, last place 1
+    pub fn prize(pool: u64, rank: usize, len: usize) -> u64 {
+        let total_weight = (len * (len + 1) / 2) as u128;
+        (pool as u128 * (len - rank) as u128 / total_weight) as u64
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct LeaderboardEntry {
+    pub device: Pubkey,
+    /// Device owner when the entry was last updated, who receives its prize
+    pub owner: Pubkey,
+    pub score: u64,
+}
+
+impl LeaderboardEntry {
+    pub const LEN: usize = 32 + 32 + 8;
+}
+
+#[account]
+pub struct TaskCommittee {
+    pub task: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub seed: [u8; 32],
+    pub selected_at: i64,
+}
+
+impl TaskCommittee {
+    pub const LEN: usize = 32 + 4 + APPEAL_COMMITTEE_SIZE * 32 + 32 + 8;
+}
+
+/// A verifier's vote on a task, seeded by [task, verifier owner] so each operator votes once per task
+#[account]
+pub struct VerificationRecord {
+    pub task: Pubkey,
+    pub verifier: Pubkey,
+    pub verifier_owner: Pubkey,
+    pub commitment: [u8; 32],
+    pub revealed: bool,
+    pub is_valid: bool,
+    pub locked_stake: u64,
+    pub settled: bool,
+    pub round: u8,
+    pub reward_claimed: bool,
+    pub timestamp: i64,
+}
+
+impl VerificationRecord {
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 1 + 1 + 8 + 1 + 1 + 1 + 8;
+
+    /// hash(vote || salt || verifier), binding the commitment to the verifier so it can't be copied
+    pub fn commitment_for(is_valid: bool, salt: &[u8; 32], verifier: &Pubkey) -> [u8; 32] {
+        hashv(&[&[is_valid as u8], salt, verifier.as_ref()]).to_bytes()
+    }
+
+    /// hash(result_hash || salt || verifier), the ReExecution counterpart of commitment_for
+    pub fn result_commitment_for(result_hash: &str, salt: &[u8; 32], verifier: &Pubkey) -> [u8; 32] {
+        hashv(&[result_hash.as_bytes(), salt, verifier.as_ref()]).to_bytes()
+    }
+}
+
+/// A committee member's re-run of a ReExecution task, referencing the original task's inputs
+#[account]
+pub struct ShadowTask {
+    pub task: Pubkey,
+    pub verifier: Pubkey,
+    pub round: u8,
+    pub input_hash: [u8; 64],
+    pub task_data: Pubkey,
+    /// The verifier's own result, filled in by reveal_reexecution
+    pub result_hash: [u8; 64],
+    pub revealed: bool,
+    pub created_at: i64,
+}
+
+impl ShadowTask {
+    pub const LEN: usize = 32 + 32 + 1 + 64 + 32 + 64 + 1 + 8;
+}
+
+/// Embedded in DeviceAccount, so laid out like it: no implicit padding, flags as 0/1
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+pub struct DeviceSpecs {
+    pub network_speed: u32,
+    /// Identifier of the GPU model (first 4 bytes of sha256 of the model name), 0 without a GPU
+    pub gpu_model: u32,
+    pub storage_gb: u16,
+    /// ISO 3166-1 numeric country code the device declares it runs in, 0 if undisclosed
+    pub region: u16,
+    pub gpu_vram_gb: u16,
+    pub cpu_cores: u8,
+    pub ram_gb: u8,
+    pub gpu_available: u8,
+    /// Set by the network authority through attest_device_region, never by the owner
+    pub region_attested: u8,
+    pub platform: u8,
+    pub arch: u8,
+    /// GPU_PRECISION_* flags the GPU supports
+    pub gpu_precisions: u8,
+    /// Set when a registered attester signed these specs, never by the owner
+    pub specs_attested: u8,
+    pub padding: [u8; 2],
+}
+
+impl DeviceSpecs {
+    pub fn validate(&self) -> Result<()> {
+        enum_from_u8::<Platform>(self.platform)?;
+        enum_from_u8::<CpuArch>(self.arch)?;
+        Ok(())
+    }
+    
+    /// Hash an attester signs to vouch for `device` running this hardware; flags are excluded
+    pub fn attestation_message(&self, device: &Pubkey) -> [u8; 32] {
+        let mut declared = *self;
+        declared.region_attested = 0;
+        declared.specs_attested = 0;
+        declared.padding = [0; 2];
+        hashv(&[b"device_specs", device.as_ref(), bytemuck::bytes_of(&declared)]).to_bytes()
+    }
+    
+    /// Whether two spec sets describe the same compute hardware, so a benchmark of one holds for the other
+    pub fn same_hardware(&self, other: &DeviceSpecs) -> bool {
+        self.cpu_cores == other.cpu_cores &&
+        self.ram_gb == other.ram_gb &&
+        self.arch == other.arch &&
+        self.gpu_available == other.gpu_available &&
+        self.gpu_model == other.gpu_model &&
+        self.gpu_vram_gb == other.gpu_vram_gb &&
+        self.gpu_precisions == other.gpu_precisions
+    }
+}
+
+/// Embedded in TaskAccount, so laid out like it: no implicit padding, flags as 0/1
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+pub struct ComputeRequirements {
+    pub estimated_duration: u32,
+    /// GPU model the task is pinned to (see DeviceSpecs::gpu_model), 0 for any
+    pub required_gpu_model: u32,
+    /// Minimum device network_speed in Mbps
+    pub min_network_speed: u32,
+    /// Data the device downloads and uploads for the task, in MB
+    pub input_size_mb: u32,
+    pub output_size_mb: u32,
+    pub storage_gb_required: u16,
+    /// ISO 3166-1 numeric country code the task must run in, 0 for anywhere
+    pub required_region: u16,
+    pub min_gpu_vram_gb: u16,
+    pub cpu_cores_required: u8,
+    pub ram_gb_required: u8,
+    pub gpu_required: u8,
+    /// Only accept devices whose region has been attested
+    pub region_attestation_required: u8,
+    /// Bitmasks of acceptable platforms and CPU architectures (bit = 1 << enum value), 0 for any
+    pub allowed_platforms: u8,
+    pub allowed_archs: u8,
+    /// GPU_PRECISION_* flags the device's GPU must all support
+    pub required_gpu_precisions: u8,
+    /// Minimum battery level for devices that are not charging
+    pub min_battery_percent: u8,
+    pub charging_required: u8,
+    /// Only accept devices whose specs are signed by a registered attester
+    pub specs_attestation_required: u8,
+    /// Only accept devices with an unexpired passing integrity verdict
+    pub integrity_required: u8,
+    /// The result must be content-addressed and confirmed by prove_storage before rewards are released
+    pub storage_proof_required: u8,
+    pub padding: [u8; 2],
+}
+
+impl ComputeRequirements {
+    /// Whether `value` is among the enum values set in `mask`; an empty mask allows everything
+    pub fn mask_allows(mask: u8, value: u8) -> bool {
+        mask == 0 || mask & 1u8.checked_shl(value as u32).unwrap_or(0) != 0
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum TaskType {
+    DataProcessing,
+    MLInference,
+    ImageProcessing,
+    VideoTranscoding,
+    GeneralCompute,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum VerificationMode {
+    Committee,
+    Optimistic,
+    /// Verified by a zero-knowledge proof checked through the task's verifier program
+    ZkProof,
+    /// Committee members re-run the task through shadow tasks and reveal their own result hashes
+    ReExecution,
+}
+
+/// How a task's reward is escrowed: as tokens in its mint's reward vault, or as lamports in the
+/// task's own SOL escrow PDA
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum RewardKind {
+    Token,
+    Sol,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum TaskStatus {
+    Pending,
+    Assigned,
+    InProgress,
+    Completed,
+    Failed,
+    Cancelled,
+    Split,
+    Waiting,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum ThermalState {
+    Nominal,
+    Fair,
+    Serious,
+    Critical,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum Platform {
+    Android,
+    Ios,
+    Linux,
+    Other,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum CpuArch {
+    Arm64,
+    X86_64,
+    Other,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum BenchmarkStatus {
+    Pending,
+    Verified,
+    Rejected,
+}
+
+/// Milestones a device can claim a badge for
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum AchievementKind {
+    Tasks100,
+    Tasks1000,
+    Uptime30Days,
+}
+
+impl AchievementKind {
+    pub fn is_earned(&self, device: &DeviceAccount) -> bool {
+        match self {
+            AchievementKind::Tasks100 => device.total_tasks_completed >= 100,
+            AchievementKind::Tasks1000 => device.total_tasks_completed >= 1000,
+            AchievementKind::Uptime30Days => device.uptime_seconds >= 30 * 24 * 60 * 60,
+        }
+    }
+    
+    pub fn badge_name(&self) -> &'static str {
+        match self {
+            AchievementKind::Tasks100 => "100 Tasks Completed",
+            AchievementKind::Tasks1000 => "1000 Tasks Completed",
+            AchievementKind::Uptime30Days => "30 Days Online",
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub enum DeviceTier {
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+}
+
+impl DeviceTier {
+    pub fn from_stake(staked_amount: u64, config: &NetworkConfig) -> Self {
+        let [silver, gold, platinum] = config.tier_thresholds;
+        if staked_amount > platinum {
+            DeviceTier::Platinum
+        } else if staked_amount > gold {
+            DeviceTier::Gold
+        } else if staked_amount > silver {
+            DeviceTier::Silver
+        } else {
+            DeviceTier::Bronze
+        }
+    }
+    
+    pub fn from_benchmark(score: u64, config: &NetworkConfig) -> Self {
+        let [silver, gold, platinum] = config.tier_benchmark_scores;
+        if score >= platinum {
+            DeviceTier::Platinum
+        } else if score >= gold {
+            DeviceTier::Gold
+        } else if score >= silver {
+            DeviceTier::Silver
+        } else {
+            DeviceTier::Bronze
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum SlashReason {
+    TaskExpired,
+    FailedVerification,
+    MinorityVote,
+    Banned,
+}
+
+#[event]
+pub struct NetworkInitialized {
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct NetworkConfigUpdated {
+    pub tier_thresholds: [u64; 3],
+    pub unstake_period: i64,
+    pub expiry_multiplier: u8,
+    pub speed_bonus_bps: u64,
+    pub verification_threshold_bps: u64,
+    pub protocol_fee_bps: u64,
+    pub matcher: Pubkey,
+    pub collateral_bps: u64,
+    pub epoch_duration: i64,
+    pub epoch_emission: u64,
+    pub vesting_cliff: i64,
+    pub vesting_duration: i64,
+    pub tier_reward_bps: [u64; 4],
+    pub surge_threshold: u8,
+    pub max_surge_bps: u64,
+    pub task_type_reputation_bps: [u64; 5],
+    pub gpu_reputation_bps: u64,
+    pub reputation_reward_unit: u64,
+    pub high_value_reward: u64,
+    pub high_value_min_reputation: u16,
+    pub max_disputes_lost: u64,
+    pub submission_fee: u64,
+    pub rate_limit_window_slots: u64,
+    pub max_submissions_per_window: u64,
+    pub min_reward_by_type: [u64; 5],
+    pub max_deadline_extension: i64,
+    pub grace_period: i64,
+    pub late_reward_floor_bps: u64,
+    pub max_concurrent_by_tier: [u16; 4],
+    pub tier_benchmark_scores: [u64; 3],
+    pub storage_oracle: Pubkey,
+    pub availability_emission: u64,
+    pub availability_reward: u64,
+    pub submitter_allowlist_enabled: bool,
+    pub initial_epoch_mint: u64,
+    pub emission_decay_bps: u64,
+    pub burn_bps: u64,
+    pub usd_price_feed_id: [u8; 32],
+    pub max_price_age: i64,
+    pub max_price_conf_bps: u64,
+    pub leaderboard_prize: u64,
+}
+
+#[event]
+pub struct TokensBurned {
+    pub amount: u64,
+    pub total_burned: u64,
+}
+
+#[event]
+pub struct EmissionsMinted {
+    pub epoch: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SolFeesWithdrawn {
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RewardMintUpdated {
+    pub mint: Pubkey,
+    pub accepted: bool,
+    pub min_reward: u64,
+    pub submission_fee: u64,
+}
+
+#[event]
+pub struct VaultsInitialized {
+    pub mint: Pubkey,
+    pub reward_vault: Pubkey,
+    pub stake_vault: Pubkey,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DeviceRegistered {
+    pub device: Pubkey,
+    pub owner: Pubkey,
+    pub device_id: String,
+    pub specs: DeviceSpecs,
+}
+
+#[event]
+pub struct DeviceLicenseClaimed {
+    pub device: Pubkey,
+    pub license_mint: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+}
+
+#[event]
+pub struct PoolCreated {
+    pub pool: Pubkey,
+    pub operator: Pubkey,
+    pub commission_bps: u16,
+}
+
+#[event]
+pub struct PoolCommissionUpdated {
+    pub pool: Pubkey,
+    pub commission_bps: u16,
+}
+
+#[event]
+pub struct PoolMembershipChanged {
+    pub pool: Pubkey,
+    pub device: Pubkey,
+    pub joined: bool,
+    pub member_count: u32,
+    pub total_stake: u64,
+}
+
+#[event]
+pub struct PoolCommissionClaimed {
+    pub pool: Pubkey,
+    pub operator: Pubkey,
+    pub tokens: u64,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct DeviceMigrated {
+    pub legacy_device: Pubkey,
+    pub device: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[event]
+pub struct SubmitterAllowlistChanged {
+    pub submitter: Pubkey,
+    pub allowed: bool,
+}
+
+#[event]
+pub struct DeviceBanStatusChanged {
+    pub device: Pubkey,
+    pub banned: bool,
+    pub slashed: u64,
+}
+
+#[event]
+pub struct DeviceDeregistered {
+    pub device: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct DeviceRegionAttested {
+    pub device: Pubkey,
+    pub region: u16,
+}
+
+#[event]
+pub struct AdminCouncilUpdated {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct AdminActionProposed {
+    pub proposal: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+}
+
+#[event]
+pub struct AdminActionApproved {
+    pub proposal: Pubkey,
+    pub member: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct AdminActionExecuted {
+    pub proposal: Pubkey,
+    pub id: u64,
+}
+
+#[event]
+pub struct AdminActionCancelled {
+    pub proposal: Pubkey,
+    pub id: u64,
+}
+
+#[event]
+pub struct TimelockDelayChanged {
+    pub delay: i64,
+}
+
+#[event]
+pub struct ActionScheduled {
+    pub action: Pubkey,
+    pub id: u64,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ActionExecuted {
+    pub action: Pubkey,
+    pub id: u64,
+}
+
+#[event]
+pub struct ActionCancelled {
+    pub action: Pubkey,
+    pub id: u64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub change: ConfigChange,
+    pub voting_ends_at: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal: Pubkey,
+    pub device: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub id: u64,
+    pub change: ConfigChange,
+}
+
+#[event]
+pub struct AttesterChanged {
+    pub attester: Pubkey,
+    pub added: bool,
+}
+
+#[event]
+pub struct DeviceIntegrityAttested {
+    pub device: Pubkey,
+    pub attester: Pubkey,
+    pub verdict_hash: [u8; 32],
+    pub verified: bool,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct BenchmarkPublished {
+    pub benchmark: Pubkey,
+    pub benchmark_id: u16,
+    pub workload_hash: [u8; 32],
+}
+
+#[event]
+pub struct BenchmarkRetired {
+    pub benchmark: Pubkey,
+    pub benchmark_id: u16,
+}
+
+#[event]
+pub struct BenchmarkSubmitted {
+    pub device: Pubkey,
+    pub benchmark: Pubkey,
+    pub score: u64,
+    pub proof_hash: [u8; 32],
+}
+
+#[event]
+pub struct BenchmarkAttested {
+    pub device: Pubkey,
+    pub verifier: Pubkey,
+    pub approve: bool,
+}
+
+#[event]
+pub struct BenchmarkVerified {
+    pub device: Pubkey,
+    pub score: u64,
+    pub tier: DeviceTier,
+}
+
+#[event]
+pub struct BenchmarkRejected {
+    pub device: Pubkey,
+    pub score: u64,
+}
+
+#[event]
+pub struct DeviceSpecsUpdated {
+    pub device: Pubkey,
+    pub specs: DeviceSpecs,
+}
+
+#[event]
+pub struct DeviceStatusUpdated {
+    pub device: Pubkey,
+    pub is_active: bool,
+    pub current_load: u8,
+    pub battery_percent: u8,
+    pub is_charging: bool,
+    pub thermal_state: ThermalState,
+}
+
+#[event]
+pub struct AgentAuthoritySet {
+    pub device: Pubkey,
+    pub agent: Pubkey,
+    pub permissions: u8,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct AgentKeyRotated {
+    pub device: Pubkey,
+    pub old_agent: Pubkey,
+    pub new_agent: Pubkey,
+    pub key_epoch: u64,
+}
+
+#[event]
+pub struct AgentKeyRevoked {
+    pub device: Pubkey,
+    pub agent: Option<Pubkey>,
+    pub key_epoch: u64,
+}
+
+#[event]
+pub struct StakeChanged {
+    pub device: Pubkey,
+    pub amount: u64,
+    pub increased: bool,
+    pub staked_amount: u64,
+    pub tier: DeviceTier,
+}
+
+#[event]
+pub struct StakeReceiptsMinted {
+    pub device: Pubkey,
+    pub holder: Pubkey,
+    pub stake: u64,
+    pub receipts: u64,
+    pub receipt_supply: u64,
+    pub receipted_stake: u64,
+}
+
+#[event]
+pub struct StakeReceiptsBurned {
+    pub device: Pubkey,
+    pub holder: Pubkey,
+    pub stake: u64,
+    pub receipts: u64,
+    pub receipt_supply: u64,
+    pub receipted_stake: u64,
+}
+
+#[event]
+pub struct TaskSubmitted {
+    pub task: Pubkey,
+    pub submitter: Pubkey,
+    pub task_type: TaskType,
+    pub reward_mint: Pubkey,
+    pub reward_kind: RewardKind,
+    pub reward_amount: u64,
+    pub verification_mode: VerificationMode,
+    pub redundancy: u8,
+    pub surge_bps: u64,
+    pub submission_fee: u64,
+    pub priority_fee: u64,
+}
+
+#[event]
+pub struct CreditsDeposited {
+    pub credit_account: Pubkey,
+    pub owner: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct CreditsSpent {
+    pub credit_account: Pubkey,
+    pub task: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct CreditsWithdrawn {
+    pub credit_account: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct TaskSplit {
+    pub task: Pubkey,
+    pub subtasks: u8,
+}
+
+#[event]
+pub struct ResultsAggregated {
+    pub task: Pubkey,
+    pub result_hash: String,
+}
+
+#[event]
+pub struct PipelineCreated {
+    pub pipeline: Pubkey,
+    pub submitter: Pubkey,
+    pub stages: u8,
+    pub total_reward: u64,
+}
+
+#[event]
+pub struct PipelineAdvanced {
+    pub pipeline: Pubkey,
+    pub stage: u8,
+    pub task: Pubkey,
+    pub input_hash: String,
+}
+
+#[event]
+pub struct TaskTemplateCreated {
+    pub template: Pubkey,
+    pub owner: Pubkey,
+    pub task_type: TaskType,
+    pub default_reward: u64,
+}
+
+#[event]
+pub struct RecurringTaskCreated {
+    pub recurring_task: Pubkey,
+    pub submitter: Pubkey,
+    pub reward_per_run: u64,
+    pub interval: i64,
+    pub budget: u64,
+}
+
+#[event]
+pub struct RecurringInstanceSpawned {
+    pub recurring_task: Pubkey,
+    pub task: Pubkey,
+    pub run: u64,
+    pub keeper: Pubkey,
+    pub budget_remaining: u64,
+}
+
+#[event]
+pub struct RecurringTaskCancelled {
+    pub recurring_task: Pubkey,
+    pub runs: u64,
+    pub refund: u64,
+}
+
+#[event]
+pub struct TaskDataFinalized {
+    pub task: Pubkey,
+    pub task_data: Pubkey,
+    pub len: u32,
+    pub data_hash: [u8; 32],
+}
+
+#[event]
+pub struct UsdRewardSet {
+    pub task: Pubkey,
+    pub usd_reward_cents: u64,
+    pub max_reward: u64,
+}
+
+#[event]
+pub struct ZkVerifierSet {
+    pub task: Pubkey,
+    pub zk_verifier: Pubkey,
+    pub zk_verifying_key: Pubkey,
 }
 
-#[derive(Accounts)]
-#[instruction(device_id: String)]
-pub struct RegisterDevice<'info> {
-    #[account(
-        init,
-        payer = owner,
-        space = 8 + DeviceAccount::LEN,
-        seeds = [b"device", device_id.as_bytes()],
-        bump
-    )]
-    pub device_account: Account<'info, DeviceAccount>,
-    #[account(mut)]
-    pub network_state: Account<'info, NetworkState>,
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct StorageProved {
+    pub task: Pubkey,
+    pub oracle: Pubkey,
+    pub result_uri: String,
 }
 
-#[derive(Accounts)]
-#[instruction(task_id: String)]
-pub struct SubmitTask<'info> {
-    #[account(
-        init,
-        payer = submitter,
-        space = 8 + TaskAccount::LEN,
-        seeds = [b"task", task_id.as_bytes()],
-        bump
-    )]
-    pub task_account: Account<'info, TaskAccount>,
-    #[account(mut)]
-    pub submitter: Signer<'info>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct AvailabilityRewardClaimed {
+    pub device: Pubkey,
+    pub amount: u64,
+    pub uptime_seconds: u64,
+    pub epoch: u64,
 }
 
-#[derive(Accounts)]
-#[instruction(task_id: String)]
-pub struct AssignTask<'info> {
-    #[account(
-        mut,
-        seeds = [b"task", task_id.as_bytes()],
-        bump
-    )]
-    pub task_account: Account<'info, TaskAccount>,
-    #[account(mut)]
-    pub device_account: Account<'info, DeviceAccount>,
-    pub authority: Signer<'info>,
+#[event]
+pub struct LeaderboardPrizesPaid {
+    pub epoch: u64,
+    pub winners: u8,
+    pub amount: u64,
 }
 
-#[derive(Accounts)]
-#[instruction(task_id: String)]
-pub struct CompleteTask<'info> {
-    #[account(
-        mut,
-        seeds = [b"task", task_id.as_bytes()],
-        bump
-    )]
-    pub task_account: Account<'info, TaskAccount>,
-    #[account(mut)]
-    pub device_account: Account<'info, DeviceAccount>,
-    #[account(
-        mut,
-        seeds = [b"network_state"],
-        bump
-    )]
-    pub network_state: Account<'info, NetworkState>,
-    #[account(mut)]
-    pub reward_vault: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub device_token_account: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct AchievementAwarded {
+    pub device: Pubkey,
+    pub owner: Pubkey,
+    pub kind: AchievementKind,
+    pub merkle_tree: Pubkey,
 }
 
-#[derive(Accounts)]
-pub struct UpdateDeviceStatus<'info> {
-    #[account(
-        mut,
-        has_one = owner
-    )]
-    pub device_account: Account<'info, DeviceAccount>,
-    pub owner: Signer<'info>,
+#[event]
+pub struct AvailabilityChallengeIssued {
+    pub device: Pubkey,
+    pub nonce: [u8; 32],
+    pub deadline: i64,
 }
 
-#[derive(Accounts)]
-pub struct StakeTokens<'info> {
-    #[account(
-        mut,
-        has_one = owner
-    )]
-    pub device_account: Account<'info, DeviceAccount>,
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    #[account(mut)]
-    pub owner_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub stake_vault: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct AvailabilityChallengeResolved {
+    pub device: Pubkey,
+    pub passed: bool,
+    pub failures: u8,
+    pub deactivated: bool,
 }
 
-#[derive(Accounts)]
-pub struct UnstakeTokens<'info> {
-    #[account(
-        mut,
-        has_one = owner
-    )]
-    pub device_account: Account<'info, DeviceAccount>,
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    #[account(mut)]
-    pub owner_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub stake_vault: Account<'info, TokenAccount>,
-    #[account(
-        seeds = [b"network_state"],
-        bump
-    )]
-    pub network_state: Account<'info, NetworkState>,
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct TaskKeyPosted {
+    pub task: Pubkey,
+    pub device: Pubkey,
+    pub device_pubkey: [u8; 32],
 }
 
-#[derive(Accounts)]
-#[instruction(task_id: String)]
-pub struct VerifyTaskResult<'info> {
-    #[account(
-        mut,
-        seeds = [b"task", task_id.as_bytes()],
-        bump
-    )]
-    pub task_account: Account<'info, TaskAccount>,
-    #[account(mut)]
-    pub device_account: Account<'info, DeviceAccount>,
-    #[account(mut)]
-    pub verifier_account: Account<'info, DeviceAccount>,
-    pub verifier: Signer<'info>,
+#[event]
+pub struct EncryptionKeySet {
+    pub device: Pubkey,
+    pub encryption_pubkey: [u8; 32],
 }
 
-#[account]
-pub struct NetworkState {
-    pub authority: Pubkey,
-    pub total_devices: u32,
-    pub total_tasks_completed: u64,
-    pub total_tokens_distributed: u64,
-    pub network_utilization: u8,
+#[event]
+pub struct TaskAllowlistSet {
+    pub task: Pubkey,
+    pub allowlist_root: [u8; 32],
 }
 
-impl NetworkState {
-    pub const LEN: usize = 32 + 4 + 8 + 8 + 1;
+#[event]
+pub struct TaskCancelled {
+    pub task: Pubkey,
+    pub submitter: Pubkey,
+    pub refund: u64,
 }
 
-#[account]
-pub struct DeviceAccount {
-    pub owner: Pubkey,
-    pub device_id: String,
-    pub specs: DeviceSpecs,
-    pub is_active: bool,
+#[event]
+pub struct TaskAssigned {
+    pub task: Pubkey,
+    pub device: Pubkey,
+    pub accepted: bool,
+}
+
+#[event]
+pub struct AssignmentAccepted {
+    pub task: Pubkey,
+    pub device: Pubkey,
+}
+
+#[event]
+pub struct AssignmentRejected {
+    pub task: Pubkey,
+    pub device: Pubkey,
+}
+
+#[event]
+pub struct TaskStarted {
+    pub task: Pubkey,
+    pub device: Pubkey,
+}
+
+#[event]
+pub struct TaskProgress {
+    pub task: Pubkey,
+    pub device: Pubkey,
+    pub progress: u8,
+}
+
+#[event]
+pub struct TaskCompleted {
+    pub task: Pubkey,
+    pub device: Pubkey,
+    pub result_hash: String,
+    pub result_uri: String,
+    pub result_size_bytes: u64,
+    pub reward: u64,
+    pub protocol_fee: u64,
+    pub late_refund: u64,
+}
+
+#[event]
+pub struct RewardVested {
+    pub device: Pubkey,
+    pub amount: u64,
+    pub total: u64,
+}
+
+#[event]
+pub struct VestedClaimed {
+    pub device: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub device: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EpochAdvanced {
+    pub epoch: u64,
+    pub emission: u64,
+    pub total_weight: u64,
+    pub reward_per_share: u128,
+}
+
+#[event]
+pub struct StakingRewardsClaimed {
+    pub device: Pubkey,
+    pub amount: u64,
+    pub epoch: u64,
+}
+
+#[event]
+pub struct UnstakeRequested {
+    pub device: Pubkey,
+    pub withdrawal: Pubkey,
+    pub amount: u64,
+    pub unlock_at: i64,
+}
+
+#[event]
+pub struct UnstakeWithdrawn {
+    pub device: Pubkey,
+    pub withdrawal: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CollateralLocked {
+    pub task: Pubkey,
+    pub device: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CollateralReleased {
+    pub task: Pubkey,
+    pub device: Pubkey,
+    pub amount: u64,
+    pub forfeited: bool,
+}
+
+#[event]
+pub struct BiddingOpened {
+    pub task: Pubkey,
+    pub max_reward: u64,
+    pub bid_deadline: i64,
+}
+
+#[event]
+pub struct BidPlaced {
+    pub task: Pubkey,
+    pub device: Pubkey,
+    pub amount: u64,
+    pub eta: u32,
+}
+
+#[event]
+pub struct TaskAwarded {
+    pub task: Pubkey,
+    pub device: Pubkey,
+    pub amount: u64,
+    pub refund: u64,
+}
+
+#[event]
+pub struct BidWithdrawn {
+    pub task: Pubkey,
+    pub device: Pubkey,
+}
+
+#[event]
+pub struct TaskDeadlineExtended {
+    pub task: Pubkey,
+    pub expires_at: i64,
+    pub top_up: u64,
+    pub reward_amount: u64,
+}
+
+#[event]
+pub struct ReputationDecayed {
+    pub device: Pubkey,
+    pub decay: u16,
     pub reputation_score: u16,
-    pub total_tasks_completed: u32,
-    pub total_tokens_earned: u64,
-    pub current_load: u8,
-    pub last_active: i64,
-    pub tier: DeviceTier,
-    pub staked_amount: u64,
-    pub stake_timestamp: i64,
-    pub total_verifications: u32,
 }
 
-impl DeviceAccount {
-    pub const LEN: usize = 32 + 4 + 32 + DeviceSpecs::LEN + 1 + 2 + 4 + 8 + 1 + 8 + 1 + 8 + 8 + 4;
+#[event]
+pub struct TaskExpired {
+    pub task: Pubkey,
+    pub device: Pubkey,
+    pub keeper: Pubkey,
+    pub slashed: u64,
+    pub keeper_reward: u64,
 }
 
-#[account]
-pub struct TaskAccount {
+#[event]
+pub struct TaskRefunded {
+    pub task: Pubkey,
     pub submitter: Pubkey,
-    pub task_id: String,
-    pub task_type: TaskType,
-    pub compute_requirements: ComputeRequirements,
-    pub reward_amount: u64,
-    pub status: TaskStatus,
-    pub assigned_device: Option<Pubkey>,
+    pub refund: u64,
+    pub penalty: u64,
+}
+
+#[event]
+pub struct TaskReassigned {
+    pub task: Pubkey,
+    pub retry_count: u8,
+}
+
+#[event]
+pub struct ReplicaClaimed {
+    pub task: Pubkey,
+    pub device: Pubkey,
+    pub assigned_count: u8,
+}
+
+#[event]
+pub struct ReplicaResultSubmitted {
+    pub task: Pubkey,
+    pub device: Pubkey,
     pub result_hash: String,
-    pub created_at: i64,
-    pub assigned_at: i64,
-    pub completed_at: i64,
-    pub expires_at: i64,
-    pub verifications: u8,
-    pub valid_verifications: u8,
+}
+
+#[event]
+pub struct RedundantTaskSettled {
+    pub task: Pubkey,
+    pub agreeing_count: u8,
+    pub succeeded: bool,
+    /// Escrow of replicas that did not share the reward, returned to the submitter
+    pub refund: u64,
+}
+
+#[event]
+pub struct ReplicaRewardPaid {
+    pub task: Pubkey,
+    pub device: Pubkey,
+    pub amount: u64,
+    pub protocol_fee: u64,
+}
+
+#[event]
+pub struct VerifierPoolInitialized {
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct VerifierPoolChanged {
+    pub device: Pubkey,
+    pub joined: bool,
+}
+
+#[event]
+pub struct CommitteeSelected {
+    pub task: Pubkey,
+    pub round: u8,
+    pub members: Vec<Pubkey>,
+}
+
+#[event]
+pub struct ShadowTaskAssigned {
+    pub task: Pubkey,
+    pub shadow_task: Pubkey,
+    pub verifier: Pubkey,
+    pub round: u8,
+}
+
+#[event]
+pub struct VerificationCommitted {
+    pub task: Pubkey,
+    pub verifier: Pubkey,
+}
+
+#[event]
+pub struct VerificationRevealed {
+    pub task: Pubkey,
+    pub verifier: Pubkey,
+    pub is_valid: bool,
+}
+
+#[event]
+pub struct VerificationRoundOpened {
+    pub task: Pubkey,
+    pub commit_deadline: i64,
+    pub reveal_deadline: i64,
+}
+
+#[event]
+pub struct TaskVerified {
+    pub task: Pubkey,
+    pub device: Pubkey,
     pub is_verified: bool,
+    pub valid_verifications: u8,
+    pub verifications: u8,
 }
 
-impl TaskAccount {
-    pub const LEN: usize = 32 + 4 + 32 + 1 + ComputeRequirements::LEN + 8 + 1 + 1 + 32 + 4 + 64 + 8 + 8 + 8 + 8 + 1 + 1 + 1;
+#[event]
+pub struct VerificationSettled {
+    pub task: Pubkey,
+    pub verifier: Pubkey,
+    pub slashed: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
-pub struct DeviceSpecs {
-    pub cpu_cores: u8,
-    pub ram_gb: u8,
-    pub storage_gb: u16,
-    pub gpu_available: bool,
-    pub network_speed: u32,
+#[event]
+pub struct VerificationRewardClaimed {
+    pub task: Pubkey,
+    pub verifier: Pubkey,
+    pub amount: u64,
 }
 
-impl DeviceSpecs {
-    pub const LEN: usize = 1 + 1 + 2 + 1 + 4;
+#[event]
+pub struct ResultChallenged {
+    pub task: Pubkey,
+    pub challenger: Pubkey,
+    pub bond: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
-pub struct ComputeRequirements {
-    pub cpu_cores_required: u8,
-    pub ram_gb_required: u8,
-    pub storage_gb_required: u16,
-    pub gpu_required: bool,
-    pub estimated_duration: u32,
+#[event]
+pub struct ChallengeResolved {
+    pub task: Pubkey,
+    pub challenger: Pubkey,
+    pub upheld: bool,
 }
 
-impl ComputeRequirements {
-    pub const LEN: usize = 1 + 1 + 2 + 1 + 4;
+#[event]
+pub struct AppealFiled {
+    pub task: Pubkey,
+    pub device: Pubkey,
+    pub bond: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
-pub enum TaskType {
-    DataProcessing,
-    MLInference,
-    ImageProcessing,
-    VideoTranscoding,
-    GeneralCompute,
+#[event]
+pub struct AppealResolved {
+    pub task: Pubkey,
+    pub device: Pubkey,
+    pub overturned: bool,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
-pub enum TaskStatus {
-    Pending,
-    Assigned,
-    InProgress,
-    Completed,
-    Failed,
+#[event]
+pub struct Slashed {
+    pub device: Pubkey,
+    pub task: Pubkey,
+    pub amount: u64,
+    pub reason: SlashReason,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, PartialOrd, Debug)]
-pub enum DeviceTier {
-    Bronze,
-    Silver,
-    Gold,
-    Platinum,
+#[event]
+pub struct TaskClosed {
+    pub task: Pubkey,
+    pub task_id: String,
+    pub submitter: Pubkey,
+    pub assigned_device: Option<Pubkey>,
+    pub reward_amount: u64,
+    pub status: TaskStatus,
+    pub is_verified: bool,
+    pub result_hash: String,
+    pub completed_at: i64,
+}
+
+#[event]
+pub struct ArchiveTreeInitialized {
+    pub merkle_tree: Pubkey,
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+}
+
+#[event]
+pub struct TaskArchived {
+    pub task: Pubkey,
+    pub task_id: String,
+    pub merkle_tree: Pubkey,
+    pub leaf_index: u64,
+    pub leaf: [u8; 32],
+    /// The archived TaskAccount's bytes, which hash into `leaf`
+    pub task_data: Vec<u8>,
 }
 
 #[error_code]
@@ -603,4 +15103,406 @@ pub enum ComputeError {
     InsufficientReputation,
     #[msg("Math overflow")]
     MathOverflow,
+    #[msg("Task has not expired yet")]
+    TaskNotExpired,
+    #[msg("Device load too high to claim new tasks")]
+    DeviceOverloaded,
+    #[msg("Task has not failed")]
+    TaskNotFailed,
+    #[msg("Task reward has already been released")]
+    RewardAlreadyReleased,
+    #[msg("Treasury must be owned by the network state")]
+    InvalidTreasury,
+    #[msg("Task is not in progress")]
+    TaskNotInProgress,
+    #[msg("Progress must be between 0 and 100")]
+    InvalidProgress,
+    #[msg("Task has reached its maximum number of retries")]
+    MaxRetriesExceeded,
+    #[msg("Assignment has already been accepted")]
+    AssignmentAlreadyAccepted,
+    #[msg("Assignment has not been accepted")]
+    AssignmentNotAccepted,
+    #[msg("Acceptance window has closed")]
+    AcceptanceWindowClosed,
+    #[msg("Task reward has not been settled")]
+    TaskNotSettled,
+    #[msg("Task retention period has not elapsed")]
+    RetentionPeriodNotMet,
+    #[msg("Device still has staked tokens")]
+    DeviceStillStaked,
+    #[msg("Device has active task assignments")]
+    DeviceHasActiveTasks,
+    #[msg("Device specs were updated too recently")]
+    SpecsUpdateCooldown,
+    #[msg("Task has already been verified")]
+    TaskAlreadyVerified,
+    #[msg("Insufficient unlocked stake to verify")]
+    InsufficientVerifierStake,
+    #[msg("Verification has already been settled")]
+    VerificationAlreadySettled,
+    #[msg("Verification outcome is not final yet")]
+    VerificationNotFinalized,
+    #[msg("Commit phase has closed")]
+    CommitPhaseClosed,
+    #[msg("Reveal phase has not started")]
+    RevealPhaseNotOpen,
+    #[msg("Reveal phase has closed")]
+    RevealPhaseClosed,
+    #[msg("Reveal phase has not closed yet")]
+    RevealPhaseNotClosed,
+    #[msg("Vote has already been revealed")]
+    VoteAlreadyRevealed,
+    #[msg("Revealed vote does not match commitment")]
+    CommitmentMismatch,
+    #[msg("Verification record belongs to a different verifier")]
+    VerifierMismatch,
+    #[msg("Verifier signer does not own the verifier device")]
+    VerifierNotOwner,
+    #[msg("Devices cannot verify their own or their owner's work")]
+    SelfVerification,
+    #[msg("Device is already in the verifier pool")]
+    AlreadyInVerifierPool,
+    #[msg("Device is not in the verifier pool")]
+    NotInVerifierPool,
+    #[msg("Verifier pool is full")]
+    VerifierPoolFull,
+    #[msg("Not enough eligible verifiers to form a committee")]
+    NotEnoughVerifiers,
+    #[msg("Invalid slot hashes sysvar data")]
+    InvalidSlotHashes,
+    #[msg("Verifier is not a member of this task's committee")]
+    NotCommitteeMember,
+    #[msg("Task does not use optimistic verification")]
+    NotOptimistic,
+    #[msg("Task result has already been challenged")]
+    AlreadyChallenged,
+    #[msg("Challenge window has closed")]
+    ChallengeWindowClosed,
+    #[msg("Challenge window is still open")]
+    ChallengeWindowOpen,
+    #[msg("Task result has not been challenged")]
+    TaskNotChallenged,
+    #[msg("Challenge has already been resolved")]
+    ChallengeAlreadyResolved,
+    #[msg("Task has already been appealed")]
+    AlreadyAppealed,
+    #[msg("Appeal window has closed")]
+    AppealWindowClosed,
+    #[msg("Appeal has already been resolved")]
+    AppealAlreadyResolved,
+    #[msg("Verification reward has already been claimed")]
+    RewardAlreadyClaimed,
+    #[msg("Vote is not eligible for a verification reward")]
+    NotEligibleForReward,
+    #[msg("Redundancy must be between 1 and the network maximum")]
+    InvalidRedundancy,
+    #[msg("Task uses redundant execution")]
+    RedundantTask,
+    #[msg("Task does not use redundant execution")]
+    NotRedundantTask,
+    #[msg("Result has already been submitted")]
+    ResultAlreadySubmitted,
+    #[msg("Replica results are still outstanding")]
+    ResultsOutstanding,
+    #[msg("Execution records do not match the task")]
+    ExecutionMismatch,
+    #[msg("Value does not fit in its fixed-size field")]
+    FieldTooLong,
+    #[msg("Stored enum value is invalid")]
+    InvalidEnumValue,
+    #[msg("Invalid network configuration")]
+    InvalidConfig,
+    #[msg("Token account has the wrong owner or mint")]
+    InvalidTokenAccount,
+    #[msg("Signer is not authorized for this action")]
+    Unauthorized,
+    #[msg("Task collateral has not been settled yet")]
+    CollateralNotSettled,
+    #[msg("Unstake cooldown has not elapsed")]
+    UnstakeCooldownActive,
+    #[msg("Current epoch has not ended yet")]
+    EpochNotEnded,
+    #[msg("No staking rewards to claim")]
+    NoStakingRewards,
+    #[msg("No task rewards to claim")]
+    NoPendingRewards,
+    #[msg("Device still has unclaimed task rewards")]
+    UnclaimedRewards,
+    #[msg("Vesting account is required while reward vesting is enabled")]
+    VestingAccountRequired,
+    #[msg("No vested tokens to release")]
+    NothingVested,
+    #[msg("Device reputation is below the task's minimum")]
+    ReputationTooLow,
+    #[msg("Submitter has lost too many disputes")]
+    SubmitterRestricted,
+    #[msg("Submitter profile is required")]
+    SubmitterProfileRequired,
+    #[msg("Too many submissions in the current window")]
+    RateLimited,
+    #[msg("Reward is below the minimum for this task type")]
+    RewardTooLow,
+    #[msg("Deadline extension is not positive or exceeds the allowed maximum")]
+    InvalidDeadlineExtension,
+    #[msg("Task is awarded through bidding")]
+    BiddingTask,
+    #[msg("Task is not open for bidding")]
+    NotBiddingTask,
+    #[msg("Bid window must be positive")]
+    InvalidBidWindow,
+    #[msg("Bid window has closed")]
+    BidWindowClosed,
+    #[msg("Bid window is still open")]
+    BidWindowOpen,
+    #[msg("Bid must be positive, within the task's reward and promise a non-zero ETA")]
+    InvalidBid,
+    #[msg("Only the submitter can award a bid other than the lowest")]
+    NotBestBid,
+    #[msg("The leading bid cannot be withdrawn while bidding is open")]
+    LeadingBidLocked,
+    #[msg("Queue neighbour accounts do not match the pending queue")]
+    InvalidQueueLink,
+    #[msg("Tasks must be claimed in queue order")]
+    QueueOrderViolation,
+    #[msg("Batch arguments do not match the accounts passed")]
+    InvalidBatch,
+    #[msg("A task can be split into between 2 and 8 subtasks")]
+    InvalidSubtaskCount,
+    #[msg("Subtask account does not match the expected task")]
+    InvalidSubtaskAccount,
+    #[msg("Task has not been split")]
+    TaskNotSplit,
+    #[msg("Not every subtask has been verified")]
+    SubtaskNotVerified,
+    #[msg("Task account does not match its task id")]
+    TaskAddressMismatch,
+    #[msg("A pipeline needs between 1 and 8 stages")]
+    InvalidPipelineStages,
+    #[msg("Task is not the pipeline's current or next stage")]
+    StageMismatch,
+    #[msg("Current pipeline stage has not been verified")]
+    StageNotVerified,
+    #[msg("Next pipeline stage has already been released")]
+    StageNotWaiting,
+    #[msg("Interval must be positive")]
+    InvalidInterval,
+    #[msg("Budget does not cover another run")]
+    InsufficientBudget,
+    #[msg("Next run is not due yet")]
+    RunNotDue,
+    #[msg("Pending index does not belong to the task type")]
+    PendingIndexMismatch,
+    #[msg("Device is not on the task's allowlist")]
+    DeviceNotAllowed,
+    #[msg("Direct tasks only go to the device they were submitted to")]
+    DirectTask,
+    #[msg("Task was not submitted directly to a device")]
+    NotDirectTask,
+    #[msg("Direct task is still awaiting or was accepted by its device")]
+    DirectTaskActive,
+    #[msg("Device is not in the region the task requires")]
+    RegionMismatch,
+    #[msg("Device platform or architecture is not supported by the task")]
+    IncompatiblePlatform,
+    #[msg("Device GPU does not meet the task's GPU requirements")]
+    InsufficientGpu,
+    #[msg("Device network is too slow for the task's data")]
+    InsufficientBandwidth,
+    #[msg("Battery level must be between 0 and 100")]
+    InvalidBatteryLevel,
+    #[msg("Device is too hot to take new work")]
+    DeviceOverheated,
+    #[msg("Device battery does not meet the task's power requirements")]
+    InsufficientBattery,
+    #[msg("Device does not have enough free cores or memory for the task")]
+    DeviceOversubscribed,
+    #[msg("Device is already running as many tasks as its tier allows")]
+    TooManyConcurrentTasks,
+    #[msg("Benchmark has been retired")]
+    BenchmarkInactive,
+    #[msg("Benchmark score must be positive")]
+    InvalidBenchmarkScore,
+    #[msg("Device already has a benchmark awaiting attestation")]
+    BenchmarkPending,
+    #[msg("Benchmark result is not awaiting attestation")]
+    BenchmarkNotPending,
+    #[msg("Verifier already attested this benchmark")]
+    AlreadyAttested,
+    #[msg("Attester is already registered")]
+    AttesterAlreadyRegistered,
+    #[msg("Attester registry is full")]
+    AttesterRegistryFull,
+    #[msg("Signer is not a registered attester")]
+    UnknownAttester,
+    #[msg("Missing or malformed Ed25519 attestation instruction")]
+    InvalidAttestation,
+    #[msg("Task requires attested device specs")]
+    SpecsNotAttested,
+    #[msg("Task requires a device with a valid integrity verdict")]
+    IntegrityNotVerified,
+    #[msg("Device has not registered an encryption key")]
+    MissingEncryptionKey,
+    #[msg("Task data length or chunk size out of bounds")]
+    InvalidDataLength,
+    #[msg("Chunk offset does not continue the written data")]
+    InvalidDataOffset,
+    #[msg("Task data is already finalized")]
+    TaskDataFinalized,
+    #[msg("Task data does not match the expected hash")]
+    DataHashMismatch,
+    #[msg("Result URI exceeds the maximum length")]
+    ResultUriTooLong,
+    #[msg("Result URI must be an ipfs://, ar:// or https:// location")]
+    InvalidResultUri,
+    #[msg("Result MIME type is malformed")]
+    InvalidResultMime,
+    #[msg("Result URI does not contain a valid IPFS CID or Arweave id")]
+    InvalidContentId,
+    #[msg("Result storage has not been proven yet")]
+    StorageNotProven,
+    #[msg("Result storage is already proven")]
+    StorageAlreadyProven,
+    #[msg("Zk proof verification is only available for MLInference tasks")]
+    ZkNotSupported,
+    #[msg("Task is not verified by zk proof")]
+    NotZkTask,
+    #[msg("Verifier program or verifying key does not match the task")]
+    ZkVerifierMismatch,
+    #[msg("Task is verified by re-execution; reveal a result hash instead")]
+    ReExecutionRequired,
+    #[msg("Task is not verified by re-execution")]
+    NotReExecutionTask,
+    #[msg("Device already has an open availability challenge")]
+    AvailabilityChallengeOpen,
+    #[msg("Device was challenged too recently")]
+    AvailabilityChallengeTooSoon,
+    #[msg("Device has no open availability challenge")]
+    NoOpenAvailabilityChallenge,
+    #[msg("Availability challenge deadline has passed")]
+    AvailabilityDeadlinePassed,
+    #[msg("Availability challenge deadline has not passed yet")]
+    AvailabilityDeadlineNotReached,
+    #[msg("Availability response does not match the challenge")]
+    InvalidAvailabilityResponse,
+    #[msg("Availability reward already claimed this epoch")]
+    AvailabilityAlreadyClaimed,
+    #[msg("No availability reward to claim")]
+    NoAvailabilityReward,
+    #[msg("Device is banned")]
+    DeviceBanned,
+    #[msg("Device is not banned")]
+    DeviceNotBanned,
+    #[msg("Submitter is not on the allowlist")]
+    SubmitterNotAllowed,
+    #[msg("Council members must be unique and the threshold between 1 and the member count")]
+    InvalidCouncil,
+    #[msg("Signer is not an admin council member")]
+    NotCouncilMember,
+    #[msg("Proposed admin instruction is too large")]
+    AdminActionTooLarge,
+    #[msg("Admin action was already executed")]
+    AdminActionExecuted,
+    #[msg("Member already approved this admin action")]
+    AlreadyApproved,
+    #[msg("Admin action lacks the council's approval threshold")]
+    ThresholdNotMet,
+    #[msg("Stake is locked until the voting window it voted in closes")]
+    StakeLockedForVote,
+    #[msg("Voting on this proposal has closed")]
+    VotingClosed,
+    #[msg("Proposal was already executed")]
+    ProposalExecuted,
+    #[msg("Proposal is still in voting or its timelock")]
+    ProposalTimelocked,
+    #[msg("Proposal did not pass")]
+    ProposalNotPassed,
+    #[msg("Scheduled action's delay has not passed yet")]
+    ActionTimelocked,
+    #[msg("Emissions for this epoch were already minted")]
+    EmissionsAlreadyMinted,
+    #[msg("Reward mint is not controlled by the network")]
+    MintAuthorityMismatch,
+    #[msg("No tokens are pending burn")]
+    NothingToBurn,
+    #[msg("Mint is not accepted for task rewards")]
+    RewardMintNotAccepted,
+    #[msg("Accepted reward mint list is full")]
+    RewardMintListFull,
+    #[msg("Tasks paid in this mint need the device's token account")]
+    DeviceTokenAccountRequired,
+    #[msg("Token-paid task is missing a token account")]
+    TokenAccountRequired,
+    #[msg("SOL-paid task is missing its escrow or the submitter's wallet")]
+    SolAccountsMissing,
+    #[msg("Not available for SOL-paid tasks")]
+    SolRewardUnsupported,
+    #[msg("Amount exceeds the SOL fees held")]
+    InsufficientSolFees,
+    #[msg("No USD price feed is configured")]
+    UsdPricingDisabled,
+    #[msg("Not a verified Pyth price update")]
+    InvalidPriceAccount,
+    #[msg("Price update is for a different feed")]
+    PriceFeedMismatch,
+    #[msg("Price update is too old")]
+    StalePrice,
+    #[msg("Price confidence interval is too wide")]
+    PriceTooUncertain,
+    #[msg("Deposit amount must be nonzero")]
+    EmptyDeposit,
+    #[msg("Not enough prepaid credits")]
+    InsufficientCredits,
+    #[msg("Agent key lacks the permission for this instruction")]
+    AgentNotPermitted,
+    #[msg("Agent authority has expired")]
+    AgentExpired,
+    #[msg("Agent key was rotated or revoked")]
+    StaleAgentKey,
+    #[msg("Intent was not signed by its submitter")]
+    InvalidIntent,
+    #[msg("Submission intent has expired")]
+    IntentExpired,
+    #[msg("Submission intent nonce is not the next one")]
+    IntentNonceMismatch,
+    #[msg("Task would cost more than the intent allows")]
+    IntentSpendExceeded,
+    #[msg("Account uses an older layout; migrate it first")]
+    AccountNeedsMigration,
+    #[msg("Account cannot be migrated by this instruction")]
+    InvalidMigration,
+    #[msg("Account already uses the current layout")]
+    AccountAlreadyCurrent,
+    #[msg("Device has not earned this achievement")]
+    AchievementNotEarned,
+    #[msg("Signer does not hold the device's license")]
+    NoDeviceLicense,
+    #[msg("License holder already owns the device")]
+    LicenseAlreadyClaimed,
+    #[msg("License mint was passed without the other license accounts")]
+    LicenseAccountsMissing,
+    #[msg("Stake receipt accounts are missing or incomplete")]
+    ReceiptAccountsMissing,
+    #[msg("Leaderboard prizes for this epoch were already paid")]
+    PrizesAlreadyPaid,
+    #[msg("Task is reserved for a pool and is routed by its operator")]
+    PoolTask,
+    #[msg("Task is not reserved for this pool")]
+    NotPoolTask,
+    #[msg("Device is not a member of this pool")]
+    DeviceNotInPool,
+    #[msg("Device belongs to a pool")]
+    DeviceInPool,
+    #[msg("Device belongs to a pool; pass its pool account")]
+    PoolAccountMissing,
+    #[msg("Commission exceeds MAX_POOL_COMMISSION_BPS")]
+    InvalidCommission,
+    #[msg("Pool has no commission to claim")]
+    NoPoolCommission,
+    #[msg("Submitter token account is required to refund the task's collateral")]
+    SubmitterAccountMissing,
+    #[msg("Verifier or replica rewards are still unclaimed")]
+    RewardsUnclaimed,
+    #[msg("Task ids starting with '~' are reserved for recurring task instances")]
+    ReservedTaskId,
 } 
\ No newline at end of file
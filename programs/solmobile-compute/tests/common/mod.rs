@@ -0,0 +1,395 @@
+//! A minimal in-process runtime for driving the program through its entrypoint.
+//!
+//! Accounts live in a `Bank` and are serialized in the loader's input format for every
+//! instruction. Syscalls are stubbed: the clock and rent sysvars come from the bank, and CPIs to
+//! the System, SPL Token and Associated Token programs are executed in process. Token Metadata
+//! CPIs are accepted without doing anything.
+
+#![allow(dead_code)]
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    sync::Once,
+};
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        entrypoint::{deserialize, ProgramResult, MAX_PERMITTED_DATA_INCREASE, NON_DUP_MARKER},
+        instruction::Instruction,
+        program_pack::Pack,
+        program_stubs::{set_syscall_stubs, SyscallStubs},
+        sysvar::instructions::{self as instructions_sysvar, BorrowedAccountMeta, BorrowedInstruction},
+    },
+    system_program,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address},
+    token::spl_token,
+};
+
+pub const LAMPORTS: u64 = 1_000_000_000_000;
+pub const START_TIME: i64 = 1_700_000_000;
+
+thread_local! {
+    static CLOCK: Cell<(u64, i64)> = const { Cell::new((1, START_TIME)) };
+    static RETURN_DATA: RefCell<Option<(Pubkey, Vec<u8>)>> = const { RefCell::new(None) };
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct AccountData {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+pub struct Bank {
+    accounts: HashMap<Pubkey, AccountData>,
+}
+
+impl Bank {
+    pub fn new() -> Self {
+        install_stubs();
+        CLOCK.with(|clock| clock.set((1, START_TIME)));
+        let mut bank = Self { accounts: HashMap::new() };
+        let programs = [
+            (system_program::ID, Pubkey::default()),
+            (spl_token::ID, bpf_loader()),
+            (associated_token::ID, bpf_loader()),
+            (solmobile_compute::TOKEN_METADATA_PROGRAM_ID, bpf_loader()),
+            (solmobile_compute::ID, bpf_loader()),
+        ];
+        for (program, loader) in programs {
+            bank.set(program, AccountData { lamports: 1, data: vec![], owner: loader, executable: true });
+        }
+        bank
+    }
+
+    pub fn now(&self) -> i64 {
+        CLOCK.with(|clock| clock.get().1)
+    }
+
+    /// Moves the clock forward, advancing the slot at the usual 400ms per slot
+    pub fn warp(&mut self, seconds: i64) {
+        CLOCK.with(|clock| {
+            let (slot, now) = clock.get();
+            clock.set((slot + (seconds as u64) * 5 / 2, now + seconds));
+        });
+    }
+
+    pub fn get(&self, key: &Pubkey) -> Option<&AccountData> {
+        self.accounts.get(key)
+    }
+
+    pub fn set(&mut self, key: Pubkey, account: AccountData) {
+        self.accounts.insert(key, account);
+    }
+
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.get(key).map_or(0, |account| account.lamports)
+    }
+
+    pub fn airdrop(&mut self, key: &Pubkey, lamports: u64) {
+        let account = self.accounts.entry(*key).or_default();
+        account.lamports += lamports;
+    }
+
+    /// A fresh system account funded for fees and rent
+    pub fn funded_keypair(&mut self) -> Pubkey {
+        let key = Pubkey::new_unique();
+        self.airdrop(&key, LAMPORTS);
+        key
+    }
+
+    pub fn account<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        let account = self.get(key).unwrap_or_else(|| panic!("account {} does not exist", key));
+        T::try_deserialize(&mut account.data.as_slice()).expect("account failed to deserialize")
+    }
+
+    /// Reads a zero-copy account
+    pub fn load<T: bytemuck::Pod>(&self, key: &Pubkey) -> T {
+        let account = self.get(key).unwrap_or_else(|| panic!("account {} does not exist", key));
+        bytemuck::pod_read_unaligned(&account.data[8..8 + std::mem::size_of::<T>()])
+    }
+
+    pub fn token_balance(&self, key: &Pubkey) -> u64 {
+        let account = self.get(key).unwrap_or_else(|| panic!("token account {} does not exist", key));
+        spl_token::state::Account::unpack(&account.data).expect("not a token account").amount
+    }
+
+    pub fn create_mint(&mut self, authority: &Pubkey, decimals: u8) -> Pubkey {
+        let mint = Pubkey::new_unique();
+        self.set(mint, AccountData {
+            lamports: Rent::default().minimum_balance(spl_token::state::Mint::LEN),
+            data: vec![0; spl_token::state::Mint::LEN],
+            owner: spl_token::ID,
+            executable: false,
+        });
+        let ix = spl_token::instruction::initialize_mint2(&spl_token::ID, &mint, authority, None, decimals).unwrap();
+        self.process(&ix).expect("mint initialization failed");
+        mint
+    }
+
+    /// The owner's associated token account for `mint`, created if needed
+    pub fn token_account(&mut self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let payer = self.funded_keypair();
+        let ix = anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            &payer,
+            owner,
+            mint,
+            &spl_token::ID,
+        );
+        self.process(&ix).expect("token account creation failed");
+        get_associated_token_address(owner, mint)
+    }
+
+    pub fn mint_to(&mut self, mint: &Pubkey, authority: &Pubkey, to: &Pubkey, amount: u64) {
+        let ix = spl_token::instruction::mint_to(&spl_token::ID, mint, to, authority, &[], amount).unwrap();
+        self.process(&ix).expect("mint_to failed");
+    }
+
+    /// Executes a single-instruction transaction. State only changes if it succeeds.
+    pub fn process(&mut self, ix: &Instruction) -> std::result::Result<(), ProgramError> {
+        self.process_transaction(std::slice::from_ref(ix))
+    }
+
+    /// Executes the last instruction of a transaction. The earlier ones are not run but are
+    /// visible through the instructions sysvar, the way precompile instructions are.
+    pub fn process_transaction(&mut self, transaction: &[Instruction]) -> std::result::Result<(), ProgramError> {
+        let (ix, preceding) = transaction.split_last().expect("empty transaction");
+        self.set_instructions_sysvar(transaction, preceding.len());
+        RETURN_DATA.with(|data| *data.borrow_mut() = None);
+
+        // The program's entrypoint wants account infos borrowed for as long as they live, so the
+        // input buffer and the infos are leaked for the duration of the test
+        let buffer: &'static mut [u64] = Box::leak(self.serialize(ix).into_boxed_slice());
+        let (program_id, infos, data) = unsafe { deserialize(buffer.as_mut_ptr() as *mut u8) };
+        let infos: &'static [AccountInfo<'static>] = Box::leak(infos.into_boxed_slice());
+        if *program_id == solmobile_compute::ID {
+            solmobile_compute::entry(program_id, infos, data)?;
+        } else {
+            invoke_builtin(program_id, infos, data)?;
+        }
+
+        for info in infos.iter().filter(|info| !info.executable) {
+            if info.lamports() == 0 {
+                self.accounts.remove(info.key);
+            } else {
+                self.accounts.insert(*info.key, AccountData {
+                    lamports: info.lamports(),
+                    data: info.data.borrow().to_vec(),
+                    owner: *info.owner,
+                    executable: false,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn set_instructions_sysvar(&mut self, transaction: &[Instruction], current: usize) {
+        let borrowed: Vec<BorrowedInstruction> = transaction.iter()
+            .map(|ix| BorrowedInstruction {
+                program_id: &ix.program_id,
+                accounts: ix.accounts.iter()
+                    .map(|meta| BorrowedAccountMeta {
+                        pubkey: &meta.pubkey,
+                        is_signer: meta.is_signer,
+                        is_writable: meta.is_writable,
+                    })
+                    .collect(),
+                data: &ix.data,
+            })
+            .collect();
+        let mut data = instructions_sysvar::construct_instructions_data(&borrowed);
+        let len = data.len();
+        data[len - 2..].copy_from_slice(&(current as u16).to_le_bytes());
+        self.set(instructions_sysvar::ID, AccountData {
+            lamports: 1,
+            data,
+            owner: anchor_lang::solana_program::sysvar::ID,
+            executable: false,
+        });
+    }
+
+    /// Lays the instruction's accounts out the way the loader passes them to a program
+    fn serialize(&self, ix: &Instruction) -> Vec<u64> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&(ix.accounts.len() as u64).to_le_bytes());
+        for (index, meta) in ix.accounts.iter().enumerate() {
+            if let Some(first) = ix.accounts[..index].iter().position(|other| other.pubkey == meta.pubkey) {
+                bytes.push(first as u8);
+                bytes.extend_from_slice(&[0; 7]);
+                continue;
+            }
+            let is_signer = ix.accounts.iter().any(|other| other.pubkey == meta.pubkey && other.is_signer);
+            let is_writable = ix.accounts.iter().any(|other| other.pubkey == meta.pubkey && other.is_writable);
+            let account = self.get(&meta.pubkey).cloned().unwrap_or_default();
+            bytes.extend_from_slice(&[NON_DUP_MARKER, is_signer as u8, is_writable as u8, account.executable as u8]);
+            bytes.extend_from_slice(&[0; 4]);
+            bytes.extend_from_slice(meta.pubkey.as_ref());
+            bytes.extend_from_slice(account.owner.as_ref());
+            bytes.extend_from_slice(&account.lamports.to_le_bytes());
+            bytes.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&account.data);
+            bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+            bytes.resize(bytes.len().next_multiple_of(8), 0);
+            bytes.extend_from_slice(&u64::MAX.to_le_bytes());
+        }
+        bytes.extend_from_slice(&(ix.data.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&ix.data);
+        bytes.extend_from_slice(ix.program_id.as_ref());
+
+        let mut words = vec![0u64; bytes.len().div_ceil(8)];
+        bytemuck::cast_slice_mut::<u64, u8>(&mut words)[..bytes.len()].copy_from_slice(&bytes);
+        words
+    }
+}
+
+impl Default for Bank {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn bpf_loader() -> Pubkey {
+    anchor_lang::solana_program::bpf_loader_upgradeable::ID
+}
+
+fn install_stubs() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        set_syscall_stubs(Box::new(Stubs));
+    });
+}
+
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_log(&self, message: &str) {
+        println!("{}", message);
+    }
+
+    fn sol_log_data(&self, _fields: &[&[u8]]) {}
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let (slot, unix_timestamp) = CLOCK.with(|clock| clock.get());
+        let clock = Clock { slot, unix_timestamp, ..Clock::default() };
+        unsafe { *(var_addr as *mut Clock) = clock };
+        0
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        0
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        RETURN_DATA.with(|data| data.borrow().clone())
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        RETURN_DATA.with(|slot| *slot.borrow_mut() = Some((Pubkey::default(), data.to_vec())));
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let mut infos = Vec::with_capacity(instruction.accounts.len());
+        for meta in &instruction.accounts {
+            let mut info = account_infos.iter()
+                .find(|info| *info.key == meta.pubkey)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?
+                .clone();
+            info.is_signer = meta.is_signer;
+            info.is_writable = meta.is_writable;
+            infos.push(info);
+        }
+        invoke_builtin(&instruction.program_id, &infos, &instruction.data)
+    }
+}
+
+/// Runs an instruction of one of the programs the bank emulates
+fn invoke_builtin(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if *program_id == system_program::ID {
+        process_system(accounts, data)
+    } else if *program_id == spl_token::ID {
+        spl_token::processor::Processor::process(program_id, accounts, data)
+    } else if *program_id == associated_token::ID {
+        process_associated_token(accounts, data)
+    } else if *program_id == solmobile_compute::TOKEN_METADATA_PROGRAM_ID {
+        Ok(())
+    } else {
+        Err(ProgramError::IncorrectProgramId)
+    }
+}
+
+fn process_system(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let u64_at = |at: usize| u64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+    let pubkey_at = |at: usize| Pubkey::new_from_array(data[at..at + 32].try_into().unwrap());
+    match u32::from_le_bytes(data[..4].try_into().unwrap()) {
+        // CreateAccount { lamports, space, owner }
+        0 => {
+            let (from, to) = (&accounts[0], &accounts[1]);
+            if to.lamports() != 0 || !to.data_is_empty() || *to.owner != system_program::ID {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            transfer(from, to, u64_at(4))?;
+            allocate(to, u64_at(12) as usize, &pubkey_at(20))
+        }
+        // Assign { owner }
+        1 => allocate(&accounts[0], accounts[0].data_len(), &pubkey_at(4)),
+        // Transfer { lamports }
+        2 => transfer(&accounts[0], &accounts[1], u64_at(4)),
+        // Allocate { space }
+        8 => allocate(&accounts[0], u64_at(4) as usize, &system_program::ID),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn transfer(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    if !from.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if *from.owner != system_program::ID || !from.data_is_empty() {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let remaining = from.lamports().checked_sub(lamports).ok_or(ProgramError::InsufficientFunds)?;
+    **from.try_borrow_mut_lamports()? = remaining;
+    **to.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+fn allocate(account: &AccountInfo, space: usize, owner: &Pubkey) -> ProgramResult {
+    if !account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    account.resize(space)?;
+    account.assign(owner);
+    Ok(())
+}
+
+/// Create and CreateIdempotent: [payer, associated account, wallet, mint, system program, token program]
+fn process_associated_token(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (payer, account, wallet, mint) = (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+    if *account.key != get_associated_token_address(wallet.key, mint.key) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if *account.owner == spl_token::ID && data.first() == Some(&1) {
+        return Ok(());
+    }
+    let space = spl_token::state::Account::LEN;
+    let rent = Rent::default().minimum_balance(space).saturating_sub(account.lamports());
+    let mut account_signer = account.clone();
+    account_signer.is_signer = true;
+    if rent > 0 {
+        transfer(payer, account, rent)?;
+    }
+    allocate(&account_signer, space, &spl_token::ID)?;
+    let init = spl_token::instruction::initialize_account3(&spl_token::ID, account.key, mint.key, wallet.key)?;
+    spl_token::processor::Processor::process(&spl_token::ID, &[account.clone(), mint.clone()], &init.data)
+}
@@ -0,0 +1,790 @@
+//! End-to-end flows run against the program's entrypoint in the in-process bank from `common`.
+
+mod common;
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{ed25519_program, instruction::Instruction, program_pack::Pack, sysvar},
+    system_program, InstructionData,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address},
+    token::spl_token,
+};
+use bytemuck::Zeroable;
+use common::{AccountData, Bank};
+use solmobile_compute::{
+    accounts, instruction, interface, ComputeError, ComputeRequirements, CreditAccount, DeviceAccount,
+    DeviceSpecs, NetworkState, RecurringTask, RewardKind, SubmissionIntent, SubmitterProfile, TaskAccount,
+    TaskStatus, TaskType, VerificationMode, CHALLENGE_WINDOW, TOKEN_METADATA_PROGRAM_ID,
+};
+
+const TASK_TYPE: TaskType = TaskType::DataProcessing;
+
+fn ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: solmobile_compute::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn error(error: ComputeError) -> ProgramError {
+    anchor_lang::error::Error::from(error).into()
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &solmobile_compute::ID).0
+}
+
+struct Device {
+    key: Pubkey,
+    owner: Pubkey,
+    license: Pubkey,
+}
+
+struct Network {
+    bank: Bank,
+    authority: Pubkey,
+    mint: Pubkey,
+}
+
+impl Network {
+    fn new() -> Self {
+        let mut bank = Bank::new();
+        let authority = bank.funded_keypair();
+        let mint = bank.create_mint(&authority, 6);
+        let mut network = Self { bank, authority, mint };
+
+        network.process(ix(
+            accounts::Initialize {
+                network_state: interface::network_state_address(),
+                network_config: interface::network_config_address(),
+                epoch_state: pda(&[b"epoch_state"]),
+                authority,
+                system_program: system_program::ID,
+            },
+            instruction::Initialize {},
+        ));
+        network.process(ix(
+            accounts::InitializeVaults {
+                network_state: interface::network_state_address(),
+                mint,
+                reward_vault: interface::reward_vault_address(&mint),
+                stake_vault: pda(&[b"stake_vault"]),
+                treasury: interface::treasury_address(&mint),
+                authority,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::InitializeVaults {},
+        ));
+        network.process(ix(
+            accounts::InitializePendingIndex {
+                pending_index: interface::pending_index_address(TASK_TYPE),
+                network_state: interface::network_state_address(),
+                authority,
+                system_program: system_program::ID,
+            },
+            instruction::InitializePendingIndex { task_type: TASK_TYPE },
+        ));
+        network.process(ix(
+            accounts::InitializePriorityIndex {
+                priority_index: interface::priority_index_address(),
+                network_state: interface::network_state_address(),
+                authority,
+                system_program: system_program::ID,
+            },
+            instruction::InitializePriorityIndex {},
+        ));
+        network
+    }
+
+    fn process(&mut self, ix: Instruction) {
+        self.bank.process(&ix).expect("instruction failed");
+    }
+
+    fn try_process(&mut self, ix: Instruction) -> std::result::Result<(), ProgramError> {
+        self.bank.process(&ix)
+    }
+
+    /// A funded wallet and its token account holding `tokens` of the network mint
+    fn user(&mut self, tokens: u64) -> (Pubkey, Pubkey) {
+        let user = self.bank.funded_keypair();
+        let token_account = self.bank.token_account(&user, &self.mint);
+        if tokens > 0 {
+            self.bank.mint_to(&self.mint, &self.authority, &token_account, tokens);
+        }
+        (user, token_account)
+    }
+
+    fn queue_tail(&self) -> Option<Pubkey> {
+        let tail = self.bank.account::<NetworkState>(&interface::network_state_address()).queue_tail;
+        (tail != Pubkey::default()).then_some(tail)
+    }
+
+    /// Accepts SOL rewards through the native mint's accepted_mints entry
+    fn accept_sol(&mut self) {
+        let native_mint = spl_token::native_mint::ID;
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            decimals: 9,
+            is_initialized: true,
+            ..Default::default()
+        }.pack_into_slice(&mut data);
+        self.bank.set(native_mint, AccountData {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: spl_token::ID,
+            executable: false,
+        });
+        self.process(ix(
+            accounts::AddRewardMint {
+                network_state: interface::network_state_address(),
+                network_config: interface::network_config_address(),
+                mint: native_mint,
+                reward_vault: interface::reward_vault_address(&native_mint),
+                treasury: interface::treasury_address(&native_mint),
+                authority: self.authority,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::AddRewardMint { min_reward: 1000, submission_fee: 5000 },
+        ));
+    }
+
+    fn register_device(&mut self, owner: Pubkey, device_id: &str) -> Device {
+        let key = pda(&[b"device", owner.as_ref(), device_id.as_bytes()]);
+        let license_mint = pda(&[b"license", key.as_ref()]);
+        let license = get_associated_token_address(&owner, &license_mint);
+        self.process(ix(
+            accounts::RegisterDevice {
+                device_account: key,
+                network_state: interface::network_state_address(),
+                attester_registry: None,
+                instructions: None,
+                owner,
+                system_program: system_program::ID,
+                license_mint,
+                license_token_account: license,
+                license_metadata: Pubkey::new_unique(),
+                license_edition: Pubkey::new_unique(),
+                token_metadata_program: TOKEN_METADATA_PROGRAM_ID,
+                token_program: spl_token::ID,
+                associated_token_program: associated_token::ID,
+            },
+            instruction::RegisterDevice {
+                device_id: device_id.to_string(),
+                device_specs: DeviceSpecs::zeroed(),
+            },
+        ));
+        Device { key, owner, license }
+    }
+
+    fn stake_ix(&self, device: &Device, owner: Pubkey, owner_token_account: Pubkey, license: Option<Pubkey>, amount: u64) -> Instruction {
+        ix(
+            accounts::StakeTokens {
+                device_account: device.key,
+                owner,
+                owner_token_account,
+                stake_vault: pda(&[b"stake_vault"]),
+                network_config: interface::network_config_address(),
+                epoch_state: pda(&[b"epoch_state"]),
+                reward_mint: self.mint,
+                token_program: spl_token::ID,
+                network_state: interface::network_state_address(),
+                receipt_mint: None,
+                receipt_token_account: None,
+                license_token_account: license,
+            },
+            instruction::StakeTokens { amount },
+        )
+    }
+
+    /// A registered device whose owner has staked `stake` network tokens
+    fn staked_device(&mut self, device_id: &str, stake: u64) -> Device {
+        let (owner, token_account) = self.user(stake);
+        let device = self.register_device(owner, device_id);
+        self.process(self.stake_ix(&device, owner, token_account, Some(device.license), stake));
+        device
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn submit_task_ix(
+        &self,
+        submitter: Pubkey,
+        submitter_token_account: Option<Pubkey>,
+        task_id: &str,
+        reward_amount: u64,
+        verification_mode: VerificationMode,
+        redundancy: u8,
+        reward_kind: RewardKind,
+    ) -> Instruction {
+        let task = interface::task_address(task_id);
+        let reward_mint = match reward_kind {
+            RewardKind::Token => self.mint,
+            RewardKind::Sol => spl_token::native_mint::ID,
+        };
+        ix(
+            accounts::SubmitTask {
+                task_account: task,
+                submitter,
+                submitter_profile: interface::submitter_profile_address(&submitter),
+                submitter_token_account,
+                credit_account: None,
+                sol_escrow: (reward_kind == RewardKind::Sol).then(|| interface::sol_escrow_address(&task)),
+                reward_vault: interface::reward_vault_address(&reward_mint),
+                queue_tail: self.queue_tail(),
+                pending_index: interface::pending_index_address(TASK_TYPE),
+                priority_index: interface::priority_index_address(),
+                network_state: interface::network_state_address(),
+                network_config: interface::network_config_address(),
+                treasury: interface::treasury_address(&reward_mint),
+                reward_mint,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                payer: submitter,
+            },
+            instruction::SubmitTask {
+                task_id: task_id.to_string(),
+                task_type: TASK_TYPE,
+                compute_requirements: ComputeRequirements::zeroed(),
+                reward_amount,
+                verification_mode,
+                redundancy,
+                min_reputation: 0,
+                priority_fee: 0,
+                reward_kind,
+            },
+        )
+    }
+
+    fn close_task_ix(&self, submitter: Pubkey, task_id: &str, sol: bool) -> Instruction {
+        let task = interface::task_address(task_id);
+        ix(
+            accounts::CloseTask {
+                task_account: task,
+                submitter,
+                sol_escrow: sol.then(|| interface::sol_escrow_address(&task)),
+                network_state: interface::network_state_address(),
+                system_program: system_program::ID,
+            },
+            instruction::CloseTask { task_id: task_id.to_string() },
+        )
+    }
+
+    fn task(&self, task_id: &str) -> TaskAccount {
+        self.bank.load(&interface::task_address(task_id))
+    }
+
+    fn device(&self, device: &Device) -> DeviceAccount {
+        self.bank.load(&device.key)
+    }
+}
+
+fn execution_address(task: &Pubkey, device: &Pubkey) -> Pubkey {
+    pda(&[b"execution", task.as_ref(), device.as_ref()])
+}
+
+#[test]
+fn redundant_task_settles_then_pays_claims_before_closing() {
+    let mut network = Network::new();
+    let (submitter, submitter_tokens) = network.user(10_000);
+    let devices: Vec<Device> = (0..3).map(|i| network.staked_device(&format!("replica-{}", i), 1_000)).collect();
+
+    let task_id = "redundant";
+    let task = interface::task_address(task_id);
+    network.process(network.submit_task_ix(
+        submitter, Some(submitter_tokens), task_id, 300, VerificationMode::Optimistic, 3, RewardKind::Token,
+    ));
+    // Reward 300 and the submission fee of 10
+    assert_eq!(network.bank.token_balance(&submitter_tokens), 9_690);
+
+    for (device, result) in devices.iter().zip(["A", "A", "B"]) {
+        network.process(ix(
+            accounts::ClaimRedundantTask {
+                task_account: task,
+                device_account: device.key,
+                execution: execution_address(&task, &device.key),
+                queue_prev: None,
+                queue_next: None,
+                pending_index: interface::pending_index_address(TASK_TYPE),
+                priority_index: interface::priority_index_address(),
+                network_state: interface::network_state_address(),
+                network_config: interface::network_config_address(),
+                owner: device.owner,
+                system_program: system_program::ID,
+                license_token_account: Some(device.license),
+            },
+            instruction::ClaimRedundantTask { task_id: task_id.to_string(), allowlist_proof: vec![] },
+        ));
+        // Each replica bonds half of its 100 token slice
+        assert_eq!(network.device(device).locked_collateral, 50);
+        network.process(ix(
+            accounts::SubmitRedundantResult {
+                task_account: task,
+                device_account: device.key,
+                execution: execution_address(&task, &device.key),
+                network_state: interface::network_state_address(),
+                owner: device.owner,
+                license_token_account: Some(device.license),
+            },
+            instruction::SubmitRedundantResult { task_id: task_id.to_string(), result_hash: result.to_string() },
+        ));
+    }
+    assert_eq!(network.task(task_id).collateral, 150);
+    assert_eq!(
+        network.try_process(network.close_task_ix(submitter, task_id, false)),
+        Err(error(ComputeError::TaskNotSettled))
+    );
+
+    let mut settle = ix(
+        accounts::SettleRedundantTask {
+            task_account: task,
+            submitter_profile: interface::submitter_profile_address(&submitter),
+            submitter_token_account: Some(submitter_tokens),
+            network_state: interface::network_state_address(),
+            network_config: interface::network_config_address(),
+            reward_vault: interface::reward_vault_address(&network.mint),
+            stake_vault: pda(&[b"stake_vault"]),
+            treasury: interface::treasury_address(&network.mint),
+            reward_mint: network.mint,
+            network_mint: network.mint,
+            token_program: spl_token::ID,
+        },
+        instruction::SettleRedundantTask { task_id: task_id.to_string() },
+    );
+    for device in &devices {
+        settle.accounts.push(AccountMeta::new(execution_address(&task, &device.key), false));
+        settle.accounts.push(AccountMeta::new(device.key, false));
+    }
+    network.process(settle);
+
+    // The outvoted replica's collateral and its unearned slice both go back to the submitter
+    assert_eq!(network.bank.token_balance(&submitter_tokens), 9_690 + 50 + 100);
+    let settled = network.task(task_id);
+    assert_eq!(settled.status, TaskStatus::Completed as u8);
+    assert_eq!((settled.collateral, settled.agreeing_count), (0, 2));
+    for (device, stake) in devices.iter().zip([1_000, 1_000, 950]) {
+        let device_account = network.device(device);
+        assert_eq!((device_account.locked_collateral, device_account.staked_amount), (0, stake));
+    }
+
+    // Shares are still owed to the agreeing replicas, so the task can't be closed yet
+    assert_eq!(
+        network.try_process(network.close_task_ix(submitter, task_id, false)),
+        Err(error(ComputeError::RewardsUnclaimed))
+    );
+
+    let claim = |network: &Network, device: &Device| ix(
+        accounts::ClaimRedundantReward {
+            task_account: task,
+            device_account: device.key,
+            execution: execution_address(&task, &device.key),
+            vesting_account: None,
+            network_state: interface::network_state_address(),
+            network_config: interface::network_config_address(),
+            reward_vault: interface::reward_vault_address(&network.mint),
+            treasury: interface::treasury_address(&network.mint),
+            owner: device.owner,
+            device_token_account: None,
+            reward_mint: network.mint,
+            token_program: spl_token::ID,
+            license_token_account: Some(device.license),
+        },
+        instruction::ClaimRedundantReward { task_id: task_id.to_string() },
+    );
+    assert_eq!(
+        network.try_process(claim(&network, &devices[2])),
+        Err(error(ComputeError::NotEligibleForReward))
+    );
+    network.process(claim(&network, &devices[0]));
+    assert_eq!(
+        network.try_process(claim(&network, &devices[0])),
+        Err(error(ComputeError::RewardAlreadyClaimed))
+    );
+    assert_eq!(
+        network.try_process(network.close_task_ix(submitter, task_id, false)),
+        Err(error(ComputeError::RewardsUnclaimed))
+    );
+    network.process(claim(&network, &devices[1]));
+    // 100 token slice less the 1% protocol fee
+    assert_eq!(network.device(&devices[0]).pending_rewards, 99);
+    assert_eq!(network.device(&devices[1]).pending_rewards, 99);
+
+    let task_rent = network.bank.lamports(&task);
+    let submitter_lamports = network.bank.lamports(&submitter);
+    network.process(network.close_task_ix(submitter, task_id, false));
+    assert!(network.bank.get(&task).is_none());
+    assert_eq!(network.bank.lamports(&submitter), submitter_lamports + task_rent);
+}
+
+#[test]
+fn recurring_instance_ids_cannot_be_squatted() {
+    let mut network = Network::new();
+    let (submitter, submitter_tokens) = network.user(10_000);
+    let (keeper, keeper_tokens) = network.user(0);
+    let (attacker, attacker_tokens) = network.user(10_000);
+
+    let recurring_id = "hourly";
+    let recurring = interface::recurring_task_address(&submitter, recurring_id);
+    network.process(ix(
+        accounts::CreateRecurringTask {
+            recurring_task: recurring,
+            submitter,
+            submitter_profile: interface::submitter_profile_address(&submitter),
+            submitter_token_account: submitter_tokens,
+            reward_vault: interface::reward_vault_address(&network.mint),
+            network_config: interface::network_config_address(),
+            network_state: interface::network_state_address(),
+            reward_mint: network.mint,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::CreateRecurringTask {
+            recurring_id: recurring_id.to_string(),
+            task_type: TASK_TYPE,
+            compute_requirements: ComputeRequirements::zeroed(),
+            verification_mode: VerificationMode::Optimistic,
+            reward_per_run: 100,
+            keeper_reward: 10,
+            interval: 3600,
+            budget: 1_000,
+        },
+    ));
+
+    let spawn = |network: &Network, task_id: &str| ix(
+        accounts::SpawnRecurringInstance {
+            recurring_task: recurring,
+            task_account: interface::task_address(task_id),
+            keeper,
+            keeper_token_account: keeper_tokens,
+            reward_vault: interface::reward_vault_address(&network.mint),
+            queue_tail: network.queue_tail(),
+            pending_index: interface::pending_index_address(TASK_TYPE),
+            network_state: interface::network_state_address(),
+            reward_mint: network.mint,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::SpawnRecurringInstance { recurring_id: recurring_id.to_string(), task_id: task_id.to_string() },
+    );
+    let instance_id = |network: &Network| network.bank.account::<RecurringTask>(&recurring).instance_id(&recurring);
+
+    // Front-running the next instance with a regular submission is rejected
+    let first = instance_id(&network);
+    assert_eq!(
+        network.try_process(network.submit_task_ix(
+            attacker, Some(attacker_tokens), &first, 100, VerificationMode::Optimistic, 1, RewardKind::Token,
+        )),
+        Err(error(ComputeError::ReservedTaskId))
+    );
+    assert!(network.bank.get(&interface::task_address(&first)).is_none());
+
+    // Keepers can only spawn at the instance's own address
+    assert_eq!(
+        network.try_process(spawn(&network, "~not-the-instance")),
+        Err(error(ComputeError::TaskAddressMismatch))
+    );
+    network.process(spawn(&network, &first));
+    assert_eq!(network.task(&first).submitter, submitter);
+    assert_eq!(network.bank.token_balance(&keeper_tokens), 10);
+
+    let second = instance_id(&network);
+    assert_ne!(first, second);
+    assert_eq!(
+        network.try_process(spawn(&network, &second)),
+        Err(error(ComputeError::RunNotDue))
+    );
+    network.bank.warp(3600);
+    network.process(spawn(&network, &second));
+    let recurring_task = network.bank.account::<RecurringTask>(&recurring);
+    assert_eq!((recurring_task.runs, recurring_task.budget_remaining), (2, 780));
+}
+
+/// Ed25519 program instruction carrying a single signature over `message` by `signer`. The
+/// program only reads the signer and message back; the precompile itself checks the signature.
+fn ed25519_instruction(signer: &Pubkey, message: &[u8]) -> Instruction {
+    const KEY_OFFSET: u16 = 16;
+    const SIGNATURE_OFFSET: u16 = KEY_OFFSET + 32;
+    const MESSAGE_OFFSET: u16 = SIGNATURE_OFFSET + 64;
+    let mut data = vec![1, 0];
+    for value in [SIGNATURE_OFFSET, u16::MAX, KEY_OFFSET, u16::MAX, MESSAGE_OFFSET, message.len() as u16, u16::MAX] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(&[0; 64]);
+    data.extend_from_slice(message);
+    Instruction { program_id: ed25519_program::ID, accounts: vec![], data }
+}
+
+#[test]
+fn submission_intents_cannot_be_replayed() {
+    let mut network = Network::new();
+    let submitter = Pubkey::new_unique();
+    let (sponsor, sponsor_tokens) = network.user(10_000);
+    let relayer = network.bank.funded_keypair();
+    let credit_account = interface::credit_account_address(&submitter, &network.mint);
+
+    network.process(ix(
+        accounts::DepositCredits {
+            credit_account,
+            depositor: sponsor,
+            depositor_token_account: sponsor_tokens,
+            reward_vault: interface::reward_vault_address(&network.mint),
+            network_state: interface::network_state_address(),
+            network_config: interface::network_config_address(),
+            reward_mint: network.mint,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::DepositCredits { beneficiary: submitter, amount: 1_000 },
+    ));
+
+    let mint = network.mint;
+    let intent = |nonce: u64, now: i64| SubmissionIntent {
+        submitter,
+        task_type: TASK_TYPE,
+        compute_requirements: ComputeRequirements::zeroed(),
+        reward_mint: mint,
+        reward_amount: 200,
+        verification_mode: VerificationMode::Optimistic,
+        redundancy: 1,
+        min_reputation: 0,
+        priority_fee: 0,
+        max_spend: 400,
+        nonce,
+        expires_at: now + 600,
+    };
+    let submit = |network: &mut Network, signer: &Pubkey, task_id: &str, intent: SubmissionIntent| {
+        let message = intent.message(task_id).unwrap();
+        let submit = ix(
+            accounts::SubmitTaskWithIntent {
+                task_account: interface::task_address(task_id),
+                submitter_profile: interface::submitter_profile_address(&submitter),
+                credit_account,
+                reward_vault: interface::reward_vault_address(&network.mint),
+                queue_tail: network.queue_tail(),
+                pending_index: interface::pending_index_address(TASK_TYPE),
+                priority_index: interface::priority_index_address(),
+                network_state: interface::network_state_address(),
+                network_config: interface::network_config_address(),
+                treasury: interface::treasury_address(&network.mint),
+                reward_mint: network.mint,
+                instructions: sysvar::instructions::ID,
+                relayer,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::SubmitTaskWithIntent { task_id: task_id.to_string(), intent },
+        );
+        network.bank.process_transaction(&[ed25519_instruction(signer, &message), submit])
+    };
+
+    let now = network.bank.now();
+    assert_eq!(
+        submit(&mut network, &Pubkey::new_unique(), "intent-1", intent(0, now)),
+        Err(error(ComputeError::InvalidIntent))
+    );
+    submit(&mut network, &submitter, "intent-1", intent(0, now)).unwrap();
+    let credits = network.bank.account::<CreditAccount>(&credit_account);
+    // Reward 200 and the submission fee of 10
+    assert_eq!((credits.balance, credits.intent_nonce), (790, 1));
+    assert_eq!(network.task("intent-1").submitter, submitter);
+
+    // The same signed terms can't be posted again under another task id
+    assert_eq!(
+        submit(&mut network, &submitter, "intent-2", intent(0, now)),
+        Err(error(ComputeError::IntentNonceMismatch))
+    );
+    assert!(network.bank.get(&interface::task_address("intent-2")).is_none());
+    submit(&mut network, &submitter, "intent-2", intent(1, now)).unwrap();
+    // The queued first task pushes utilization up, so the second one is surge priced
+    let surged_reward = network.task("intent-2").reward_amount;
+    assert!(surged_reward > 200);
+    let credits = network.bank.account::<CreditAccount>(&credit_account);
+    assert_eq!((credits.balance, credits.intent_nonce), (790 - surged_reward - 10, 2));
+
+    let profile = network.bank.account::<SubmitterProfile>(&interface::submitter_profile_address(&submitter));
+    assert_eq!((profile.tasks_submitted, profile.total_rewards_paid), (2, 200 + surged_reward));
+}
+
+#[test]
+fn device_control_follows_the_license() {
+    let mut network = Network::new();
+    let (alice, alice_tokens) = network.user(1_000);
+    let (bob, bob_tokens) = network.user(1_000);
+    let device = network.register_device(alice, "phone");
+    let license_mint = network.device(&device).license_mint;
+    assert_eq!(network.bank.token_balance(&device.license), 1);
+
+    network.process(network.stake_ix(&device, alice, alice_tokens, Some(device.license), 400));
+    assert_eq!(
+        network.try_process(network.stake_ix(&device, alice, alice_tokens, None, 100)),
+        Err(error(ComputeError::NoDeviceLicense))
+    );
+
+    // Selling the license NFT hands over the device
+    let bob_license = network.bank.token_account(&bob, &license_mint);
+    let transfer = spl_token::instruction::transfer(&spl_token::ID, &device.license, &bob_license, &alice, &[], 1).unwrap();
+    network.bank.process(&transfer).unwrap();
+    assert_eq!(
+        network.try_process(network.stake_ix(&device, alice, alice_tokens, Some(device.license), 100)),
+        Err(error(ComputeError::NoDeviceLicense))
+    );
+
+    let claim = |new_owner: Pubkey, license_token_account: Pubkey| ix(
+        accounts::ClaimDeviceLicense { device_account: device.key, license_token_account, new_owner },
+        instruction::ClaimDeviceLicense {},
+    );
+    assert_eq!(
+        network.try_process(claim(alice, device.license)),
+        Err(error(ComputeError::NoDeviceLicense))
+    );
+    network.process(claim(bob, bob_license));
+    assert_eq!(network.device(&device).owner, bob);
+    assert_eq!(
+        network.try_process(claim(bob, bob_license)),
+        Err(error(ComputeError::LicenseAlreadyClaimed))
+    );
+
+    network.process(network.stake_ix(&device, bob, bob_tokens, Some(bob_license), 100));
+    assert_eq!(network.device(&device).staked_amount, 500);
+}
+
+#[test]
+fn sol_escrows_are_refunded_and_closed_in_full() {
+    let mut network = Network::new();
+    network.accept_sol();
+    let submitter = network.bank.funded_keypair();
+    let submission_fee = 5000;
+    // Kept under high_value_reward, which would call for a more reputable device
+    let reward = 8_000;
+    let profile_address = interface::submitter_profile_address(&submitter);
+
+    // Cancelling hands back the whole escrow, rent deposit included
+    let start = network.bank.lamports(&submitter);
+    network.process(network.submit_task_ix(submitter, None, "sol-cancel", reward, VerificationMode::Optimistic, 1, RewardKind::Sol));
+    let task = interface::task_address("sol-cancel");
+    let escrow = interface::sol_escrow_address(&task);
+    assert_eq!(network.bank.lamports(&escrow), reward + Rent::default().minimum_balance(0));
+    let profile_rent = network.bank.lamports(&profile_address);
+    network.process(ix(
+        accounts::CancelTask {
+            task_account: task,
+            submitter,
+            submitter_profile: profile_address,
+            submitter_token_account: None,
+            sol_escrow: Some(escrow),
+            reward_vault: interface::reward_vault_address(&spl_token::native_mint::ID),
+            queue_prev: None,
+            queue_next: None,
+            pending_index: interface::pending_index_address(TASK_TYPE),
+            priority_index: interface::priority_index_address(),
+            network_state: interface::network_state_address(),
+            reward_mint: spl_token::native_mint::ID,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::CancelTask { task_id: "sol-cancel".to_string() },
+    ));
+    assert!(network.bank.get(&escrow).is_none());
+    assert!(network.bank.get(&task).is_none());
+    assert_eq!(network.bank.lamports(&submitter), start - submission_fee - profile_rent);
+    let profile = network.bank.account::<SubmitterProfile>(&profile_address);
+    assert_eq!((profile.tasks_cancelled, profile.total_rewards_paid), (1, 0));
+
+    // A completed task's escrow keeps the verification fee until the task is closed
+    let device = network.staked_device("sol-runner", reward);
+    let task_id = "sol-close";
+    let task = interface::task_address(task_id);
+    let escrow = interface::sol_escrow_address(&task);
+    network.process(network.submit_task_ix(submitter, None, task_id, reward, VerificationMode::Optimistic, 1, RewardKind::Sol));
+    network.process(ix(
+        accounts::ClaimTask {
+            task_account: task,
+            device_account: device.key,
+            queue_head: None,
+            queue_prev: None,
+            queue_next: None,
+            pending_index: interface::pending_index_address(TASK_TYPE),
+            priority_index: interface::priority_index_address(),
+            network_state: interface::network_state_address(),
+            network_config: interface::network_config_address(),
+            owner: device.owner,
+            license_token_account: Some(device.license),
+        },
+        instruction::ClaimTask { task_id: task_id.to_string(), allowlist_proof: vec![] },
+    ));
+    network.process(ix(
+        accounts::StartTask {
+            task_account: task,
+            device_account: device.key,
+            agent_authority: None,
+            authority: device.owner,
+            license_token_account: Some(device.license),
+        },
+        instruction::StartTask { task_id: task_id.to_string() },
+    ));
+    let owner_lamports = network.bank.lamports(&device.owner);
+    network.process(ix(
+        accounts::CompleteTask {
+            task_account: task,
+            device_account: device.key,
+            vesting_account: None,
+            network_state: interface::network_state_address(),
+            network_config: interface::network_config_address(),
+            reward_vault: interface::reward_vault_address(&spl_token::native_mint::ID),
+            treasury: interface::treasury_address(&spl_token::native_mint::ID),
+            submitter_token_account: None,
+            sol_escrow: Some(escrow),
+            owner: device.owner,
+            agent_authority: None,
+            authority: device.owner,
+            submitter: Some(submitter),
+            price_update: None,
+            device_token_account: None,
+            reward_mint: spl_token::native_mint::ID,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+            epoch_state: pda(&[b"epoch_state"]),
+            leaderboard: None,
+            pool: None,
+            license_token_account: Some(device.license),
+        },
+        instruction::CompleteTask {
+            task_id: task_id.to_string(),
+            result_hash: "result".to_string(),
+            result_uri: "https://results.example/sol-close".to_string(),
+            result_size_bytes: 1,
+            result_mime: "application/json".to_string(),
+        },
+    ));
+    // 5% verification fee stays escrowed and 1% goes to the protocol
+    let verification_pool = reward / 20;
+    assert_eq!(network.bank.lamports(&device.owner), owner_lamports + reward - verification_pool - reward / 100);
+    assert_eq!(network.bank.lamports(&escrow), verification_pool + Rent::default().minimum_balance(0));
+
+    // The device's collateral has to be released before the task can be closed
+    assert_eq!(
+        network.try_process(network.close_task_ix(submitter, task_id, true)),
+        Err(error(ComputeError::CollateralNotSettled))
+    );
+    network.bank.warp(CHALLENGE_WINDOW + 1);
+    network.process(ix(
+        accounts::AcceptOptimisticResult {
+            task_account: task,
+            device_account: device.key,
+            network_config: interface::network_config_address(),
+        },
+        instruction::AcceptOptimisticResult { task_id: task_id.to_string() },
+    ));
+    assert_eq!(network.device(&device).locked_collateral, 0);
+
+    let submitter_lamports = network.bank.lamports(&submitter);
+    let returned = network.bank.lamports(&task) + network.bank.lamports(&escrow);
+    network.process(network.close_task_ix(submitter, task_id, true));
+    assert!(network.bank.get(&escrow).is_none());
+    assert!(network.bank.get(&task).is_none());
+    assert_eq!(network.bank.lamports(&submitter), submitter_lamports + returned);
+}